@@ -8,9 +8,10 @@ use specs::{Entity, saveload::{ConvertSaveload, Marker}, error::NoError};
 use serde::{Serialize, Deserialize};
 use rltk::{RGB, Point, FontCharType};
 use crate::gamelog::LogFragment;
-use super::{attr_bonus, Map, MasterDungeonMap};
+use super::{attr_bonus, HazardType, Map, MasterDungeonMap};
 use std::{collections::{BTreeMap, HashMap}, convert::Infallible};
 use crate::effects::{EffectType, Targets};
+use crate::raws::Reaction;
 
 #[derive(Component, Serialize, Deserialize, Clone, Debug)]
 pub struct Position {
@@ -83,19 +84,43 @@ pub struct Item {
     pub initiative_penalty: f32,
     pub weight_lbs: f32,
     pub base_value: i32,
+    pub affix_value: i32,
     pub class: ItemClass,
     pub quality: ItemQuality,
-    pub vendor_category: Option<String>
+    pub vendor_category: Option<String>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub unidentified_name: Option<String>
 }
 
 impl Item {
+    // affixes only add to the price once the item is identified
+    pub fn value(&self) -> i32 {
+        if self.unidentified_name.is_some() {
+            self.base_value
+        } else {
+            self.base_value + self.affix_value
+        }
+    }
+
     pub fn full_name(&self) -> String {
+        if let Some(unidentified_name) = &self.unidentified_name {
+            return unidentified_name.clone();
+        }
+
+        let mut name = self.name.clone();
+        if let Some(prefix) = &self.prefix {
+            name = format!("{} {}", prefix, name);
+        }
+        if let Some(suffix) = &self.suffix {
+            name = format!("{} {}", name, suffix);
+        }
         match self.quality {
-            ItemQuality::Damaged => format!("Damaged {}", self.name),
-            ItemQuality::Worn => format!("Worn {}", self.name),
-            ItemQuality::Improved => format!("Improved {}", self.name),
-            ItemQuality::Exceptional => format!("Exceptional {}", self.name),
-            _ => self.name.clone()
+            ItemQuality::Damaged => format!("Damaged {}", name),
+            ItemQuality::Worn => format!("Worn {}", name),
+            ItemQuality::Improved => format!("Improved {}", name),
+            ItemQuality::Exceptional => format!("Exceptional {}", name),
+            _ => name
         }
     }
 }
@@ -268,9 +293,20 @@ pub struct Damage {
     pub damage: String
 }
 
+// leaves a lasting hazard on the tiles it hits
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct CreatesHazard {
+    pub hazard_type: HazardType,
+    pub turns: i32
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum AoeShape { Circle, Cone, Line, Ring, Cross }
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct AreaOfEffect {
-    pub radius: i32
+    pub radius: i32,
+    pub shape: AoeShape
 }
 
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
@@ -285,6 +321,7 @@ pub struct SerializationHelper {
     pub map: Map,
     pub quests: Quests,
     pub active_quests: ActiveQuests,
+    pub clock: GameClock
 }
 
 #[derive(Component, Serialize, Deserialize, Clone)]
@@ -524,16 +561,31 @@ impl Skills {
     }
 }
 
+pub const RAGE_DECAY_PER_TURN: i32 = 1;
+pub const FOCUS_REGEN_PER_TURN: i32 = 1;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Pool {
     pub max: i32,
     pub current: i32
 }
 
+impl Pool {
+    pub fn empty() -> Pool {
+        Pool{ max: 0, current: 0 }
+    }
+
+    pub fn gain(&mut self, amount: i32) {
+        self.current = i32::clamp(self.current + amount, 0, self.max);
+    }
+}
+
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct Pools {
     pub hit_points: Pool,
     pub mana: Pool,
+    pub rage: Pool,
+    pub focus: Pool,
     pub xp: i32,
     pub level: i32,
     pub total_weight: f32,
@@ -661,12 +713,57 @@ pub struct EquipmentChanged {}
 
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct Vendor {
-    pub category: String
+    pub category: String,
+    pub hours: Option<(i32, i32)>
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Hireable {
+    pub cost: i32
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CompanionMode {
+    Follow,
+    Guard { x: i32, y: i32 },
+    Attack
+}
+
+// allies of the player, either hired or summoned
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Companion {
+    pub mode: CompanionMode,
+    pub summoned_by: Option<String>
 }
 
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct TownPortal {}
 
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Identify {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Gatherable {
+    pub item: String
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct CraftingStation {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Durability {
+    pub current: i32,
+    pub max: i32
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Cursed {
+    pub known: bool
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct RemoveCurse {}
+
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct EntryTrigger {}
 
@@ -694,6 +791,34 @@ pub struct ApplyTeleport {
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct SingleActivation {}
 
+// not drawn until detected
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Hidden {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Trap {
+    pub difficulty: i32
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct WantsToDisarm {
+    pub trap: Entity
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct WantsToUnlock {
+    pub target: Entity
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Pit {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Alarm {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct RandomTeleport {}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct SpawnParticleLine {
     pub glyph: FontCharType,
@@ -732,11 +857,13 @@ impl AttributeBonus {
         self.intelligence = self.combine_attribute(self.intelligence, other.unwrap().intelligence);
     }
 
+    // a bonus only one side has is kept rather than dropped
     fn combine_attribute(&self, my_attribute: Option<i32>, other_attribute: Option<i32>) -> Option<i32> {
         if my_attribute.is_some() {
             if other_attribute.is_some() {
                 return Some(my_attribute.unwrap() + other_attribute.unwrap());
             }
+            return my_attribute;
         } else if other_attribute.is_some() {
             return other_attribute;
         }
@@ -762,11 +889,13 @@ impl SkillBonus {
         self.magic = self.combine_skill(self.magic, other.unwrap().magic);
     }
 
+    // a bonus only one side has is kept rather than dropped
     fn combine_skill(&self, my_skill: Option<i32>, other_skill: Option<i32>) -> Option<i32> {
         if my_skill.is_some() {
             if other_skill.is_some() {
                 return Some(my_skill.unwrap() + other_skill.unwrap());
             }
+            return my_skill;
         } else if other_skill.is_some() {
             return other_skill;
         }
@@ -840,9 +969,59 @@ pub struct TileSize {
     pub y: i32
 }
 
+pub const ATTRIBUTE_POINTS_PER_LEVEL: i32 = 1;
+pub const SKILL_POINTS_PER_LEVEL: i32 = 2;
+pub const ATTRIBUTE_NAMES: [&str; 4] = ["Strength", "Dexterity", "Constitution", "Intelligence"];
+pub const SKILL_NAMES: [&str; 4] = ["Melee", "Defence", "Ranged", "Magic"];
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct WantsToLevelUp {
-    pub passives: BTreeMap<String, ClassPassive>
+    pub passives: BTreeMap<String, ClassPassive>,
+    pub attribute_points: i32,
+    pub skill_points: i32,
+    pub allocations: Vec<String>, // in order so they can be undone
+    pub allocating: bool,
+    pub passive_points: i32,
+    pub level_gain: bool
+}
+
+impl WantsToLevelUp {
+    pub fn new(passives: BTreeMap<String, ClassPassive>) -> WantsToLevelUp {
+        WantsToLevelUp {
+            passives,
+            attribute_points: ATTRIBUTE_POINTS_PER_LEVEL,
+            skill_points: SKILL_POINTS_PER_LEVEL,
+            allocations: Vec::new(),
+            allocating: false,
+            passive_points: 1,
+            level_gain: true
+        }
+    }
+
+    // re-spend passive levels given back by a respec
+    pub fn respec(passives: BTreeMap<String, ClassPassive>, passive_points: i32) -> WantsToLevelUp {
+        WantsToLevelUp {
+            passives,
+            attribute_points: 0,
+            skill_points: 0,
+            allocations: Vec::new(),
+            allocating: false,
+            passive_points,
+            level_gain: false
+        }
+    }
+
+    pub fn allocated(&self, name: &str) -> i32 {
+        self.allocations.iter().filter(|allocation| allocation.as_str() == name).count() as i32
+    }
+
+    pub fn remaining_attribute_points(&self) -> i32 {
+        self.attribute_points - ATTRIBUTE_NAMES.iter().map(|name| self.allocated(name)).sum::<i32>()
+    }
+
+    pub fn remaining_skill_points(&self) -> i32 {
+        self.skill_points - SKILL_NAMES.iter().map(|name| self.allocated(name)).sum::<i32>()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -967,6 +1146,10 @@ pub struct Ability {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AbilityLevel {
     pub mana_cost: Option<i32>,
+    pub rage_cost: Option<i32>,
+    pub focus_cost: Option<i32>,
+    pub cooldown: Option<i32>,
+    pub charges: Option<i32>,
     pub effects: HashMap<String, String>
 }
 
@@ -975,7 +1158,85 @@ pub struct KnownAbility {
     pub name: String,
     pub level: i32,
     pub mana_cost: i32,
-    pub ability_type: AbilityType
+    pub rage_cost: i32,
+    pub focus_cost: i32,
+    pub cooldown: i32,
+    pub max_charges: i32,
+    pub charges: i32,
+    pub recharge_turns: i32,
+    pub ability_type: AbilityType,
+    pub from_passive: bool
+}
+
+impl KnownAbility {
+    pub fn new(name: &str, ability_type: AbilityType, level: i32, level_data: &AbilityLevel, from_passive: bool) -> KnownAbility {
+        let max_charges = level_data.charges.unwrap_or(1);
+        KnownAbility {
+            name: name.to_string(),
+            level,
+            mana_cost: level_data.mana_cost.unwrap_or(0),
+            rage_cost: level_data.rage_cost.unwrap_or(0),
+            focus_cost: level_data.focus_cost.unwrap_or(0),
+            cooldown: level_data.cooldown.unwrap_or(0),
+            max_charges,
+            charges: max_charges,
+            recharge_turns: 0,
+            ability_type,
+            from_passive
+        }
+    }
+
+    pub fn set_level(&mut self, level: i32, level_data: &AbilityLevel) {
+        let max_charges = level_data.charges.unwrap_or(1);
+        self.level = level;
+        self.mana_cost = level_data.mana_cost.unwrap_or(0);
+        self.rage_cost = level_data.rage_cost.unwrap_or(0);
+        self.focus_cost = level_data.focus_cost.unwrap_or(0);
+        self.cooldown = level_data.cooldown.unwrap_or(0);
+        // extra charges are available straight away
+        self.charges = i32::max(0, self.charges + max_charges - self.max_charges);
+        self.max_charges = max_charges;
+    }
+
+    pub fn costs(&self) -> Vec<(&'static str, i32)> {
+        [("mana", self.mana_cost), ("rage", self.rage_cost), ("focus", self.focus_cost)]
+            .into_iter()
+            .filter(|cost| cost.1 > 0)
+            .collect()
+    }
+
+    pub fn missing_resource(&self, pools: &Pools) -> Option<&'static str> {
+        if pools.mana.current < self.mana_cost { return Some("mana"); }
+        if pools.rage.current < self.rage_cost { return Some("rage"); }
+        if pools.focus.current < self.focus_cost { return Some("focus"); }
+        None
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.charges > 0
+    }
+
+    pub fn spend(&mut self, pools: &mut Pools) {
+        pools.mana.current -= self.mana_cost;
+        pools.rage.current -= self.rage_cost;
+        pools.focus.current -= self.focus_cost;
+        if self.cooldown > 0 {
+            if self.charges == self.max_charges {
+                self.recharge_turns = self.cooldown;
+            }
+            self.charges -= 1;
+        }
+    }
+
+    // charges come back one at a time
+    pub fn tick_cooldown(&mut self) {
+        if self.charges >= self.max_charges { return; }
+        self.recharge_turns -= 1;
+        if self.recharge_turns < 1 {
+            self.charges += 1;
+            self.recharge_turns = if self.charges < self.max_charges { self.cooldown } else { 0 };
+        }
+    }
 }
 
 // Need a wrapper to be able to (de)serialize collections of Entities. See https://github.com/amethyst/specs/issues/681
@@ -1109,16 +1370,166 @@ pub struct Dodge {
 }
 
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
-pub struct WantsToLearnAbility {
+pub struct Summon {
+    pub mob: String
+}
+
+// totals of the modifiers from an entity's known passive abilities
+#[derive(Component, Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct PassiveBonus {
+    pub block_chance: f32,
+    pub dodge_chance: f32,
+    pub thorns: i32,
+    pub mana_regen: i32,
+    pub mana_regen_turns: i32
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AbilityToLearn {
     pub ability_name: String,
-    pub level: i32
+    pub level: i32,
+    pub from_passive: bool
+}
+
+// queued so several abilities can be learned in the same turn
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct WantsToLearnAbility {
+    pub abilities: Vec<AbilityToLearn>
+}
+
+impl WantsToLearnAbility {
+    pub fn learn(wants_learn: &mut WriteStorage<WantsToLearnAbility>, entity: Entity, ability_name: &str, level: i32, from_passive: bool) {
+        let ability = AbilityToLearn{ ability_name: ability_name.to_string(), level, from_passive };
+        if let Some(learn) = wants_learn.get_mut(entity) {
+            learn.abilities.push(ability);
+        } else {
+            wants_learn.insert(entity, WantsToLearnAbility{ abilities: vec![ability] }).expect("Unable to insert");
+        }
+    }
 }
 
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct WantsToLevelAbility {
-    pub ability_name: String
+    pub ability_names: Vec<String>
+}
+
+impl WantsToLevelAbility {
+    pub fn level(wants_level: &mut WriteStorage<WantsToLevelAbility>, entity: Entity, ability_name: &str) {
+        if let Some(level) = wants_level.get_mut(entity) {
+            level.ability_names.push(ability_name.to_string());
+        } else {
+            wants_level.insert(entity, WantsToLevelAbility{ ability_names: vec![ability_name.to_string()] }).expect("Unable to insert");
+        }
+    }
+}
+
+// one turn is a minute of game time
+pub const TURNS_PER_HOUR: i32 = 60;
+pub const TURNS_PER_DAY: i32 = TURNS_PER_HOUR * 24;
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum TimeOfDay { Dawn, Day, Dusk, Night }
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GameClock {
+    pub turn: i32
+}
+
+impl GameClock {
+    pub fn day(&self) -> i32 { self.turn / TURNS_PER_DAY + 1 }
+
+    pub fn hour(&self) -> i32 { (self.turn % TURNS_PER_DAY) / TURNS_PER_HOUR }
+
+    pub fn minute(&self) -> i32 { self.turn % TURNS_PER_HOUR }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.hour() {
+            5..=6 => TimeOfDay::Dawn,
+            7..=18 => TimeOfDay::Day,
+            19..=20 => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night
+        }
+    }
+
+    pub fn is_dark(&self) -> bool {
+        matches!(self.time_of_day(), TimeOfDay::Dusk | TimeOfDay::Night)
+    }
+
+    // brightness of outdoor maps between 0 and 1
+    pub fn daylight(&self) -> f32 {
+        const NIGHT_LIGHT: f32 = 0.2;
+        let minutes = (self.turn % TURNS_PER_DAY) as f32;
+        let transition = (2 * TURNS_PER_HOUR) as f32;
+        match self.time_of_day() {
+            TimeOfDay::Day => 1.0,
+            TimeOfDay::Night => NIGHT_LIGHT,
+            TimeOfDay::Dawn => NIGHT_LIGHT + (1.0 - NIGHT_LIGHT) * (minutes - (5 * TURNS_PER_HOUR) as f32) / transition,
+            TimeOfDay::Dusk => 1.0 - (1.0 - NIGHT_LIGHT) * (minutes - (19 * TURNS_PER_HOUR) as f32) / transition
+        }
+    }
+
+    // handles opening hours that run past midnight
+    pub fn is_between(&self, open: i32, close: i32) -> bool {
+        let hour = self.hour();
+        if open <= close { hour >= open && hour < close } else { hour >= open || hour < close }
+    }
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Nocturnal {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Swimmer {}
+
+pub const SWIM_INITIATIVE_PENALTY: i32 = 3;
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Swimming {
+    pub turns: i32
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct WaterBreathing {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Boat {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Digger {
+    pub power: i32
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Tunnels {}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Breakable {
+    pub hit_points: i32
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Smasher {}
+
+// lock id of the door on a level that the boss carries the key for
+pub const BOSS_LOCK: &str = "boss";
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Locked {
+    pub lock_id: String
 }
 
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Key {
+    pub lock_id: String
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Lockpick {}
+
+// ruined by a soaking when swimming
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct WaterDamageable {}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct Quests {
     pub quests: Vec<Quest>
@@ -1164,7 +1575,8 @@ pub enum QuestStatus {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct QuestReward {
     pub gold: Option<String>,
-    pub xp: Option<i32>
+    pub xp: Option<i32>,
+    pub remove_curses: bool
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
@@ -1241,6 +1653,19 @@ pub struct Species {
     pub name: String
 }
 
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct RacialPassive {
+    pub name: String,
+    pub description: String,
+    pub darkvision: i32,
+    pub damage_over_time_resistance: f32
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct FactionAttitudes {
+    pub reactions: HashMap<String, Reaction>
+}
+
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct Repeat {
     pub count: i32
@@ -1258,3 +1683,111 @@ pub struct RegenBonus {
     pub health: Option<i32>,
     pub mana: Option<i32>
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ability(cooldown: i32, charges: i32) -> KnownAbility {
+        let level = AbilityLevel {
+            mana_cost: None,
+            rage_cost: None,
+            focus_cost: None,
+            cooldown: Some(cooldown),
+            charges: Some(charges),
+            effects: HashMap::new()
+        };
+        KnownAbility::new("Test", AbilityType::Active, 1, &level, false)
+    }
+
+    fn pools() -> Pools {
+        Pools {
+            hit_points: Pool{ max: 10, current: 10 },
+            mana: Pool{ max: 10, current: 10 },
+            rage: Pool{ max: 0, current: 0 },
+            focus: Pool{ max: 0, current: 0 },
+            xp: 0,
+            level: 1,
+            total_weight: 0.0,
+            initiative_penalty: InitiativePenalty::initial(),
+            gold: 0,
+            total_armour_class: 10,
+            base_damage: "1d4".to_string(),
+            god_mode: false
+        }
+    }
+
+    #[test]
+    fn attribute_combine_keeps_bonuses_from_both_sides() {
+        let mut bonus = AttributeBonus{ strength: Some(2), dexterity: None, constitution: Some(1), intelligence: None };
+        bonus.combine(Some(&AttributeBonus{ strength: Some(1), dexterity: Some(3), constitution: None, intelligence: None }));
+
+        assert_eq!(bonus.strength, Some(3));
+        assert_eq!(bonus.dexterity, Some(3));
+        assert_eq!(bonus.constitution, Some(1));
+        assert_eq!(bonus.intelligence, None);
+    }
+
+    #[test]
+    fn skill_combine_keeps_bonuses_from_both_sides() {
+        let mut bonus = SkillBonus{ melee: Some(2), defence: None, ranged: Some(-1), magic: None };
+        bonus.combine(Some(&SkillBonus{ melee: Some(-3), defence: Some(1), ranged: None, magic: None }));
+
+        assert_eq!(bonus.melee, Some(-1));
+        assert_eq!(bonus.defence, Some(1));
+        assert_eq!(bonus.ranged, Some(-1));
+        assert_eq!(bonus.magic, None);
+    }
+
+    #[test]
+    fn combine_with_nothing_changes_nothing() {
+        let mut bonus = SkillBonus{ melee: Some(2), defence: None, ranged: None, magic: None };
+        bonus.combine(None);
+        assert_eq!(bonus.melee, Some(2));
+    }
+
+    #[test]
+    fn cooldown_recharges_after_its_turns() {
+        let mut known = ability(3, 1);
+        let mut pools = pools();
+
+        known.spend(&mut pools);
+        assert!(!known.is_ready());
+        known.tick_cooldown();
+        known.tick_cooldown();
+        assert!(!known.is_ready());
+        known.tick_cooldown();
+        assert!(known.is_ready());
+        assert_eq!(known.recharge_turns, 0);
+    }
+
+    #[test]
+    fn charges_come_back_one_at_a_time() {
+        let mut known = ability(2, 2);
+        let mut pools = pools();
+
+        known.spend(&mut pools);
+        known.spend(&mut pools);
+        assert_eq!(known.charges, 0);
+        known.tick_cooldown();
+        known.tick_cooldown();
+        assert_eq!(known.charges, 1);
+        assert_eq!(known.recharge_turns, 2);
+        known.tick_cooldown();
+        known.tick_cooldown();
+        assert_eq!(known.charges, 2);
+
+        // a full ability doesn't tick
+        known.tick_cooldown();
+        assert_eq!(known.charges, 2);
+    }
+
+    #[test]
+    fn no_cooldown_never_uses_charges() {
+        let mut known = ability(0, 1);
+        let mut pools = pools();
+
+        known.spend(&mut pools);
+        assert!(known.is_ready());
+    }
+}
@@ -1,7 +1,7 @@
 use specs::{prelude::*, saveload::SimpleMarker, saveload::MarkedBuilder};
 use super::*;
-use crate::{player_xp_for_level, CharacterClass, DamageOverTime, Duration, Map, Name, Player, Pools, ProgressSource, QuestProgress,
-    RunState, SerializeMe,StatusEffect, StatusEffectChanged, WantsToLevelUp};
+use crate::{grant_xp, CharacterClass, Companion, DamageOverTime, Duration, Map, Name, Player, Pools, ProgressSource, QuestProgress,
+    RunState, SerializeMe,StatusEffect, StatusEffectChanged, TileSize, WantsToLevelUp, RacialPassive};
 use crate::gamelog;
 use crate::spatial;
 
@@ -9,6 +9,7 @@ pub fn inflict_damage(ecs: &mut World, damage: &EffectSpawner, target: Entity) {
     let mut pools = ecs.write_storage::<Pools>();
     let player_entity = ecs.fetch::<Entity>();
     let names = ecs.read_storage::<Name>();
+    let mut rage_dealt: Option<(Entity, i32)> = None;
 
     if let Some(pool) = pools.get_mut(target) {
         if !pool.god_mode {
@@ -25,8 +26,13 @@ pub fn inflict_damage(ecs: &mut World, damage: &EffectSpawner, target: Entity) {
                         .append("damage to")
                         .character_name(&names.get(target).unwrap().name)
                         .log();
+                    if creator != target {
+                        rage_dealt = Some((creator, amount));
+                    }
                 }
                 pool.hit_points.current -= amount;
+                // taking damage builds rage
+                pool.rage.gain(amount);
                 add_effect(
                     None,
                     EffectType::Bloodstain,
@@ -56,6 +62,13 @@ pub fn inflict_damage(ecs: &mut World, damage: &EffectSpawner, target: Entity) {
             }
         }
     }
+
+    // dealing damage builds rage
+    if let Some((creator, rage)) = rage_dealt {
+        if let Some(creator_pool) = pools.get_mut(creator) {
+            creator_pool.rage.gain(rage);
+        }
+    }
 }
 
 pub fn heal_damage(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
@@ -90,10 +103,17 @@ pub fn death(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
     let mut quest_progress = ecs.write_storage::<QuestProgress>();
 
     if let Some(pos) = entity_position(ecs, target) {
-        spatial::remove_entity(target, pos as usize);
+        let map = ecs.fetch::<Map>();
+        for idx in map.footprint(pos as usize, ecs.read_storage::<TileSize>().get(target)) {
+            spatial::remove_entity(target, idx);
+        }
     }
 
-    if let Some(creator) = effect.creator {
+    // kills by companions count as the player's
+    let creator = effect.creator.map(|creator| {
+        if ecs.read_storage::<Companion>().get(creator).is_some() { *ecs.fetch::<Entity>() } else { creator }
+    });
+    if let Some(creator) = creator {
         if ecs.read_storage::<Player>().get(creator).is_some() {
             if let Some(pools) = pools.get(target) {
                 xp_gain += pools.level * 100;
@@ -105,18 +125,14 @@ pub fn death(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
 
             if xp_gain != 0 || gold_gain != 0 {
                 let player_pools = pools.get_mut(creator).unwrap();
-                
-                player_pools.xp += xp_gain;
+
                 player_pools.gold += gold_gain;
-                if player_pools.xp >= player_xp_for_level(player_pools.level) {
-                    let character_classes = ecs.read_storage::<CharacterClass>();
-                    let player_class = character_classes.get(creator).unwrap();
-                    let mut level_ups = ecs.write_storage::<WantsToLevelUp>();
-                    level_ups.insert(creator, WantsToLevelUp{ passives: player_class.passives.clone() }).expect("Unable to insert");
-
-                    let mut runstate = ecs.fetch_mut::<RunState>();
-                    *runstate = RunState::LevelUp;
-                }
+                grant_xp(
+                    creator, player_pools, xp_gain,
+                    &ecs.read_storage::<CharacterClass>(),
+                    &mut ecs.write_storage::<WantsToLevelUp>(),
+                    &mut ecs.fetch_mut::<RunState>()
+                );
             }
         }
     }
@@ -126,9 +142,14 @@ pub fn damage_over_time(ecs: &mut World, effect: &EffectSpawner, target: Entity)
     if let EffectType::DamageOverTime{damage, duration} = &effect.effect_type {
         if let Some(creator) = effect.creator { if creator == target { return; } }
 
+        let mut damage = *damage;
+        if let Some(passive) = ecs.read_storage::<RacialPassive>().get(target) {
+            damage = i32::max(1, (damage as f32 * (1.0 - passive.damage_over_time_resistance)) as i32);
+        }
+
         ecs.create_entity()
             .with(StatusEffect{ target, is_debuff: true })
-            .with(DamageOverTime{ damage: *damage })
+            .with(DamageOverTime{ damage })
             .with(Duration{ turns: *duration  })
             .with(Name{ name: "Damage Over Time".to_string() })
             .marked::<SimpleMarker<SerializeMe>>()
@@ -0,0 +1,48 @@
+use specs::prelude::*;
+use super::*;
+use crate::{gamelog, raws, Durability, EquipmentChanged, Equipped, Item};
+
+pub fn wear_item(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
+    if let EffectType::Wear{amount} = effect.effect_type {
+        let owner = ecs.read_storage::<Equipped>().get(target).map(|equipped| equipped.owner);
+        let is_player = owner == Some(*ecs.fetch::<Entity>());
+
+        let mut durabilities = ecs.write_storage::<Durability>();
+        let durability = match durabilities.get_mut(target) {
+            Some(durability) => durability,
+            None => return
+        };
+        let item = ecs.read_storage::<Item>().get(target).unwrap().clone();
+        let quality_before = raws::effective_item_quality(&item.quality, durability);
+        durability.current = i32::max(0, durability.current - amount);
+        let broken = durability.current == 0;
+        let quality_after = raws::effective_item_quality(&item.quality, durability);
+        std::mem::drop(durabilities);
+        if !broken && quality_before == quality_after { return; }
+
+        if broken {
+            if is_player {
+                gamelog::Logger::new()
+                    .append("Your")
+                    .item_name(&item)
+                    .colour(rltk::RGB::named(rltk::RED))
+                    .append("breaks!")
+                    .log();
+            }
+            ecs.entities().delete(target).expect("Unable to delete broken item");
+        } else {
+            raws::refresh_item_condition(&raws::RAWS.lock().unwrap(), ecs, target);
+            if is_player {
+                gamelog::Logger::new()
+                    .append("Your")
+                    .item_name(&item)
+                    .append("is starting to wear out.")
+                    .log();
+            }
+        }
+
+        if let Some(owner) = owner {
+            ecs.write_storage::<EquipmentChanged>().insert(owner, EquipmentChanged{}).expect("Unable to insert");
+        }
+    }
+}
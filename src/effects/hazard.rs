@@ -0,0 +1,17 @@
+use specs::prelude::*;
+use super::*;
+use crate::{HazardType, Map};
+
+// how far lightning can travel through a body of water
+const LIGHTNING_CHAIN_LIMIT: usize = 40;
+
+pub fn create_hazard(ecs: &mut World, effect: &EffectSpawner, tile_idx: i32) {
+    if let EffectType::Hazard{hazard_type, turns} = &effect.effect_type {
+        let mut map = ecs.fetch_mut::<Map>();
+        let idx = tile_idx as usize;
+        match hazard_type {
+            HazardType::Lightning => map.electrify_water(idx, *turns, LIGHTNING_CHAIN_LIMIT),
+            _ => map.add_hazard(idx, *hazard_type, *turns)
+        }
+    }
+}
@@ -15,10 +15,17 @@ mod slow;
 mod rage;
 mod fortress;
 mod frost_shield;
+mod durability;
+mod summon;
+mod hazard;
+mod traps;
+mod water_breathing;
+mod terrain;
+pub use terrain::SECRET_DOOR_DIFFICULTY;
 pub use targeting::*;
 use rltk::{FontCharType, RGB, Point};
 use crate::spatial;
-use super::AttributeBonus;
+use super::{AttributeBonus, HazardType};
 
 lazy_static! {
     pub static ref EFFECT_QUEUE: Mutex<VecDeque<EffectSpawner>> = Mutex::new(VecDeque::new());
@@ -45,7 +52,17 @@ pub enum EffectType {
     Stun { duration: i32 },
     Rage { duration: i32 },
     Fortress { duration: i32 },
-    FrostShield { duration: i32 }
+    FrostShield { duration: i32 },
+    Wear { amount: i32 },
+    Summon { mob: String, source: String },
+    Hazard { hazard_type: HazardType, turns: i32 },
+    Pit,
+    Alarm,
+    RandomTeleport,
+    WaterBreathing { duration: i32 },
+    Dig { power: i32 },
+    Smash { damage: i32 },
+    RevealSecretDoor
 }
 
 #[derive(Clone, Debug)]
@@ -109,6 +126,10 @@ fn affect_tile(ecs: &mut World, effect: &mut EffectSpawner, tile_idx: i32) {
         EffectType::Bloodstain => damage::bloodstain(ecs, tile_idx),
         EffectType::Particle{..} => particles::particle_to_tile(ecs, tile_idx, &effect),
         EffectType::ParticleProjectile {..} => particles::projectile(ecs, tile_idx, &effect),
+        EffectType::Hazard{..} => hazard::create_hazard(ecs, effect, tile_idx),
+        EffectType::Alarm => traps::sound_alarm(ecs, tile_idx),
+        EffectType::Dig{..} => terrain::dig(ecs, effect, tile_idx),
+        EffectType::RevealSecretDoor => terrain::reveal_secret_door(ecs, tile_idx),
         _ => {
             let content = spatial::get_tile_content_clone(tile_idx as usize);
             content.iter().for_each(|entity| affect_entity(ecs, effect, *entity));
@@ -145,6 +166,22 @@ fn affect_entity(ecs: &mut World, effect: &mut EffectSpawner, target: Entity) {
         EffectType::Rage{..} => rage::apply_rage(ecs, effect, target),
         EffectType::Fortress{..} => fortress::apply_fortress(ecs, effect, target),
         EffectType::FrostShield{..} => frost_shield::apply_frost_shield(ecs, effect, target),
+        EffectType::Wear{..} => durability::wear_item(ecs, effect, target),
+        EffectType::Summon{..} => summon::summon(ecs, effect, target),
+        EffectType::Hazard{..} => {
+            if let Some(pos) = entity_position(ecs, target) {
+                hazard::create_hazard(ecs, effect, pos)
+            }
+        }
+        EffectType::Alarm => {
+            if let Some(pos) = entity_position(ecs, target) {
+                traps::sound_alarm(ecs, pos)
+            }
+        }
+        EffectType::Pit => traps::fall_into_pit(ecs, target),
+        EffectType::RandomTeleport => movement::apply_random_teleport(ecs, target),
+        EffectType::WaterBreathing{..} => water_breathing::apply_water_breathing(ecs, effect, target),
+        EffectType::Smash{..} => terrain::smash(ecs, effect, target),
         _ => {}
     }
 }
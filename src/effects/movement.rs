@@ -1,6 +1,7 @@
 use specs::prelude::*;
 use super::*;
-use crate::components::ApplyTeleport;
+use crate::components::{ApplyTeleport, Pools};
+use crate::{gamelog, rng, tile_walkable, Map};
 
 pub fn apply_teleport(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
     let player_entity = ecs.fetch::<Entity>();
@@ -15,3 +16,26 @@ pub fn apply_teleport(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
         }
     }
 }
+
+pub fn apply_random_teleport(ecs: &mut World, target: Entity) {
+    // only creatures get moved, not the trap that caused it
+    if ecs.read_storage::<Pools>().get(target).is_none() { return; }
+
+    let map = ecs.fetch::<Map>();
+    for _ in 0..100 {
+        let x = rng::range(1, map.width - 1);
+        let y = rng::range(1, map.height - 1);
+        let idx = map.xy_idx(x, y);
+        if tile_walkable(&map.tiles[idx]) && !spatial::is_blocked(idx) && !map.hazards.contains_key(&idx) {
+            ecs.write_storage::<ApplyTeleport>().insert(target, ApplyTeleport{
+                dest_x: x,
+                dest_y: y,
+                dest_map: map.name.clone()
+            }).expect("Unable to insert");
+            if target == *ecs.fetch::<Entity>() {
+                gamelog::Logger::new().append("You are suddenly somewhere else!").log();
+            }
+            return;
+        }
+    }
+}
@@ -1,17 +1,20 @@
 use specs::{prelude::*, saveload::SimpleMarker, saveload::MarkedBuilder};
 use super::*;
-use crate::components::{StatusEffect, StatusEffectChanged, Duration, Name, SerializeMe, AttributeBonus, SkillBonus, Faction, Rage};
-use crate::raws::{RAWS, faction_reaction, Reaction};
+use crate::components::{StatusEffect, StatusEffectChanged, Duration, Name, SerializeMe, AttributeBonus, SkillBonus, Faction, Rage,
+    FactionAttitudes};
+use crate::raws::{RAWS, attitude_reaction, Reaction};
 
 pub fn apply_rage(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
     if let EffectType::Rage{duration} = &effect.effect_type {
         if let Some(creator) = effect.creator {
             let factions = ecs.read_storage::<Faction>();
+            let attitudes = ecs.read_storage::<FactionAttitudes>();
             if let Some(creator_faction) = factions.get(creator) {
                 if let Some(target_faction) = factions.get(target) {
-                    let reaction = faction_reaction(
+                    let reaction = attitude_reaction(
                         &creator_faction.name,
                         &target_faction.name,
+                        attitudes.get(target),
                         &RAWS.lock().unwrap()
                     );
                     // don't apply rage to enemies
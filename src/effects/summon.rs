@@ -0,0 +1,53 @@
+use specs::prelude::*;
+use super::*;
+use crate::{gamelog, dismiss_summon, free_tile_near, make_companion, Faction, Map, Name, Position};
+use crate::raws::{self, SpawnType, RAWS};
+
+pub fn summon(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
+    if let EffectType::Summon{mob, source} = &effect.effect_type {
+        let player_entity = *ecs.fetch::<Entity>();
+        if target == player_entity {
+            dismiss_summon(ecs, source);
+        }
+
+        let pos = match ecs.read_storage::<Position>().get(target) {
+            Some(pos) => pos.clone(),
+            None => return
+        };
+        let spawn_pos = {
+            let map = ecs.fetch::<Map>();
+            free_tile_near(&map, pos.x, pos.y, |idx| !spatial::is_blocked(idx))
+        };
+        let (x, y) = match spawn_pos {
+            Some(spawn_pos) => spawn_pos,
+            None => return
+        };
+
+        let summoned = match raws::spawn_named_mob(&RAWS.lock().unwrap(), ecs, mob, SpawnType::AtPosition{ x, y }) {
+            Some(summoned) => summoned,
+            None => return
+        };
+        let map_idx = ecs.fetch::<Map>().xy_idx(x, y);
+        spatial::index_entity(summoned, map_idx, true);
+
+        if target == player_entity {
+            make_companion(ecs, summoned, Some(source.clone()));
+            gamelog::Logger::new()
+                .append("You summon a")
+                .character_name(mob)
+                .log();
+        } else {
+            // summons fight for whoever summoned them
+            let faction = ecs.read_storage::<Faction>().get(target).cloned();
+            if let Some(faction) = faction {
+                ecs.write_storage::<Faction>().insert(summoned, faction).expect("Unable to insert");
+            }
+            let name = ecs.read_storage::<Name>().get(target).map_or("Something".to_string(), |name| name.name.clone());
+            gamelog::Logger::new()
+                .character_name(name)
+                .append("summons a")
+                .character_name(mob)
+                .log();
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use specs::prelude::*;
-use rltk::Point;
-use crate::components::{Position, InBackpack, Equipped};
+use rltk::{Algorithm2D, BaseMap, Point};
+use crate::components::{AoeShape, AreaOfEffect, Position, InBackpack, Equipped};
 use crate::map::Map;
 
 pub fn entity_position(ecs: &World, target: Entity) -> Option<i32> {
@@ -11,22 +11,74 @@ pub fn entity_position(ecs: &World, target: Entity) -> Option<i32> {
     None
 }
 
-pub fn aoe_tiles(map: &Map, target: Point, radius: i32) -> Vec<i32> {
-    let mut blast_tiles = rltk::field_of_view(target, radius, &*map);
-    blast_tiles.retain(|p| p.x > 0 && p.x < map.width - 1 && p.y > 0 && p.y < map.height - 1);
-    let mut result = Vec::new();
-    for t in blast_tiles.iter() {
-        result.push(map.xy_idx(t.x, t.y) as i32);
-    }
-    result
+// cones spread 45 degrees either side of the direction they are aimed in
+const CONE_HALF_ANGLE_COS: f32 = 0.707;
+
+pub fn aoe_tiles(map: &Map, origin: Point, target: Point, aoe: &AreaOfEffect) -> Vec<i32> {
+    aoe_points(map, origin, target, aoe).iter()
+        .map(|p| map.xy_idx(p.x, p.y) as i32)
+        .collect()
 }
 
-pub fn aoe_points(map: &Map, target: Point, radius: i32) -> Vec<Point> {
-    let mut points = rltk::field_of_view(target, radius, &*map);
+pub fn aoe_points(map: &Map, origin: Point, target: Point, aoe: &AreaOfEffect) -> Vec<Point> {
+    let mut points = match aoe.shape {
+        // cones and lines need a direction, without one they fall back to a circle around the target
+        AoeShape::Cone if origin != target => cone_points(map, origin, target, aoe.radius),
+        AoeShape::Line if origin != target => line_points(map, origin, target, aoe.radius),
+        AoeShape::Ring => {
+            let mut points = rltk::field_of_view(target, aoe.radius, map);
+            points.retain(|p| rltk::DistanceAlg::Pythagoras.distance2d(target, *p).round() as i32 == aoe.radius);
+            points
+        }
+        AoeShape::Cross => cross_points(map, target, aoe.radius),
+        _ => rltk::field_of_view(target, aoe.radius, map)
+    };
     points.retain(|p| p.x > 0 && p.x < map.width - 1 && p.y > 0 && p.y < map.height - 1);
     points
 }
 
+fn cone_points(map: &Map, origin: Point, target: Point, radius: i32) -> Vec<Point> {
+    let (dir_x, dir_y) = direction(origin, target);
+    let mut points = rltk::field_of_view(origin, radius, map);
+    points.retain(|p| {
+        if *p == origin { return false; }
+        let (x, y) = direction(origin, *p);
+        x * dir_x + y * dir_y >= CONE_HALF_ANGLE_COS
+    });
+    points
+}
+
+// a line from the origin through the target that stops at the first wall
+fn line_points(map: &Map, origin: Point, target: Point, length: i32) -> Vec<Point> {
+    let (dir_x, dir_y) = direction(origin, target);
+    let end = Point::new(
+        origin.x + (dir_x * length as f32).round() as i32,
+        origin.y + (dir_y * length as f32).round() as i32
+    );
+    rltk::line2d(rltk::LineAlg::Bresenham, origin, end).into_iter()
+        .filter(|p| *p != origin)
+        .take_while(|p| map.in_bounds(*p) && !map.is_opaque(map.xy_idx(p.x, p.y)))
+        .collect()
+}
+
+fn cross_points(map: &Map, target: Point, radius: i32) -> Vec<Point> {
+    let mut points = vec![target];
+    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        for step in 1..=radius {
+            let point = Point::new(target.x + dx * step, target.y + dy * step);
+            if !map.in_bounds(point) || map.is_opaque(map.xy_idx(point.x, point.y)) { break; }
+            points.push(point);
+        }
+    }
+    points
+}
+
+fn direction(from: Point, to: Point) -> (f32, f32) {
+    let (x, y) = ((to.x - from.x) as f32, (to.y - from.y) as f32);
+    let length = (x * x + y * y).sqrt();
+    if length == 0.0 { (0.0, 0.0) } else { (x / length, y / length) }
+}
+
 pub fn find_item_position(ecs: &World, target: Entity, creator: Option<Entity>) -> Option<i32> {
     let positions = ecs.read_storage::<Position>();
     let map = ecs.fetch::<Map>();
@@ -60,3 +112,88 @@ pub fn find_item_position(ecs: &World, target: Entity, creator: Option<Entity>)
     // can't find it
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::TileType;
+
+    fn aoe(shape: AoeShape, radius: i32) -> AreaOfEffect {
+        AreaOfEffect{ radius, shape }
+    }
+
+    #[test]
+    fn circle_covers_the_radius() {
+        let map = Map::open(20, 20);
+        let points = aoe_points(&map, Point::new(5, 10), Point::new(10, 10), &aoe(AoeShape::Circle, 2));
+
+        assert!(points.contains(&Point::new(10, 10)));
+        assert!(points.contains(&Point::new(12, 10)));
+        assert!(!points.contains(&Point::new(13, 10)));
+    }
+
+    #[test]
+    fn cross_stops_at_walls() {
+        let mut map = Map::open(20, 20);
+        let wall_idx = map.xy_idx(12, 10);
+        map.tiles[wall_idx] = TileType::Wall;
+        let points = aoe_points(&map, Point::new(5, 10), Point::new(10, 10), &aoe(AoeShape::Cross, 3));
+
+        assert!(points.contains(&Point::new(11, 10)));
+        assert!(!points.contains(&Point::new(12, 10)));
+        assert!(!points.contains(&Point::new(13, 10)));
+        assert!(points.contains(&Point::new(10, 7)));
+        assert!(!points.contains(&Point::new(11, 11)));
+        assert_eq!(points.len(), 1 + 1 + 3 + 3 + 3);
+    }
+
+    #[test]
+    fn line_runs_from_the_origin_towards_the_target() {
+        let map = Map::open(20, 20);
+        let points = aoe_points(&map, Point::new(5, 10), Point::new(7, 10), &aoe(AoeShape::Line, 4));
+
+        assert_eq!(points, vec![Point::new(6, 10), Point::new(7, 10), Point::new(8, 10), Point::new(9, 10)]);
+    }
+
+    #[test]
+    fn cone_only_spreads_forwards() {
+        let map = Map::open(20, 20);
+        let points = aoe_points(&map, Point::new(10, 10), Point::new(13, 10), &aoe(AoeShape::Cone, 4));
+
+        assert!(points.contains(&Point::new(13, 10)));
+        assert!(points.contains(&Point::new(13, 11)));
+        assert!(!points.contains(&Point::new(10, 10)));
+        assert!(!points.contains(&Point::new(8, 10)));
+        assert!(!points.contains(&Point::new(10, 13)));
+    }
+
+    #[test]
+    fn ring_only_covers_its_edge() {
+        let map = Map::open(20, 20);
+        let points = aoe_points(&map, Point::new(5, 10), Point::new(10, 10), &aoe(AoeShape::Ring, 3));
+
+        assert!(points.contains(&Point::new(13, 10)));
+        assert!(points.contains(&Point::new(10, 7)));
+        assert!(!points.contains(&Point::new(10, 10)));
+        assert!(!points.contains(&Point::new(11, 10)));
+    }
+
+    #[test]
+    fn directional_shapes_without_a_direction_fall_back_to_a_circle() {
+        let map = Map::open(20, 20);
+        let target = Point::new(10, 10);
+        let cone = aoe_points(&map, target, target, &aoe(AoeShape::Cone, 2));
+        let circle = aoe_points(&map, target, target, &aoe(AoeShape::Circle, 2));
+
+        assert_eq!(cone.len(), circle.len());
+        assert!(cone.contains(&Point::new(8, 10)));
+    }
+
+    #[test]
+    fn points_stay_off_the_map_edge() {
+        let map = Map::open(20, 20);
+        let points = aoe_points(&map, Point::new(5, 5), Point::new(1, 1), &aoe(AoeShape::Circle, 3));
+
+        assert!(points.iter().all(|p| p.x > 0 && p.y > 0));
+    }
+}
@@ -0,0 +1,88 @@
+use specs::prelude::*;
+use super::*;
+use crate::{change_tile, gamelog, spawn_entity, Breakable, Map, Name, TileType, Viewshed};
+
+// how much digging it takes to break through a wall
+pub const WALL_HARDNESS: i32 = 12;
+
+// the roll needed to notice a secret door
+pub const SECRET_DOOR_DIFFICULTY: i32 = 15;
+
+pub fn dig(ecs: &mut World, effect: &EffectSpawner, tile_idx: i32) {
+    if let EffectType::Dig{power} = effect.effect_type {
+        let idx = tile_idx as usize;
+        let is_player = effect.creator == Some(*ecs.fetch::<Entity>());
+        let mut map = ecs.fetch_mut::<Map>();
+        let (x, y) = map.idx_xy(idx);
+        // town walls and the edges of the map can't be dug through
+        if map.tiles[idx] != TileType::Wall || x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 { return; }
+
+        let damage = map.wall_damage.entry(idx).or_insert(0);
+        *damage += power;
+        if *damage < WALL_HARDNESS {
+            if is_player {
+                gamelog::Logger::new().append("You chip away at the wall.").log();
+            }
+            return;
+        }
+        map.wall_damage.remove(&idx);
+        map.secret_doors.remove(&idx);
+        let visible = map.visible_tiles[idx];
+        std::mem::drop(map);
+
+        change_tile(ecs, idx, TileType::Floor);
+        if visible {
+            gamelog::Logger::new().append("The wall crumbles away.").log();
+        }
+    }
+}
+
+pub fn smash(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
+    if let EffectType::Smash{damage} = effect.effect_type {
+        let is_player = effect.creator == Some(*ecs.fetch::<Entity>());
+        let name = ecs.read_storage::<Name>().get(target).map_or("door".to_string(), |n| n.name.clone());
+        let mut breakables = ecs.write_storage::<Breakable>();
+        let breakable = match breakables.get_mut(target) {
+            Some(breakable) => breakable,
+            None => return
+        };
+        breakable.hit_points -= damage;
+        let broken = breakable.hit_points < 1;
+        std::mem::drop(breakables);
+
+        if !broken {
+            if is_player {
+                gamelog::Logger::new().append(format!("You smash at the {}.", name)).log();
+            }
+            return;
+        }
+
+        let visible = entity_position(ecs, target).is_some_and(|idx| ecs.fetch::<Map>().visible_tiles[idx as usize]);
+        if visible {
+            gamelog::Logger::new()
+                .colour(rltk::RGB::named(rltk::ORANGE))
+                .append(format!("The {} is smashed to pieces!", name))
+                .log();
+        }
+        ecs.entities().delete(target).expect("Unable to delete");
+        for viewshed in (&mut ecs.write_storage::<Viewshed>()).join() {
+            viewshed.dirty = true;
+        }
+    }
+}
+
+pub fn reveal_secret_door(ecs: &mut World, tile_idx: i32) {
+    let idx = tile_idx as usize;
+    {
+        let mut map = ecs.fetch_mut::<Map>();
+        // the wall may already have been dug or changed some other way
+        if !map.secret_doors.remove(&idx) || map.tiles[idx] != TileType::Wall { return; }
+    }
+
+    change_tile(ecs, idx, TileType::Floor);
+    spawn_entity(ecs, &(&idx, &"Door".to_string()));
+    gamelog::Logger::new()
+        .colour(rltk::RGB::named(rltk::ORANGE))
+        .append("You find a secret door!")
+        .log();
+}
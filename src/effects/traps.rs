@@ -0,0 +1,50 @@
+use specs::prelude::*;
+use rltk::Point;
+use super::*;
+use crate::{gamelog, rng, Chasing, Companion, Faction, FactionAttitudes, Map, Pools, Position, RunState, TileType};
+use crate::raws::{attitude_reaction, Reaction, RAWS};
+
+// how far away monsters can hear an alarm
+const ALARM_RADIUS: f32 = 25.0;
+
+pub fn sound_alarm(ecs: &mut World, tile_idx: i32) {
+    let player_entity = *ecs.fetch::<Entity>();
+    let map = ecs.fetch::<Map>();
+    let alarm_pos = Point::new(tile_idx % map.width, tile_idx / map.width);
+    let entities = ecs.entities();
+    let factions = ecs.read_storage::<Faction>();
+    let attitudes = ecs.read_storage::<FactionAttitudes>();
+    let positions = ecs.read_storage::<Position>();
+    let pools = ecs.read_storage::<Pools>();
+    let companions = ecs.read_storage::<Companion>();
+    let mut chasing = ecs.write_storage::<Chasing>();
+
+    gamelog::Logger::new().colour(rltk::RGB::named(rltk::ORANGE)).append("A loud alarm rings out!").log();
+
+    for (entity, faction, pos, _pools, _companion) in (&entities, &factions, &positions, &pools, !&companions).join() {
+        if entity == player_entity { continue; }
+        if rltk::DistanceAlg::Pythagoras.distance2d(alarm_pos, Point::new(pos.x, pos.y)) > ALARM_RADIUS { continue; }
+        let reaction = attitude_reaction(&faction.name, "Player", attitudes.get(player_entity), &RAWS.lock().unwrap());
+        if reaction == Reaction::Attack {
+            chasing.insert(entity, Chasing{ target: player_entity }).expect("Unable to insert");
+        }
+    }
+}
+
+pub fn fall_into_pit(ecs: &mut World, target: Entity) {
+    let player_entity = *ecs.fetch::<Entity>();
+    if target != player_entity { return; }
+
+    let next_map = ecs.fetch::<Map>().tiles.iter().find_map(|tt| match tt {
+        TileType::NextArea{map_name} => Some(map_name.clone()),
+        _ => None
+    });
+    match next_map {
+        Some(map_name) => {
+            gamelog::Logger::new().append("You fall through a pit to the level below!").log();
+            add_effect(None, EffectType::Damage{ amount: rng::roll_dice(1, 6), hits_self: false }, Targets::Single{ target });
+            *ecs.fetch_mut::<RunState>() = RunState::TransitionMap{ map_name };
+        }
+        None => gamelog::Logger::new().append("You stumble into a shallow pit.").log()
+    }
+}
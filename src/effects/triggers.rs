@@ -3,7 +3,9 @@ use specs::prelude::*;
 use super::*;
 use crate::{determine_roll, gamelog, raws, Attributes, Chest, Confusion, Consumable, Damage, DamageOverTime, Duration, Food, Fortress,
     FrostShield, Healing, Item, KnownAbility, LootTable, MagicMapping, Map, Name, Pools, Rage, RestoresMana, RunState, SelfDamage,
-    SingleActivation, Skills, Slow, SpawnParticleBurst, SpawnParticleLine, Stun, TeleportTo, TownPortal, ItemQuality};
+    SingleActivation, Skills, Slow, SpawnParticleBurst, SpawnParticleLine, Stun, TeleportTo, TownPortal, ItemQuality, Identify,
+    InBackpack, identify_item, RemoveCurse, Cursed, Equipped, Gatherable, Summon, CreatesHazard, Alarm, Pit, RandomTeleport,
+    WaterBreathing, Tunnels};
 
 pub fn item_trigger(ecs: &mut World, creator: Option<Entity>, item_entity: Entity, targets: &Targets) {
     // check charges
@@ -19,6 +21,11 @@ pub fn item_trigger(ecs: &mut World, creator: Option<Entity>, item_entity: Entit
         }
     }
 
+    // using an item identifies it
+    if creator == Some(*ecs.fetch::<Entity>()) {
+        identify_item(ecs, item_entity);
+    }
+
     let did_something = event_trigger(ecs, creator, item_entity, targets);
 
     // delete consumables after use
@@ -45,8 +52,8 @@ pub fn ability_trigger(ecs: &mut World, creator: Option<Entity>, known_ability_e
     if is_repeat {
         did_something = true;
     } else if let Some(caster) = creator {
-        let all_known_abilities = ecs.read_storage::<KnownAbility>();
-        if let Some(known_ability) = all_known_abilities.get(known_ability_entity) {
+        let mut all_known_abilities = ecs.write_storage::<KnownAbility>();
+        if let Some(known_ability) = all_known_abilities.get_mut(known_ability_entity) {
             let mut pools = ecs.write_storage::<Pools>();
             if let Some(pool) = pools.get_mut(caster) {
                 let self_damages = ecs.read_storage::<SelfDamage>();
@@ -66,9 +73,9 @@ pub fn ability_trigger(ecs: &mut World, creator: Option<Entity>, known_ability_e
                     }
                     did_something = true;
                 }
-                if known_ability.mana_cost <= pool.mana.current {
+                if known_ability.is_ready() && known_ability.missing_resource(pool).is_none() {
                     if !pool.god_mode {
-                        pool.mana.current -= known_ability.mana_cost;
+                        known_ability.spend(pool);
                     }
                     did_something = true;
                 }
@@ -152,6 +159,51 @@ fn event_trigger(ecs: &mut World, creator: Option<Entity>, entity: Entity, targe
         }
     }
 
+    // identify
+    if ecs.read_storage::<Identify>().get(entity).is_some() {
+        if let Some(user) = creator {
+            let unidentified_items: Vec<Entity> = (&ecs.entities(), &ecs.read_storage::<Item>(), &ecs.read_storage::<InBackpack>()).join()
+                .filter(|(_, item, backpack)| backpack.owner == user && item.unidentified_name.is_some())
+                .map(|(item_entity, _, _)| item_entity)
+                .collect();
+
+            if unidentified_items.is_empty() {
+                gamelog::Logger::new().append("You have nothing to identify.").log();
+            }
+            for item_entity in unidentified_items.iter() {
+                if identify_item(ecs, *item_entity) {
+                    gamelog::Logger::new()
+                        .append("You identify")
+                        .item_name(ecs.read_storage::<Item>().get(*item_entity).unwrap())
+                        .log();
+                }
+                did_something = true;
+            }
+        }
+    }
+
+    // remove curse
+    if ecs.read_storage::<RemoveCurse>().get(entity).is_some() {
+        if let Some(user) = creator {
+            let cursed_items: Vec<Entity> = (&ecs.entities(), &ecs.read_storage::<Cursed>()).join()
+                .filter(|(item_entity, _)| {
+                    ecs.read_storage::<Equipped>().get(*item_entity).is_some_and(|equipped| equipped.owner == user)
+                    || ecs.read_storage::<InBackpack>().get(*item_entity).is_some_and(|backpack| backpack.owner == user)
+                })
+                .map(|(item_entity, _)| item_entity)
+                .collect();
+
+            if cursed_items.is_empty() {
+                gamelog::Logger::new().append("You feel as if someone is watching over you.").log();
+            } else {
+                let mut cursed = ecs.write_storage::<Cursed>();
+                cursed_items.iter().for_each(|item_entity| { cursed.remove(*item_entity); });
+                gamelog::Logger::new().append("You feel a malevolent aura lift.").log();
+            }
+            did_something = true;
+        }
+    }
+
     // healing
     if let Some(heal) = ecs.read_storage::<Healing>().get(entity) {
         add_effect(creator, EffectType::Healing{ amount: heal.heal_amount }, targets.clone());
@@ -243,6 +295,50 @@ fn event_trigger(ecs: &mut World, creator: Option<Entity>, entity: Entity, targe
         }
     }
 
+    // tunnelling through walls
+    if ecs.read_storage::<Tunnels>().get(entity).is_some() {
+        add_effect(creator, EffectType::Dig{ power: terrain::WALL_HARDNESS }, targets.clone());
+        did_something = true;
+    }
+
+    // water breathing
+    if ecs.read_storage::<WaterBreathing>().get(entity).is_some() {
+        if let Some(duration) = ecs.read_storage::<Duration>().get(entity) {
+            add_effect(creator, EffectType::WaterBreathing{ duration: duration.turns }, targets.clone());
+            did_something = true;
+        }
+    }
+
+    // lasting hazards on the affected tiles
+    if let Some(hazard) = ecs.read_storage::<CreatesHazard>().get(entity) {
+        add_effect(creator, EffectType::Hazard{ hazard_type: hazard.hazard_type, turns: hazard.turns }, targets.clone());
+        did_something = true;
+    }
+
+    // traps
+    if ecs.read_storage::<Alarm>().get(entity).is_some() {
+        add_effect(creator, EffectType::Alarm, targets.clone());
+        did_something = true;
+    }
+    if ecs.read_storage::<RandomTeleport>().get(entity).is_some() {
+        add_effect(creator, EffectType::RandomTeleport, targets.clone());
+        did_something = true;
+    }
+    // pits only swallow the player
+    if ecs.read_storage::<Pit>().get(entity).is_some() && creator == Some(*ecs.fetch::<Entity>()) {
+        add_effect(creator, EffectType::Pit, targets.clone());
+        did_something = true;
+    }
+
+    // summoning always happens next to the creator
+    if let Some(summon) = ecs.read_storage::<Summon>().get(entity) {
+        if let Some(creator) = creator {
+            let source = ecs.read_storage::<Name>().get(entity).map_or(summon.mob.clone(), |name| name.name.clone());
+            add_effect(Some(creator), EffectType::Summon{ mob: summon.mob.clone(), source }, Targets::Single{ target: creator });
+            did_something = true;
+        }
+    }
+
     // teleportation
     if let Some(teleport) = ecs.read_storage::<TeleportTo>().get(entity) {
         add_effect(
@@ -331,6 +427,29 @@ fn event_trigger(ecs: &mut World, creator: Option<Entity>, entity: Entity, targe
         did_something = true;
     }
 
+    // gather materials
+    let mut gathered: Option<String> = None;
+    if let Some(gatherable) = ecs.read_storage::<Gatherable>().get(entity) {
+        if creator.is_some() {
+            gathered = Some(gatherable.item.clone());
+        }
+    }
+    if let Some(item_name) = gathered {
+        raws::spawn_named_item(
+            &raws::RAWS.lock().unwrap(),
+            ecs,
+            &item_name,
+            raws::SpawnType::Carried { by: creator.unwrap() },
+            ItemQuality::Standard
+        );
+        gamelog::Logger::new()
+            .append("You gather some")
+            .colour(RGB::named(rltk::CYAN))
+            .append(&item_name)
+            .log();
+        did_something = true;
+    }
+
     did_something
 }
 
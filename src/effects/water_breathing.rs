@@ -0,0 +1,30 @@
+use specs::{prelude::*, saveload::SimpleMarker, saveload::MarkedBuilder};
+use super::*;
+use crate::components::{StatusEffect, StatusEffectChanged, Duration, Name, SerializeMe, WaterBreathing};
+
+pub fn apply_water_breathing(ecs: &mut World, effect: &EffectSpawner, target: Entity) {
+    if let EffectType::WaterBreathing{duration} = &effect.effect_type {
+        delete_duplicate_effect(ecs, target);
+
+        ecs.create_entity()
+            .with(StatusEffect{ target, is_debuff: false })
+            .with(Duration{ turns: *duration })
+            .with(Name{ name: "Water Breathing".to_string() })
+            .with(WaterBreathing{})
+            .marked::<SimpleMarker<SerializeMe>>()
+            .build();
+        ecs.write_storage::<StatusEffectChanged>().insert(target, StatusEffectChanged{}).expect("Insert failed");
+    }
+}
+
+fn delete_duplicate_effect(ecs: &mut World, target: Entity) {
+    let entities = ecs.entities();
+    let status_effects = ecs.read_storage::<StatusEffect>();
+    let water_breathing = ecs.read_storage::<WaterBreathing>();
+
+    for (entity, status_effect, _water_breathing) in (&entities, &status_effects, &water_breathing).join() {
+        if status_effect.target == target {
+            entities.delete(entity).expect("Unable to delete entity");
+        }
+    }
+}
@@ -1,13 +1,13 @@
 use rltk::prelude::*;
-use super::{yellow, black, red, cyan, green, white};
+use super::{yellow, black, white};
 use crate::{State, RunState};
 use crate::raws;
 
-#[derive(PartialEq, Copy, Clone, Debug)]
-pub enum CharacterClassSelection { Warrior, Sorceror, Ranger }
+// classes past this scroll into view as the selection moves
+const MAX_VISIBLE_CLASSES: usize = 8;
 
-#[derive(PartialEq, Copy, Clone)]
-pub enum CharacterClassResult { NoSelection{ selected: CharacterClassSelection}, Selected{ selected: CharacterClassSelection } }
+#[derive(PartialEq, Clone)]
+pub enum CharacterClassResult { NoSelection{ selected: usize }, Selected{ selected: String } }
 
 pub fn character_class_select_menu(gs: &mut State, ctx: &mut Rltk) -> CharacterClassResult {
     let runstate = gs.ecs.fetch::<RunState>();
@@ -17,71 +17,55 @@ pub fn character_class_select_menu(gs: &mut State, ctx: &mut Rltk) -> CharacterC
     draw_batch.print_color_centered(18, "Choose your character class", ColorPair::new(yellow(), black()));
 
     if let RunState::CharacterClassSelectMenu{ menu_selection: selection } = *runstate {
-        if selection == CharacterClassSelection::Warrior {
-            draw_batch.print_color_centered(26, "Warrior", ColorPair::new(red(), black()));
-            draw_batch.print_color_centered(
-                28,
-                raws::get_character_class_description(&raws::RAWS.lock().unwrap(), "Warrior").unwrap(),
-                ColorPair::new(red(), black())
-            );
-        } else {
-            draw_batch.print_color_centered(26, "Warrior", ColorPair::new(white(), black()));
-        }
+        let raw_master = raws::RAWS.lock().unwrap();
+        let class_names = raws::get_character_class_names(&raw_master);
+        if class_names.is_empty() { return CharacterClassResult::NoSelection { selected: 0 }; }
 
-        if selection == CharacterClassSelection::Sorceror {
-            draw_batch.print_color_centered(32, "Sorceror", ColorPair::new(cyan(), black()));
-            draw_batch.print_color_centered(
-                34,
-                raws::get_character_class_description(&raws::RAWS.lock().unwrap(),
-                "Sorceror").unwrap(),
-                ColorPair::new(cyan(), black())
-            );
-        } else {
-            draw_batch.print_color_centered(32, "Sorceror", ColorPair::new(white(), black()));
+        let first = if selection < MAX_VISIBLE_CLASSES { 0 } else { selection + 1 - MAX_VISIBLE_CLASSES };
+        if first > 0 {
+            draw_batch.print_color_centered(23, "(more above)", ColorPair::new(white(), black()));
+        }
+        if first + MAX_VISIBLE_CLASSES < class_names.len() {
+            draw_batch.print_color_centered(26 + MAX_VISIBLE_CLASSES as i32 * 6, "(more below)", ColorPair::new(white(), black()));
         }
 
-        if selection == CharacterClassSelection::Ranger {
-            draw_batch.print_color_centered(38, "Ranger", ColorPair::new(green(), black()));
-            draw_batch.print_color_centered(
-                40,
-                raws::get_character_class_description(&raws::RAWS.lock().unwrap(),
-                "Ranger").unwrap(),
-                ColorPair::new(green(), black())
-            );
-        } else {
-            draw_batch.print_color_centered(38, "Ranger", ColorPair::new(white(), black()));
+        let mut y = 26;
+        for (i, class_name) in class_names.iter().enumerate().skip(first).take(MAX_VISIBLE_CLASSES) {
+            if i == selection {
+                let colour = raws::get_character_class_colour(&raw_master, class_name);
+                draw_batch.print_color_centered(y, class_name, ColorPair::new(colour, black()));
+                draw_batch.print_color_centered(
+                    y + 2,
+                    raws::get_character_class_description(&raw_master, class_name).unwrap(),
+                    ColorPair::new(colour, black())
+                );
+            } else {
+                draw_batch.print_color_centered(y, class_name, ColorPair::new(white(), black()));
+            }
+            y += 6;
         }
 
         draw_batch.submit(2000).expect("Draw batch submission failed");
 
+        let count = class_names.len();
         match ctx.key {
             None => return CharacterClassResult::NoSelection { selected: selection },
             Some(key) => {
                 match key {
                     VirtualKeyCode::Up => {
-                        let new_selection;
-                        match selection {
-                            CharacterClassSelection::Warrior => new_selection = CharacterClassSelection::Ranger,
-                            CharacterClassSelection::Sorceror => new_selection = CharacterClassSelection::Warrior,
-                            CharacterClassSelection::Ranger => new_selection = CharacterClassSelection::Sorceror
-                        }
+                        let new_selection = if selection == 0 { count - 1 } else { selection - 1 };
                         return CharacterClassResult::NoSelection { selected: new_selection };
                     },
                     VirtualKeyCode::Down => {
-                        let new_selection;
-                        match selection {
-                            CharacterClassSelection::Warrior => new_selection = CharacterClassSelection::Sorceror,
-                            CharacterClassSelection::Sorceror => new_selection = CharacterClassSelection::Ranger,
-                            CharacterClassSelection::Ranger => new_selection = CharacterClassSelection::Warrior
-                        }
+                        let new_selection = (selection + 1) % count;
                         return CharacterClassResult::NoSelection { selected: new_selection };
                     },
-                    VirtualKeyCode::Return => return CharacterClassResult::Selected { selected: selection },
+                    VirtualKeyCode::Return => return CharacterClassResult::Selected { selected: class_names[selection].clone() },
                     _ => return CharacterClassResult::NoSelection { selected: selection }
                 }
             }
         }
     }
 
-    CharacterClassResult::NoSelection { selected: CharacterClassSelection::Warrior }
+    CharacterClassResult::NoSelection { selected: 0 }
 }
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use specs::prelude::*;
+use rltk::prelude::*;
+use super::{black, box_height, item_tooltip, menu_box, red, white, y_start, yellow, green};
+use crate::{InBackpack, Item, Name, State};
+use crate::raws::{self, get_item_class_colour, ItemData, RecipeData};
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum CraftingResult { NoResponse, Cancel, Craft }
+
+pub fn backpack_item_counts(ecs: &World) -> HashMap<String, i32> {
+    let player_entity = ecs.fetch::<Entity>();
+    let backpacks = ecs.read_storage::<InBackpack>();
+    let items = ecs.read_storage::<Item>();
+
+    let mut counts: HashMap<String, i32> = HashMap::new();
+    for (item, backpack) in (&items, &backpacks).join() {
+        if backpack.owner == *player_entity {
+            *counts.entry(item.name.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+pub fn has_ingredients(recipe: &RecipeData, counts: &HashMap<String, i32>) -> bool {
+    recipe.ingredients.iter().all(|ingredient| {
+        *counts.get(&ingredient.name).unwrap_or(&0) >= ingredient.count
+    })
+}
+
+pub fn show_crafting_menu(gs: &mut State, ctx: &mut Rltk, station: Entity) -> (CraftingResult, Option<String>) {
+    let names = gs.ecs.read_storage::<Name>();
+    let mut draw_batch = DrawBatch::new();
+
+    let station_name = names.get(station).unwrap().name.clone();
+    // look everything up before rendering, the item tooltip needs the raws lock too
+    let (recipes, outputs) = {
+        let raw_master = raws::RAWS.lock().unwrap();
+        let recipes = raws::get_station_recipes(&station_name, &raw_master);
+        let outputs: Vec<(RGB, Option<ItemData>)> = recipes.iter().map(|recipe| {
+            match raws::get_item_template(&recipe.output, &raw_master) {
+                Some(item) => (get_item_class_colour(item.class.as_str(), &raw_master), Some(item)),
+                None => (white(), None)
+            }
+        }).collect();
+        (recipes, outputs)
+    };
+    let counts = backpack_item_counts(&gs.ecs);
+    let count = recipes.len();
+
+    let mut y = y_start(count);
+    menu_box(&mut draw_batch, 15, y, 75, box_height(count), format!("Craft what at the {}?", station_name));
+
+    let mouse_pos = ctx.mouse_pos();
+    let mut tooltip: Option<(ItemData, i32, i32)> = None;
+    y += 1;
+    for (j, recipe) in recipes.iter().enumerate() {
+        draw_batch.set(Point::new(18, y), ColorPair::new(white(), black()), rltk::to_cp437('('));
+        draw_batch.set(Point::new(19, y), ColorPair::new(yellow(), black()), 97+j as rltk::FontCharType);
+        draw_batch.set(Point::new(20, y), ColorPair::new(white(), black()), rltk::to_cp437(')'));
+
+        let (item_class_colour, output) = &outputs[j];
+        draw_batch.print_color(Point::new(23, y), &recipe.name, ColorPair::new(*item_class_colour, black()));
+
+        // ingredients are shown as have/need
+        let mut x = 48;
+        for ingredient in recipe.ingredients.iter() {
+            let have = *counts.get(&ingredient.name).unwrap_or(&0);
+            let colour = if have >= ingredient.count { green() } else { red() };
+            let text = format!("{} {}/{}", ingredient.name, have, ingredient.count);
+            draw_batch.print_color(Point::new(x, y), &text, ColorPair::new(colour, black()));
+            x += text.len() as i32 + 2;
+        }
+
+        if mouse_pos.0 >= 23 && mouse_pos.0 < 48 && mouse_pos.1 == y {
+            if let Some(item) = output {
+                tooltip = Some((item.clone(), 25, y));
+            }
+        }
+
+        y += 2;
+    }
+
+    draw_batch.submit(1000).expect("Draw batch submission failed");
+
+    if let Some((item, x, y)) = tooltip {
+        item_tooltip(item).render(&mut draw_batch, x, y);
+        draw_batch.submit(3500).expect("Draw batch submission failed");
+    }
+
+    match ctx.key {
+        None => (CraftingResult::NoResponse, None),
+        Some(key) => {
+            match key {
+                VirtualKeyCode::Escape => { (CraftingResult::Cancel, None) }
+                _ => {
+                    let selection = rltk::letter_to_option(key);
+                    if selection > -1 && selection < count as i32 {
+                        return (CraftingResult::Craft, Some(recipes[selection as usize].name.clone()));
+                    }
+                    (CraftingResult::NoResponse, None)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use specs::prelude::*;
+use rltk::prelude::*;
+use super::{black, menu_box, menu_option, red, white, yellow};
+use crate::{Hireable, Name, Pools, State};
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum HireResult { NoResponse, Cancel, Hire }
+
+pub fn show_hire_menu(gs: &mut State, ctx: &mut Rltk, mercenary: Entity) -> HireResult {
+    let names = gs.ecs.read_storage::<Name>();
+    let hireables = gs.ecs.read_storage::<Hireable>();
+    let pools = gs.ecs.read_storage::<Pools>();
+    let player_entity = gs.ecs.fetch::<Entity>();
+    let mut draw_batch = DrawBatch::new();
+
+    let name = names.get(mercenary).map_or("Mercenary".to_string(), |name| name.name.clone());
+    let cost = match hireables.get(mercenary) {
+        Some(hireable) => hireable.cost,
+        None => return HireResult::Cancel
+    };
+    let gold = pools.get(*player_entity).unwrap().gold;
+
+    let y = 20;
+    menu_box(&mut draw_batch, 20, y, 45, 6, format!("Hire the {}?", name));
+    draw_batch.print_color(
+        Point::new(23, y + 1),
+        format!("The {} will fight at your side.", name),
+        ColorPair::new(white(), black())
+    );
+    let cost_colour = if gold >= cost { yellow() } else { red() };
+    menu_option(&mut draw_batch, 23, y + 3, rltk::to_cp437('a'), format!("Hire for {} gold", cost), Some(cost_colour));
+
+    draw_batch.submit(1000).expect("Draw batch submission failed");
+
+    match ctx.key {
+        None => HireResult::NoResponse,
+        Some(key) => match key {
+            VirtualKeyCode::Escape => HireResult::Cancel,
+            VirtualKeyCode::A => HireResult::Hire,
+            _ => HireResult::NoResponse
+        }
+    }
+}
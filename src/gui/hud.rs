@@ -1,8 +1,9 @@
 use specs::prelude::*;
 use rltk::prelude::*;
-use super::{black, blue, box_gray, cyan, draw_requirement, draw_map_tooltips, gold, green, light_gray, orange, red, white, yellow};
-use crate::{carry_capacity_lbs, player_xp_for_level, AbilityType, ActiveQuests, Attribute, Attributes, Consumable, Duration, Entity,
-    Equipped, HungerClock, HungerState, InBackpack, Item, KnownAbilities, KnownAbility, Map, Name, Pools, Quest, Skill, Skills, StatusEffect
+use super::{black, blue, box_gray, cyan, draw_requirement, draw_map_tooltips, draw_weather, gold, green, light_gray, orange, red, white, yellow};
+use crate::{carry_capacity_lbs, player_xp_for_level, AbilityType, ActiveQuests, Attribute, Attributes, Consumable, Cursed, Duration, Entity,
+    Equipped, HungerClock, HungerState, InBackpack, Item, KnownAbilities, KnownAbility, Map, Name, Pools, Quest, Skill, Skills, StatusEffect,
+    GameClock, TimeOfDay
 };
 use crate::raws;
 use crate::gamelog;
@@ -28,6 +29,19 @@ fn draw_borders(ecs: &World, draw_batch: &mut DrawBatch) {
     draw_batch.set(Point::new(x_pos, 0), ColorPair::new(box_gray(), black()), to_cp437('┤'));
     draw_batch.set(Point::new(x_pos + name_length as i32 + 1, 0), ColorPair::new(box_gray(), black()), to_cp437('├'));
     draw_batch.print_color(Point::new(x_pos+1, 0), &map.name, ColorPair::new(white(), black()));
+
+    // game clock
+    let clock = ecs.fetch::<GameClock>();
+    let time = format!("Day {} {:02}:{:02}", clock.day(), clock.hour(), clock.minute());
+    let time_colour = match clock.time_of_day() {
+        TimeOfDay::Dawn | TimeOfDay::Dusk => orange(),
+        TimeOfDay::Day => yellow(),
+        TimeOfDay::Night => cyan()
+    };
+    let x_pos = 86 - time.len() as i32;
+    draw_batch.set(Point::new(x_pos - 1, 0), ColorPair::new(box_gray(), black()), to_cp437('┤'));
+    draw_batch.set(Point::new(86, 0), ColorPair::new(box_gray(), black()), to_cp437('├'));
+    draw_batch.print_color(Point::new(x_pos, 0), &time, ColorPair::new(time_colour, black()));
 }
 
 fn draw_stats(ecs: &World, draw_batch: &mut DrawBatch, player_entity: &Entity, y: &mut i32) {
@@ -59,6 +73,15 @@ fn draw_stats(ecs: &World, draw_batch: &mut DrawBatch, player_entity: &Entity, y
     );
     draw_batch.print_color(Point::new(106, *y), &mana, ColorPair::new(white(), black()));
 
+    // class resources
+    for (label, pool, colour) in [("Rage: ", &player_pools.rage, orange()), ("Focus: ", &player_pools.focus, green())] {
+        if pool.max < 1 { continue; }
+        *y += 1;
+        draw_batch.print_color(Point::new(90, *y), label, ColorPair::new(white(), black()));
+        draw_batch.bar_horizontal(Point::new(100, *y), 18, pool.current, pool.max, ColorPair::new(colour, black()));
+        draw_batch.print_color(Point::new(106, *y), format!("{}/{}", pool.current, pool.max), ColorPair::new(white(), black()));
+    }
+
     *y += 1;
     draw_batch.print_color(Point::new(90, *y), &level, ColorPair::new(white(), black()));
     draw_batch.bar_horizontal(
@@ -187,14 +210,19 @@ fn draw_skill(name: &str, skill: &Skill, y: i32, draw_batch: &mut DrawBatch) {
 fn draw_equipment(ecs: &World, draw_batch: &mut DrawBatch, player: &Entity, y: &mut i32) {
     let equipped = ecs.read_storage::<Equipped>();
     let items = ecs.read_storage::<Item>();
+    let cursed = ecs.read_storage::<Cursed>();
     *y += 1;
-    for (item, equipment) in (&items, &equipped).join() {
+    for (entity, item, equipment) in (&ecs.entities(), &items, &equipped).join() {
         if equipment.owner == *player {
+            let name = item.full_name();
             draw_batch.print_color(
                 Point::new(90, *y),
-                item.full_name(),
+                &name,
                 ColorPair::new(raws::get_item_colour(&item, &raws::RAWS.lock().unwrap()), black())
             );
+            if cursed.get(entity).is_some_and(|curse| curse.known) {
+                draw_batch.print_color(Point::new(91 + name.len() as i32, *y), "(cursed)", ColorPair::new(red(), black()));
+            }
             *y += 1;
         }
     }
@@ -231,17 +259,55 @@ fn draw_abilities(ecs: &World, draw_batch: &mut DrawBatch, player: &Entity, y: &
     let known_ability_lists = ecs.read_storage::<KnownAbilities>();
     let player_abilities = &known_ability_lists.get(*player).unwrap().abilities;
     let all_known_abilities = ecs.read_storage::<KnownAbility>();
+    let pools = ecs.read_storage::<Pools>();
+    let player_pools = pools.get(*player).unwrap();
     let mut index = 1;
     for ability_entity in player_abilities.iter() {
         let known_ability = all_known_abilities.get(*ability_entity).unwrap();
         if known_ability.ability_type == AbilityType::Active {
-            draw_batch.print_color(Point::new(90, *y), &format!("^{}", index), ColorPair::new(cyan(), black()));
+            // abilities that can't be used right now are greyed out
+            let usable = known_ability.is_ready() && known_ability.missing_resource(player_pools).is_none();
+            let fg = if usable { cyan() } else { box_gray() };
+            draw_batch.print_color(Point::new(90, *y), &format!("^{}", index), ColorPair::new(fg, black()));
+            draw_batch.print_color(Point::new(93, *y), &known_ability.name, ColorPair::new(fg, black()));
+
+            let mut x = 94 + known_ability.name.len() as i32;
+            for (resource, cost) in known_ability.costs() {
+                let colour = match resource {
+                    "rage" => orange(),
+                    "focus" => green(),
+                    _ => blue()
+                };
+                let cost = format!("({})", cost);
+                draw_batch.print_color(Point::new(x, *y), &cost, ColorPair::new(colour, black()));
+                x += cost.len() as i32 + 1;
+            }
+            if known_ability.max_charges > 1 {
+                let charges = format!("x{}", known_ability.charges);
+                draw_batch.print_color(Point::new(x, *y), &charges, ColorPair::new(fg, black()));
+                x += charges.len() as i32 + 1;
+            }
+            if !known_ability.is_ready() {
+                draw_batch.print_color(
+                    Point::new(x, *y),
+                    format!("[{}]", known_ability.recharge_turns),
+                    ColorPair::new(red(), black())
+                );
+            }
+            index += 1;
+            *y += 1;
+        }
+    }
+
+    // passives are always on so they have no hotkey
+    for ability_entity in player_abilities.iter() {
+        let known_ability = all_known_abilities.get(*ability_entity).unwrap();
+        if known_ability.ability_type == AbilityType::Passive {
             draw_batch.print_color(
                 Point::new(93, *y),
-                &format!("{} ({})", known_ability.name, known_ability.mana_cost),
-                ColorPair::new(cyan(), black())
+                format!("{} (passive)", known_ability.name),
+                ColorPair::new(green(), black())
             );
-            index += 1;
             *y += 1;
         }
     }
@@ -341,6 +407,7 @@ pub fn draw_ui(ecs: &World, ctx: &mut Rltk) {
 
     gamelog::print_log(&mut rltk::BACKEND_INTERNAL.lock().consoles[1].console, Point::new(2, 35));
     draw_map_tooltips(ecs, ctx);
+    draw_weather(ecs);
 
     draw_batch.submit(3000).expect("Draw batch submission failed");
 }
@@ -1,13 +1,16 @@
 use specs::prelude::*;
 use rltk::prelude::*;
 use super::{green, white, black, magenta, yellow, gold};
-use crate::{raws::find_ability_by_name, AttributeBonus, CharacterClass, ClassPassive, ClassPassiveLevel, SkillBonus, State, WantsToLevelUp, Ability};
+use crate::{raws::find_ability_by_name, AttributeBonus, CharacterClass, ClassPassive, ClassPassiveLevel, SkillBonus, State, WantsToLevelUp, Ability,
+    Attributes, Skills, ATTRIBUTE_NAMES, SKILL_NAMES};
 use std::collections::{BTreeMap, HashMap};
 
 pub enum LevelUpMenuResult {
     NoResponse,
     SelectedPassive,
     DeselectedPassive,
+    AllocatedPoint,
+    UndoneAllocation,
     Done
 }
 
@@ -17,6 +20,9 @@ pub fn show_levelup_menu(gs: &mut State, ctx: &mut Rltk) -> LevelUpMenuResult {
     let character_classes = gs.ecs.read_storage::<CharacterClass>();
     let player_class = character_classes.get(*player).unwrap();
     let level_up = level_ups.get_mut(*player).unwrap();
+    if level_up.allocating {
+        return show_allocation_step(gs, ctx, *player, level_up);
+    }
     let mut draw_batch = DrawBatch::new();
 
     draw_batch.draw_box(Rect::with_size(0, 0, 119, 99), ColorPair::new(white(), black()));
@@ -26,6 +32,13 @@ pub fn show_levelup_menu(gs: &mut State, ctx: &mut Rltk) -> LevelUpMenuResult {
         "Pick a passive ability to learn/improve",
         ColorPair::new(yellow(), black())
     );
+    if level_up.passive_points > 1 {
+        draw_batch.print_color(
+            Point::new(2, 6),
+            format!("Passive points remaining: {}", level_up.passive_points - selected_count(level_up, player_class)),
+            ColorPair::new(gold(), black())
+        );
+    }
 
     let mut y = 8;
     let mut j = 0;
@@ -34,7 +47,7 @@ pub fn show_levelup_menu(gs: &mut State, ctx: &mut Rltk) -> LevelUpMenuResult {
         let selection = rltk::to_char(97 + j);
         passive_selections.insert(selection.to_string(), name.clone());
 
-        draw_passive_choice(&mut draw_batch, &gs.ecs, &mut y, passive, format!("({})", selection), passive_selected(level_up, passive));
+        draw_passive_choice(&mut draw_batch, &gs.ecs, &mut y, passive, format!("({})", selection), level_up.passives[name].current_level);
         j += 1;
     }
     draw_batch.print_color(Point::new(102, 97), "ENTER when done", ColorPair::new(yellow(), black()));
@@ -60,12 +73,18 @@ pub fn show_levelup_menu(gs: &mut State, ctx: &mut Rltk) -> LevelUpMenuResult {
                     }
                 }
                 VirtualKeyCode::Return => {
-                    let mut selection_made = false;
-                    for (_name, passive) in player_class.passives.iter() {
-                        if passive_selected(level_up, passive) { selection_made = true; }
-                    }
+                    // every point must be spent unless there is nothing left to improve
+                    let available: i32 = player_class.passives.values()
+                        .map(|passive| passive.levels.len() as i32 - passive.current_level)
+                        .sum();
+                    let selection_made = selected_count(level_up, player_class) >= i32::min(level_up.passive_points, available)
+                        && selected_count(level_up, player_class) > 0;
 
                     if selection_made {
+                        if level_up.attribute_points > 0 || level_up.skill_points > 0 {
+                            level_up.allocating = true;
+                            return LevelUpMenuResult::NoResponse;
+                        }
                         return LevelUpMenuResult::Done;
                     } else {
                         return LevelUpMenuResult::NoResponse;
@@ -79,9 +98,116 @@ pub fn show_levelup_menu(gs: &mut State, ctx: &mut Rltk) -> LevelUpMenuResult {
     LevelUpMenuResult::NoResponse
 }
 
-fn draw_passive_choice(draw_batch: &mut DrawBatch, ecs: &World, y: &mut i32, passive: &ClassPassive, selection: String, selected: bool) {
+fn show_allocation_step(gs: &State, ctx: &mut Rltk, player: Entity, level_up: &mut WantsToLevelUp) -> LevelUpMenuResult {
+    let attributes = gs.ecs.read_storage::<Attributes>();
+    let skills = gs.ecs.read_storage::<Skills>();
+    let player_attributes = attributes.get(player).unwrap();
+    let player_skills = skills.get(player).unwrap();
+    let mut draw_batch = DrawBatch::new();
+
+    draw_batch.draw_box(Rect::with_size(0, 0, 119, 99), ColorPair::new(white(), black()));
+    draw_batch.print_color(Point::new(2, 2), "Level Up", ColorPair::new(yellow(), black()));
+    draw_batch.print_color(
+        Point::new(2, 4),
+        "Spend your attribute and skill points",
+        ColorPair::new(yellow(), black())
+    );
+
+    let attribute_values = [
+        player_attributes.strength.base,
+        player_attributes.dexterity.base,
+        player_attributes.constitution.base,
+        player_attributes.intelligence.base
+    ];
+    let skill_values = [
+        player_skills.melee.base,
+        player_skills.defence.base,
+        player_skills.ranged.base,
+        player_skills.magic.base
+    ];
+
+    let mut y = 8;
+    draw_batch.print_color(
+        Point::new(4, y),
+        format!("Attribute points remaining: {}", level_up.remaining_attribute_points()),
+        ColorPair::new(gold(), black())
+    );
+    y += 2;
+    let mut options: Vec<(&str, bool)> = Vec::new();
+    for (name, value) in ATTRIBUTE_NAMES.iter().zip(attribute_values.iter()) {
+        draw_allocation_choice(&mut draw_batch, y, options.len(), name, *value, level_up.allocated(name));
+        options.push((name, true));
+        y += 2;
+    }
+    y += 2;
+    draw_batch.print_color(
+        Point::new(4, y),
+        format!("Skill points remaining: {}", level_up.remaining_skill_points()),
+        ColorPair::new(gold(), black())
+    );
+    y += 2;
+    for (name, value) in SKILL_NAMES.iter().zip(skill_values.iter()) {
+        draw_allocation_choice(&mut draw_batch, y, options.len(), name, *value, level_up.allocated(name));
+        options.push((name, false));
+        y += 2;
+    }
+
+    draw_batch.print_color(Point::new(2, 97), "BACKSPACE to undo, ESCAPE to go back", ColorPair::new(yellow(), black()));
+    let all_spent = level_up.remaining_attribute_points() < 1 && level_up.remaining_skill_points() < 1;
+    if all_spent {
+        draw_batch.print_color(Point::new(102, 97), "ENTER when done", ColorPair::new(yellow(), black()));
+    }
+
+    draw_batch.submit(5000).expect("Draw batch submission failed");
+
+    match ctx.key {
+        None => {},
+        Some(key) => {
+            match key {
+                VirtualKeyCode::Back => {
+                    if level_up.allocations.pop().is_some() { return LevelUpMenuResult::UndoneAllocation; }
+                }
+                VirtualKeyCode::Escape => {
+                    level_up.allocating = false;
+                }
+                VirtualKeyCode::Return => {
+                    if all_spent { return LevelUpMenuResult::Done; }
+                }
+                _ => {
+                    let selection = rltk::letter_to_option(key);
+                    if selection > -1 && selection < options.len() as i32 {
+                        let (name, is_attribute) = options[selection as usize];
+                        let remaining = if is_attribute { level_up.remaining_attribute_points() } else { level_up.remaining_skill_points() };
+                        if remaining > 0 {
+                            level_up.allocations.push(name.to_string());
+                            return LevelUpMenuResult::AllocatedPoint;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    LevelUpMenuResult::NoResponse
+}
+
+fn draw_allocation_choice(draw_batch: &mut DrawBatch, y: i32, index: usize, name: &str, value: i32, allocated: i32) {
+    draw_batch.print_color(Point::new(4, y), format!("({})", rltk::to_char(97 + index as u8)), ColorPair::new(yellow(), black()));
+    draw_batch.print_color(Point::new(8, y), name, ColorPair::new(white(), black()));
+    if allocated > 0 {
+        draw_batch.print_color(
+            Point::new(24, y),
+            format!("{} (+{})", value + allocated, allocated),
+            ColorPair::new(green(), black())
+        );
+    } else {
+        draw_batch.print_color(Point::new(24, y), value.to_string(), ColorPair::new(white(), black()));
+    }
+}
+
+fn draw_passive_choice(draw_batch: &mut DrawBatch, ecs: &World, y: &mut i32, passive: &ClassPassive, selection: String, display_level_int: i32) {
+    let selected = display_level_int != passive.current_level;
     let colour = if selected { green() } else if passive.is_max_level() { magenta() } else { white() };
-    let display_level_int = if selected { passive.current_level + 1 } else { passive.current_level };
 
     draw_batch.print_color(Point::new(4, *y), selection, ColorPair::new(yellow(), black()));
     draw_batch.print_color(Point::new(8, *y), passive.name.clone(), ColorPair::new(gold(), black()));
@@ -237,6 +363,12 @@ fn cumulative_level(passive: &ClassPassive, display_level: i32) -> Option<ClassP
     Some(ClassPassiveLevel { attribute_bonus, skill_bonus, learn_ability, level_ability })
 }
 
+fn selected_count(level_up: &WantsToLevelUp, player_class: &CharacterClass) -> i32 {
+    level_up.passives.iter()
+        .map(|(name, passive)| passive.current_level - player_class.passives[name].current_level)
+        .sum()
+}
+
 fn passive_selected(level_up: &mut WantsToLevelUp, passive: &ClassPassive) -> bool {
     for (name, pass) in level_up.passives.iter() {
         if name.as_str() == passive.name.as_str() {
@@ -257,6 +389,20 @@ fn handle_selection(selection: &str, passive_selections: HashMap<String, String>
     let passive_name = &passive_selections[selection];
     let passive = &player_class.passives[passive_name];
 
+    if level_up.passive_points > 1 {
+        // several points to spend, keep adding levels until the passive is maxed or points run out
+        let level_up_passive = &level_up.passives[passive_name];
+        let can_add = level_up_passive.current_level < level_up_passive.levels.len() as i32;
+        if can_add && selected_count(level_up, player_class) < level_up.passive_points {
+            level_up.passives.get_mut(passive_name).unwrap().current_level += 1;
+            return LevelUpMenuResult::SelectedPassive;
+        } else if passive_selected(level_up, passive) {
+            level_up.passives.get_mut(passive_name).unwrap().current_level = passive.current_level;
+            return LevelUpMenuResult::DeselectedPassive;
+        }
+        return LevelUpMenuResult::NoResponse;
+    }
+
     if passive_selected(level_up, &passive) {
         let level_up_passive = level_up.passives.get_mut(passive_name).unwrap();
         level_up_passive.current_level -= 1;
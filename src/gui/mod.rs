@@ -3,6 +3,7 @@ use rltk::RGB;
 mod main_menu;
 mod in_game_menu;
 mod character_class_menu;
+mod species_menu;
 mod hud;
 mod tooltips;
 mod ranged_target;
@@ -15,9 +16,15 @@ mod quest_giver_menu;
 mod levelup_menu;
 mod game_over_menu;
 mod cheat_menu;
+mod crafting_menu;
+mod hire_menu;
+mod world_map;
+mod travel_menu;
+mod weather;
 pub use main_menu::*;
 pub use in_game_menu::*;
 pub use character_class_menu::*;
+pub use species_menu::*;
 pub use hud::*;
 pub use tooltips::*;
 pub use ranged_target::*;
@@ -30,6 +37,11 @@ pub use quest_giver_menu::*;
 pub use levelup_menu::*;
 pub use game_over_menu::*;
 pub use cheat_menu::*;
+pub use crafting_menu::*;
+pub use hire_menu::*;
+pub use world_map::*;
+pub use travel_menu::*;
+pub use weather::*;
 
 pub fn white() -> RGB { RGB::named(rltk::WHITE) }
 pub fn black() -> RGB { RGB::named(rltk::BLACK) }
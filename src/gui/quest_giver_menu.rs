@@ -115,6 +115,13 @@ pub fn show_quest_giver_menu(gs: &mut State, ctx: &mut Rltk, quest_giver: Entity
           ColorPair::new(cyan(), black())
         ); y += 2;
       }
+      if reward.remove_curses {
+        draw_batch.print_color(
+          Point::new(6, y),
+          "Curses lifted",
+          ColorPair::new(white(), black())
+        ); y += 2;
+      }
     }
 
     y += 4;
@@ -53,7 +53,7 @@ pub fn ranged_target(gs: &mut State, ctx: &mut Rltk, min_range: f32, max_range:
             // display projected area of effect
             let map = gs.ecs.fetch::<Map>();
             // use the position of the mouse on the map for calculation
-            let points = aoe_points(&*map, Point::new(mouse_map_pos.0, mouse_map_pos.1), ability_aoe.radius);
+            let points = aoe_points(&*map, *player_pos, Point::new(mouse_map_pos.0, mouse_map_pos.1), ability_aoe);
             for point in points.iter() {
                 // use the position of the mouse on the screen for display
                 draw_batch.set_bg(Point::new(point.x - min_x + 1, point.y - min_y + 1), cyan());
@@ -0,0 +1,59 @@
+use rltk::prelude::*;
+use super::{yellow, black, white, green};
+use crate::{State, RunState};
+use crate::raws;
+
+#[derive(PartialEq, Clone)]
+pub enum SpeciesResult { NoSelection{ selected: usize }, Selected{ selected: String } }
+
+pub fn species_select_menu(gs: &mut State, ctx: &mut Rltk) -> SpeciesResult {
+    let runstate = gs.ecs.fetch::<RunState>();
+    let mut draw_batch = DrawBatch::new();
+
+    draw_batch.print_color_centered(15, "Roguelike", ColorPair::new(yellow(), black()));
+    draw_batch.print_color_centered(18, "Choose your species", ColorPair::new(yellow(), black()));
+
+    if let RunState::SpeciesSelectMenu{ menu_selection: selection, .. } = *runstate {
+        let raw_master = raws::RAWS.lock().unwrap();
+        let species_names = raws::get_playable_species_names(&raw_master);
+        if species_names.is_empty() { return SpeciesResult::NoSelection { selected: 0 }; }
+
+        let mut y = 26;
+        for (i, species_name) in species_names.iter().enumerate() {
+            if i == selection {
+                draw_batch.print_color_centered(y, species_name, ColorPair::new(green(), black()));
+                draw_batch.print_color_centered(
+                    y + 2,
+                    raws::get_species_description(&raw_master, species_name).unwrap(),
+                    ColorPair::new(green(), black())
+                );
+            } else {
+                draw_batch.print_color_centered(y, species_name, ColorPair::new(white(), black()));
+            }
+            y += 6;
+        }
+
+        draw_batch.submit(2000).expect("Draw batch submission failed");
+
+        let count = species_names.len();
+        match ctx.key {
+            None => return SpeciesResult::NoSelection { selected: selection },
+            Some(key) => {
+                match key {
+                    VirtualKeyCode::Up => {
+                        let new_selection = if selection == 0 { count - 1 } else { selection - 1 };
+                        return SpeciesResult::NoSelection { selected: new_selection };
+                    },
+                    VirtualKeyCode::Down => {
+                        let new_selection = (selection + 1) % count;
+                        return SpeciesResult::NoSelection { selected: new_selection };
+                    },
+                    VirtualKeyCode::Return => return SpeciesResult::Selected { selected: species_names[selection].clone() },
+                    _ => return SpeciesResult::NoSelection { selected: selection }
+                }
+            }
+        }
+    }
+
+    SpeciesResult::NoSelection { selected: 0 }
+}
@@ -2,7 +2,8 @@ use std::fmt::Display;
 use specs::prelude::*;
 use rltk::prelude::*;
 use super::{black, box_gray, light_gray, white, green, red};
-use crate::{Map, Name, Position, Pools, StatusEffect, Duration, Item, AttributeBonus, Equippable, SkillBonus, Weapon, Wearable, TileType};
+use crate::{Map, Name, Position, Pools, TileSize, StatusEffect, Duration, Item, AttributeBonus, Equippable, SkillBonus, Weapon, Wearable, TileType, RegenBonus, Cursed,
+    Durability, Hidden, Locked};
 use crate::camera;
 use crate::raws::{self, ItemData};
 
@@ -108,8 +109,11 @@ pub fn draw_map_tooltips(ecs: &World, ctx : &mut Rltk) {
     }
 
     // entities
-    for (entity, name, position) in (&entities, &names, &positions).join() {
-        if position.x == mouse_map_pos.0 && position.y == mouse_map_pos.1 {
+    let hidden = ecs.read_storage::<Hidden>();
+    let sizes = ecs.read_storage::<TileSize>();
+    for (entity, name, position, _hidden) in (&entities, &names, &positions, !&hidden).join() {
+        // large entities can be hovered over on any tile they cover
+        if map.footprint(map.xy_idx(position.x, position.y), sizes.get(entity)).contains(&idx) {
             if let Some(item) = items.get(entity) {
                 tip_boxes.push(ground_item_tooltip(ecs, item.full_name(), entity));
                 continue;
@@ -132,6 +136,9 @@ pub fn draw_map_tooltips(ecs: &World, ctx : &mut Rltk) {
                 tip.add(format!("Level: {}", stat.level));
                 tip.add(format!("HP: {}/{}", stat.hit_points.current, stat.hit_points.max));
             }
+            if ecs.read_storage::<Locked>().get(entity).is_some() {
+                tip.add_colored("Locked".to_string(), red());
+            }
 
             tip_boxes.push(tip);
         }
@@ -189,12 +196,25 @@ pub fn item_entity_tooltip(ecs: &World, name: String, entity: Entity) -> Tooltip
     let items = ecs.read_storage::<Item>();
     let skill_bonuses = ecs.read_storage::<SkillBonus>();
     let attribute_bonuses = ecs.read_storage::<AttributeBonus>();
+    let regen_bonuses = ecs.read_storage::<RegenBonus>();
 
     let mut tooltip = Tooltip::new();
     if let Some(item) = items.get(entity) {
         tooltip.set_color(raws::get_item_colour(item, &raws::RAWS.lock().unwrap()));
     }
     tooltip.add(name);
+    if let Some(item) = items.get(entity) {
+        if item.unidentified_name.is_some() {
+            tooltip.add_colored("Unidentified".to_string(), red());
+            if let Some(equippable) = equippables.get(entity) {
+                tooltip.add(format!("Slot: {:?}", equippable.slot));
+            }
+            return tooltip;
+        }
+        if ecs.read_storage::<Cursed>().get(entity).is_some_and(|curse| curse.known) {
+            tooltip.add_colored("Cursed".to_string(), red());
+        }
+    }
 
     if let Some(weapon) = weapons.get(entity) {
         tooltip.add(format!("Attribute: {:?}", weapon.attribute));
@@ -203,6 +223,9 @@ pub fn item_entity_tooltip(ecs: &World, name: String, entity: Entity) -> Tooltip
 
         let range = if let Some(range) = weapon.range { range.to_string() } else { "melee".to_string() };
         tooltip.add(format!("Range: {}", range));
+        if let Some(proc_chance) = weapon.proc_chance {
+            tooltip.add(format!("Proc chance: {}%", (proc_chance * 100.0) as i32));
+        }
     }
     if let Some(wearable) = wearables.get(entity) {
         tooltip.add(format!("Armour class: {}", wearable.armour_class));
@@ -210,6 +233,14 @@ pub fn item_entity_tooltip(ecs: &World, name: String, entity: Entity) -> Tooltip
     if let Some(equippable) = equippables.get(entity) {
         tooltip.add(format!("Slot: {:?}", equippable.slot));
     }
+    if let Some(durability) = ecs.read_storage::<Durability>().get(entity) {
+        let text = format!("Durability: {}/{}", durability.current, durability.max);
+        if durability.current * 2 <= durability.max {
+            tooltip.add_colored(text, red());
+        } else {
+            tooltip.add(text);
+        }
+    }
 
     if let Some(attribute_bonus) = attribute_bonuses.get(entity) {
         add_bonus_line(&mut tooltip, attribute_bonus.strength, "Strength".to_string());
@@ -223,6 +254,10 @@ pub fn item_entity_tooltip(ecs: &World, name: String, entity: Entity) -> Tooltip
         add_bonus_line(&mut tooltip, skill_bonus.magic, "Magic".to_string());
         add_bonus_line(&mut tooltip, skill_bonus.ranged, "Ranged".to_string());
     }
+    if let Some(regen_bonus) = regen_bonuses.get(entity) {
+        add_bonus_line(&mut tooltip, regen_bonus.health, "Health regen".to_string());
+        add_bonus_line(&mut tooltip, regen_bonus.mana, "Mana regen".to_string());
+    }
 
     tooltip
 }
@@ -0,0 +1,57 @@
+use specs::prelude::*;
+use rltk::prelude::*;
+use super::{menu_box, menu_option, y_start, box_height, ItemMenuResult};
+use crate::{Map, MapMarker, Name, Position, TileType, Vendor};
+
+pub fn show_travel_menu(ecs: &World, ctx: &mut Rltk) -> (ItemMenuResult, Option<usize>) {
+    let map = ecs.fetch::<Map>();
+    let entities = ecs.entities();
+    let names = ecs.read_storage::<Name>();
+    let positions = ecs.read_storage::<Position>();
+    let vendors = ecs.read_storage::<Vendor>();
+    let markers = ecs.read_storage::<MapMarker>();
+    let mut draw_batch = DrawBatch::new();
+
+    // known exits, vendors and points of interest
+    let mut destinations: Vec<(String, usize)> = Vec::new();
+    for (idx, tile) in map.tiles.iter().enumerate() {
+        if !map.revealed_tiles[idx] { continue; }
+        match tile {
+            TileType::NextArea{ map_name } => destinations.push((format!("Exit to {}", map_name), idx)),
+            TileType::PreviousArea{ map_name } => destinations.push((format!("Back to {}", map_name), idx)),
+            _ => {}
+        }
+    }
+    for (entity, name, pos) in (&entities, &names, &positions).join() {
+        let idx = map.xy_idx(pos.x, pos.y);
+        if (vendors.get(entity).is_some() && map.revealed_tiles[idx]) || markers.get(entity).is_some() {
+            destinations.push((name.name.clone(), idx));
+        }
+    }
+    destinations.truncate(26);
+
+    let count = destinations.len();
+    let mut y = y_start(count);
+    menu_box(&mut draw_batch, 25, y, 40, box_height(count), "Travel where?");
+    y += 1;
+    for (j, (label, _)) in destinations.iter().enumerate() {
+        menu_option(&mut draw_batch, 27, y, 97 + j as FontCharType, label, None);
+        y += 2;
+    }
+
+    draw_batch.submit(1000).expect("Draw batch submission failed");
+
+    match ctx.key {
+        None => (ItemMenuResult::NoResponse, None),
+        Some(key) => match key {
+            VirtualKeyCode::Escape => (ItemMenuResult::Cancel, None),
+            _ => {
+                let selection = rltk::letter_to_option(key);
+                if selection > -1 && selection < count as i32 {
+                    return (ItemMenuResult::Selected, Some(destinations[selection as usize].1));
+                }
+                (ItemMenuResult::NoResponse, None)
+            }
+        }
+    }
+}
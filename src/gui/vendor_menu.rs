@@ -1,11 +1,11 @@
 use specs::prelude::*;
 use rltk::prelude::*;
 use super::{black, box_height, item_entity_tooltip, item_tooltip, menu_box, white, y_start, yellow};
-use crate::{Consumable, InBackpack, Item, ItemClass, ItemQuality, State, Vendor};
+use crate::{CharacterClass, Consumable, Cursed, Durability, Equipped, InBackpack, Item, ItemClass, ItemQuality, Pools, State, Vendor};
 use crate::raws::{self, get_item_class_colour, ItemData};
 
 #[derive(PartialEq, Copy, Clone, Debug)]
-pub enum VendorMode { Buy, Sell, Improve }
+pub enum VendorMode { Buy, Sell, Improve, Repair, Identify, Uncurse, Respec }
 
 #[derive(PartialEq, Copy, Clone)]
 pub enum VendorResult {
@@ -14,16 +14,28 @@ pub enum VendorResult {
     Buy,
     Sell,
     Improve,
+    Repair,
+    Identify,
+    Uncurse,
+    Respec,
     BuyMode,
     SellMode,
-    ImproveMode
+    ImproveMode,
+    RepairMode,
+    IdentifyMode,
+    UncurseMode,
+    RespecMode
 }
 
 pub fn show_vendor_menu(gs: &mut State, ctx: &mut Rltk, vendor: Entity, mode: VendorMode) -> (VendorResult, Option<Entity>, Option<String>, Option<i32>) {
     match mode {
         VendorMode::Buy => vendor_buy_menu(gs, ctx, vendor),
         VendorMode::Sell => vendor_sell_menu(gs, ctx),
-        VendorMode::Improve => vendor_improve_menu(gs, ctx, vendor)
+        VendorMode::Improve => vendor_improve_menu(gs, ctx, vendor),
+        VendorMode::Repair => vendor_repair_menu(gs, ctx, vendor),
+        VendorMode::Identify => vendor_identify_menu(gs, ctx),
+        VendorMode::Uncurse => vendor_uncurse_menu(gs, ctx),
+        VendorMode::Respec => vendor_respec_menu(gs, ctx)
     }
 }
 
@@ -53,7 +65,7 @@ fn vendor_sell_menu(gs: &mut State, ctx: &mut Rltk) -> (VendorResult, Option<Ent
                 item.full_name(),
                 ColorPair::new(raws::get_item_colour(&item, &raws::RAWS.lock().unwrap()), black())
             );
-            draw_batch.print(Point::new(67, y), &format!("{:.0} gp", item.base_value as f32 * 0.8));
+            draw_batch.print(Point::new(67, y), format!("{:.0} gp", item.value() as f32 * 0.8));
 
             if mouse_pos.0 >= 28 && mouse_pos.0 < 67 && mouse_pos.1 == y {
                 tooltip = Some((entity, item.full_name(), y));
@@ -94,11 +106,19 @@ fn vendor_buy_menu(gs: &mut State, ctx: &mut Rltk, vendor: Entity) -> (VendorRes
     let vendors = gs.ecs.read_storage::<Vendor>();
     let mut draw_batch = DrawBatch::new();
 
-    let inventory = raws::get_vendor_items(&vendors.get(vendor).unwrap().category, &raws::RAWS.lock().unwrap());
+    let category = &vendors.get(vendor).unwrap().category;
+    let inventory = raws::get_vendor_items(category, &raws::RAWS.lock().unwrap());
     let count = inventory.len();
+    // the temple doesn't improve items but can lift curses instead
+    let is_temple = category == "temple";
 
     let mut y = y_start(count);
-    menu_box(&mut draw_batch, 20, y, 55, box_height(count), "Buy which item? (SPACE to switch to improve mode)");
+    let title = if is_temple {
+        "Buy which item? (SPACE to switch to uncurse mode)"
+    } else {
+        "Buy which item? (SPACE to switch to improve mode)"
+    };
+    menu_box(&mut draw_batch, 20, y, 55, box_height(count), title);
 
     let mouse_pos = ctx.mouse_pos();
     let mut tooltip: Option<(ItemData, i32, i32)> = None;
@@ -135,7 +155,9 @@ fn vendor_buy_menu(gs: &mut State, ctx: &mut Rltk, vendor: Entity) -> (VendorRes
         None => (VendorResult::NoResponse, None, None, None),
         Some(key) => {
             match key {
-                VirtualKeyCode::Space => { (VendorResult::ImproveMode, None, None, None) }
+                VirtualKeyCode::Space => {
+                    if is_temple { (VendorResult::UncurseMode, None, None, None) } else { (VendorResult::ImproveMode, None, None, None) }
+                }
                 VirtualKeyCode::Escape => { (VendorResult::Cancel, None, None, None) }
                 _ => {
                     let selection = rltk::letter_to_option(key);
@@ -155,28 +177,94 @@ fn vendor_buy_menu(gs: &mut State, ctx: &mut Rltk, vendor: Entity) -> (VendorRes
 }
 
 fn vendor_improve_menu(gs: &mut State, ctx: &mut Rltk, vendor_entity: Entity) -> (VendorResult, Option<Entity>, Option<String>, Option<i32>) {
-    let player_entity = gs.ecs.fetch::<Entity>();
     let backpacks = gs.ecs.read_storage::<InBackpack>();
-    let items = gs.ecs.read_storage::<Item>();
     let consumables = gs.ecs.read_storage::<Consumable>();
     let vendors = gs.ecs.read_storage::<Vendor>();
     let vendor = vendors.get(vendor_entity).unwrap();
-    let entities = gs.ecs.entities();
+
+    vendor_item_list_menu(
+        &gs.ecs, ctx,
+        "Improve which item? (SPACE to switch to repair mode)",
+        VendorResult::Improve, VendorResult::RepairMode,
+        |entity, item| backpacks.get(entity).is_some() && consumables.get(entity).is_none() && item_can_be_improved(item, &vendor.category),
+        |_, item| item.value() * 2
+    )
+}
+
+fn vendor_repair_menu(gs: &mut State, ctx: &mut Rltk, vendor_entity: Entity) -> (VendorResult, Option<Entity>, Option<String>, Option<i32>) {
+    let durabilities = gs.ecs.read_storage::<Durability>();
+    let vendors = gs.ecs.read_storage::<Vendor>();
+    let vendor = vendors.get(vendor_entity).unwrap();
+
+    vendor_item_list_menu(
+        &gs.ecs, ctx,
+        "Repair which item? (SPACE to switch to identify mode)",
+        VendorResult::Repair, VendorResult::IdentifyMode,
+        |entity, item| {
+            item.vendor_category.as_ref().is_some_and(|category| *category == vendor.category)
+                && durabilities.get(entity).is_some_and(|durability| durability.current < durability.max)
+        },
+        |entity, item| repair_cost(item, durabilities.get(entity).unwrap())
+    )
+}
+
+fn repair_cost(item: &Item, durability: &Durability) -> i32 {
+    let missing = (durability.max - durability.current) as f32 / durability.max as f32;
+    i32::max(1, (item.value() as f32 * missing * 0.5) as i32)
+}
+
+fn vendor_identify_menu(gs: &mut State, ctx: &mut Rltk) -> (VendorResult, Option<Entity>, Option<String>, Option<i32>) {
+    vendor_item_list_menu(
+        &gs.ecs, ctx,
+        "Identify which item? (SPACE to switch to sell mode)",
+        VendorResult::Identify, VendorResult::SellMode,
+        |_, item| item.unidentified_name.is_some(),
+        |_, item| i32::max(10, item.value() / 2)
+    )
+}
+
+fn vendor_uncurse_menu(gs: &mut State, ctx: &mut Rltk) -> (VendorResult, Option<Entity>, Option<String>, Option<i32>) {
+    let cursed = gs.ecs.read_storage::<Cursed>();
+
+    vendor_item_list_menu(
+        &gs.ecs, ctx,
+        "Uncurse which item? (SPACE to switch to respec mode)",
+        VendorResult::Uncurse, VendorResult::RespecMode,
+        |entity, _| cursed.get(entity).is_some_and(|curse| curse.known),
+        |_, item| i32::max(50, item.value())
+    )
+}
+
+// lists the player's carried and worn items that a vendor service can be used on
+fn vendor_item_list_menu(
+    ecs: &World,
+    ctx: &mut Rltk,
+    title: &str,
+    selected: VendorResult,
+    next_mode: VendorResult,
+    filter: impl Fn(Entity, &Item) -> bool,
+    cost_fn: impl Fn(Entity, &Item) -> i32
+) -> (VendorResult, Option<Entity>, Option<String>, Option<i32>) {
+    let player_entity = ecs.fetch::<Entity>();
+    let backpacks = ecs.read_storage::<InBackpack>();
+    let equipped = ecs.read_storage::<Equipped>();
+    let items = ecs.read_storage::<Item>();
+    let entities = ecs.entities();
     let mut draw_batch = DrawBatch::new();
 
     let mut inventory: Vec<(Entity, &Item, String, i32)> = Vec::new();
-    for (entity, item, backpack) in (&entities, &items, &backpacks).join() {
-        if backpack.owner == *player_entity && consumables.get(entity).is_none() {
-            if item_can_be_improved(item, &vendor.category) {
-                inventory.push((entity, item, item.full_name(), item.base_value * 2));
-            }
+    for (entity, item) in (&entities, &items).join() {
+        let owned = backpacks.get(entity).is_some_and(|backpack| backpack.owner == *player_entity)
+            || equipped.get(entity).is_some_and(|equipped| equipped.owner == *player_entity);
+        if owned && filter(entity, item) {
+            inventory.push((entity, item, item.full_name(), cost_fn(entity, item)));
         }
     }
     inventory.sort_by(|a,b| a.3.partial_cmp(&b.3).unwrap());
 
     let count = inventory.len();
     let mut y = y_start(count);
-    menu_box(&mut draw_batch, 20, y, 55, box_height(count), "Improve which item? (SPACE to switch to sell mode)");
+    menu_box(&mut draw_batch, 20, y, 55, box_height(count), title);
 
     let mouse_pos = ctx.mouse_pos();
     let mut tooltip: Option<(Entity, String, i32, i32)> = None;
@@ -194,7 +282,7 @@ fn vendor_improve_menu(gs: &mut State, ctx: &mut Rltk, vendor_entity: Entity) ->
         draw_batch.print(Point::new(67, y), format!("{} gp", cost));
 
         if tooltip.is_none() && mouse_pos.0 >= 28 && mouse_pos.0 < 67 && mouse_pos.1 == y {
-            tooltip = Some((*entity, item.full_name(), 30, y));
+            tooltip = Some((*entity, name.clone(), 30, y));
         }
 
         y += 2;
@@ -203,7 +291,7 @@ fn vendor_improve_menu(gs: &mut State, ctx: &mut Rltk, vendor_entity: Entity) ->
     draw_batch.submit(1000).expect("Draw batch submission failed");
 
     if let Some((entity, name, x, y)) = tooltip {
-        item_entity_tooltip(&gs.ecs, name, entity).render(&mut draw_batch, x, y);
+        item_entity_tooltip(ecs, name, entity).render(&mut draw_batch, x, y);
         draw_batch.submit(3500).expect("Draw batch submission failed");
     }
 
@@ -211,12 +299,12 @@ fn vendor_improve_menu(gs: &mut State, ctx: &mut Rltk, vendor_entity: Entity) ->
         None => (VendorResult::NoResponse, None, None, None),
         Some(key) => {
             match key {
-                VirtualKeyCode::Space => { (VendorResult::SellMode, None, None, None) }
+                VirtualKeyCode::Space => { (next_mode, None, None, None) }
                 VirtualKeyCode::Escape => { (VendorResult::Cancel, None, None, None) }
                 _ => {
                     let selection = rltk::letter_to_option(key);
                     if selection > -1 && selection < count as i32 {
-                        return (VendorResult::Improve, Some(inventory[selection as usize].0), None, Some(inventory[selection as usize].3));
+                        return (selected, Some(inventory[selection as usize].0), None, Some(inventory[selection as usize].3));
                     }
                     (VendorResult::NoResponse, None, None, None)
                 }
@@ -225,6 +313,49 @@ fn vendor_improve_menu(gs: &mut State, ctx: &mut Rltk, vendor_entity: Entity) ->
     }
 }
 
+fn vendor_respec_menu(gs: &mut State, ctx: &mut Rltk) -> (VendorResult, Option<Entity>, Option<String>, Option<i32>) {
+    let player_entity = gs.ecs.fetch::<Entity>();
+    let pools = gs.ecs.read_storage::<Pools>();
+    let character_classes = gs.ecs.read_storage::<CharacterClass>();
+    let mut draw_batch = DrawBatch::new();
+
+    let cost = respec_cost(pools.get(*player_entity).unwrap().level);
+    let passive_levels: i32 = character_classes.get(*player_entity)
+        .map_or(0, |class| class.passives.values().map(|passive| passive.current_level).sum());
+
+    let mut y = y_start(1);
+    menu_box(&mut draw_batch, 20, y, 55, box_height(1), "Unlearn passives? (SPACE to switch to sell mode)");
+
+    y += 1;
+    draw_batch.set(Point::new(23, y), ColorPair::new(white(), black()), rltk::to_cp437('('));
+    draw_batch.set(Point::new(24, y), ColorPair::new(yellow(), black()), rltk::to_cp437('a'));
+    draw_batch.set(Point::new(25, y), ColorPair::new(white(), black()), rltk::to_cp437(')'));
+    draw_batch.print_color(
+        Point::new(28, y),
+        format!("Reset {} passive levels", passive_levels),
+        ColorPair::new(white(), black())
+    );
+    draw_batch.print(Point::new(67, y), format!("{} gp", cost));
+
+    draw_batch.submit(1000).expect("Draw batch submission failed");
+
+    match ctx.key {
+        None => (VendorResult::NoResponse, None, None, None),
+        Some(key) => {
+            match key {
+                VirtualKeyCode::Space => { (VendorResult::SellMode, None, None, None) }
+                VirtualKeyCode::Escape => { (VendorResult::Cancel, None, None, None) }
+                VirtualKeyCode::A => { (VendorResult::Respec, None, None, Some(cost)) }
+                _ => { (VendorResult::NoResponse, None, None, None) }
+            }
+        }
+    }
+}
+
+pub fn respec_cost(level: i32) -> i32 {
+    100 * level
+}
+
 fn item_can_be_improved(item: &Item, vendor_category: &String) -> bool {
     if item.class == ItemClass::Set || item.class == ItemClass::Unique { return false; }
 
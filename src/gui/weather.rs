@@ -0,0 +1,32 @@
+use specs::prelude::*;
+use rltk::prelude::*;
+use crate::{GameClock, Map, Weather};
+
+// the map area in sparse console coordinates
+const OVERLAY_WIDTH: i32 = 87;
+const OVERLAY_HEIGHT: i32 = 34;
+
+pub fn draw_weather(ecs: &World) {
+    let map = ecs.fetch::<Map>();
+    if map.indoors { return; }
+
+    let (count, glyph, colour) = match map.weather {
+        Weather::Clear => return,
+        Weather::Rain => (60, '/', RGB::from_f32(0.4, 0.6, 1.0)),
+        Weather::Storm => (140, '/', RGB::from_f32(0.6, 0.7, 1.0)),
+        Weather::Fog => (220, '░', RGB::from_f32(0.45, 0.45, 0.45))
+    };
+
+    // drawn over the map without hiding it
+    let transparent = RGBA::from_f32(0., 0., 0., 0.);
+    // seeded from the turn so the particles only move when time passes
+    let mut rng = RandomNumberGenerator::seeded(ecs.fetch::<GameClock>().turn as u64);
+    let mut draw_batch = DrawBatch::new();
+    draw_batch.target(1);
+    for _ in 0..count {
+        let x = rng.range(1, OVERLAY_WIDTH);
+        let y = rng.range(1, OVERLAY_HEIGHT);
+        draw_batch.set(Point::new(x, y), ColorPair::new(colour, transparent), to_cp437(glyph));
+    }
+    draw_batch.submit(2500).expect("Draw batch submission failed");
+}
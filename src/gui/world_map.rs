@@ -0,0 +1,206 @@
+use std::collections::{HashMap, VecDeque};
+use specs::prelude::*;
+use rltk::prelude::*;
+use super::{black, box_gray, light_gray, white, yellow};
+use crate::{tile_glyph, Chest, Hidden, Map, MasterDungeonMap, Position, QuestGiver, Renderable, Vendor};
+
+const SCREEN_WIDTH: i32 = 120;
+const SCREEN_HEIGHT: i32 = 100;
+const MAP_TOP: i32 = 4;
+const MAP_LEFT: i32 = 2;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum WorldMapTab { Level, Dungeon }
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum WorldMapResult { NoResponse, Cancel, SwitchTab { tab: WorldMapTab } }
+
+pub fn show_world_map(ecs: &World, ctx: &mut Rltk, tab: WorldMapTab) -> WorldMapResult {
+    let mut draw_batch = DrawBatch::new();
+
+    draw_batch.draw_box(
+        Rect::with_size(0, 0, SCREEN_WIDTH - 1, SCREEN_HEIGHT - 1),
+        ColorPair::new(box_gray(), black())
+    );
+    draw_tabs(&mut draw_batch, tab);
+    match tab {
+        WorldMapTab::Level => draw_level(ecs, &mut draw_batch),
+        WorldMapTab::Dungeon => draw_dungeon(ecs, &mut draw_batch)
+    }
+    draw_batch.print_color(
+        Point::new(3, SCREEN_HEIGHT - 1),
+        "TAB to switch view, ESCAPE to close",
+        ColorPair::new(yellow(), black())
+    );
+
+    draw_batch.submit(1000).expect("Draw batch submission failed");
+
+    match ctx.key {
+        None => WorldMapResult::NoResponse,
+        Some(key) => match key {
+            VirtualKeyCode::Escape | VirtualKeyCode::M => WorldMapResult::Cancel,
+            VirtualKeyCode::Tab => {
+                let next = if tab == WorldMapTab::Level { WorldMapTab::Dungeon } else { WorldMapTab::Level };
+                WorldMapResult::SwitchTab{ tab: next }
+            }
+            _ => WorldMapResult::NoResponse
+        }
+    }
+}
+
+fn draw_tabs(draw_batch: &mut DrawBatch, tab: WorldMapTab) {
+    let (level_colour, dungeon_colour) = match tab {
+        WorldMapTab::Level => (yellow(), box_gray()),
+        WorldMapTab::Dungeon => (box_gray(), yellow())
+    };
+    draw_batch.print_color(Point::new(3, 1), "[ Level ]", ColorPair::new(level_colour, black()));
+    draw_batch.print_color(Point::new(14, 1), "[ Dungeon ]", ColorPair::new(dungeon_colour, black()));
+}
+
+fn draw_level(ecs: &World, draw_batch: &mut DrawBatch) {
+    let map = ecs.fetch::<Map>();
+    let available_width = SCREEN_WIDTH - MAP_LEFT * 2;
+    let available_height = SCREEN_HEIGHT - MAP_TOP - 4;
+
+    // shrink larger maps so the whole level fits on screen
+    let scale = i32::max(1, i32::max(
+        (map.width + available_width - 1) / available_width,
+        (map.height + available_height - 1) / available_height
+    ));
+
+    draw_batch.print_color(
+        Point::new(30, 1),
+        format!("{} (level {})", map.name, map.area_level),
+        ColorPair::new(white(), black())
+    );
+
+    for sy in 0..(map.height + scale - 1) / scale {
+        for sx in 0..(map.width + scale - 1) / scale {
+            // use the first revealed tile in each block
+            let mut revealed: Option<usize> = None;
+            'block: for cy in 0..scale {
+                for cx in 0..scale {
+                    let (tx, ty) = (sx * scale + cx, sy * scale + cy);
+                    if tx >= map.width || ty >= map.height { continue; }
+                    let idx = map.xy_idx(tx, ty);
+                    if map.revealed_tiles[idx] {
+                        revealed = Some(idx);
+                        break 'block;
+                    }
+                }
+            }
+            if let Some(idx) = revealed {
+                let (glyph, fg, bg) = tile_glyph(idx, &map);
+                draw_batch.set(Point::new(MAP_LEFT + sx, MAP_TOP + sy), ColorPair::new(fg, bg), glyph);
+            }
+        }
+    }
+
+    for (idx, marker) in map.markers.iter() {
+        if !map.revealed_tiles[*idx] { continue; }
+        let (x, y) = map.idx_xy(*idx);
+        draw_batch.set(Point::new(MAP_LEFT + x / scale, MAP_TOP + y / scale), ColorPair::new(marker.fg, marker.bg), marker.glyph);
+    }
+
+    let entities = ecs.entities();
+    let positions = ecs.read_storage::<Position>();
+    let renderables = ecs.read_storage::<Renderable>();
+    let vendors = ecs.read_storage::<Vendor>();
+    let quest_givers = ecs.read_storage::<QuestGiver>();
+    let chests = ecs.read_storage::<Chest>();
+    let hidden = ecs.read_storage::<Hidden>();
+    let player_entity = ecs.fetch::<Entity>();
+
+    for (entity, pos, render, _hidden) in (&entities, &positions, &renderables, !&hidden).join() {
+        let idx = map.xy_idx(pos.x, pos.y);
+        if map.revealed_tiles[idx]
+            && (vendors.get(entity).is_some() || quest_givers.get(entity).is_some() || chests.get(entity).is_some()) {
+            draw_batch.set(Point::new(MAP_LEFT + pos.x / scale, MAP_TOP + pos.y / scale), ColorPair::new(render.fg, render.bg), render.glyph);
+        }
+    }
+
+    // the player is always drawn on top
+    if let (Some(pos), Some(render)) = (positions.get(*player_entity), renderables.get(*player_entity)) {
+        draw_batch.set(
+            Point::new(MAP_LEFT + pos.x / scale, MAP_TOP + pos.y / scale),
+            ColorPair::new(render.fg, render.bg),
+            render.glyph
+        );
+    }
+}
+
+fn draw_dungeon(ecs: &World, draw_batch: &mut DrawBatch) {
+    let current_map = ecs.fetch::<Map>();
+    let dungeon_master = ecs.fetch::<MasterDungeonMap>();
+
+    let start_map = match dungeon_master.get_start_map() {
+        Some(name) => name,
+        None => return
+    };
+
+    // lay maps out in rows by how many transitions they are from the start
+    let mut depths: HashMap<String, i32> = HashMap::new();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut open_list: VecDeque<String> = VecDeque::new();
+    depths.insert(start_map.clone(), 0);
+    open_list.push_back(start_map);
+    while let Some(map_name) = open_list.pop_front() {
+        let depth = depths[&map_name];
+        if rows.len() <= depth as usize { rows.push(Vec::new()); }
+        rows[depth as usize].push(map_name.clone());
+        for linked in dungeon_master.get_transitions(&map_name) {
+            if !depths.contains_key(&linked) {
+                depths.insert(linked.clone(), depth + 1);
+                open_list.push_back(linked);
+            }
+        }
+    }
+
+    let row_height = i32::max(6, i32::min(10, (SCREEN_HEIGHT - MAP_TOP - 4) / i32::max(1, rows.len() as i32)));
+    let mut node_centres: HashMap<String, Point> = HashMap::new();
+    for (depth, row) in rows.iter().enumerate() {
+        let spacing = SCREEN_WIDTH / (row.len() as i32 + 1);
+        for (i, map_name) in row.iter().enumerate() {
+            node_centres.insert(map_name.clone(), Point::new(spacing * (i as i32 + 1), MAP_TOP + 1 + depth as i32 * row_height));
+        }
+    }
+
+    // links between maps
+    for (map_name, centre) in node_centres.iter() {
+        for linked in dungeon_master.get_transitions(map_name) {
+            if depths.get(&linked).is_none_or(|d| *d <= depths[map_name]) { continue; }
+            let target = node_centres[&linked];
+            let explored = dungeon_master.has_visited(map_name) && dungeon_master.has_visited(&linked);
+            let colour = if explored { white() } else { box_gray() };
+            let start = Point::new(centre.x, centre.y + 3);
+            let end = Point::new(target.x, target.y - 2);
+            for point in line2d(LineAlg::Bresenham, start, end) {
+                draw_batch.set(point, ColorPair::new(colour, black()), to_cp437('·'));
+            }
+        }
+    }
+
+    // the maps themselves
+    for (map_name, centre) in node_centres.iter() {
+        let visited = dungeon_master.has_visited(map_name);
+        let is_current = *map_name == current_map.name;
+        let label = if visited { map_name.clone() } else { "???".to_string() };
+        let detail = format!("level {}", dungeon_master.get_area_level(map_name).unwrap_or(0));
+        let width = usize::max(label.len(), detail.len()) as i32 + 3;
+        let colour = if is_current { yellow() } else if visited { white() } else { box_gray() };
+
+        draw_batch.draw_box(
+            Rect::with_size(centre.x - width / 2, centre.y - 1, width, 3),
+            ColorPair::new(colour, black())
+        );
+        draw_batch.print_color_centered_at(Point::new(centre.x, centre.y), &label, ColorPair::new(colour, black()));
+        draw_batch.print_color_centered_at(Point::new(centre.x, centre.y + 1), &detail, ColorPair::new(light_gray(), black()));
+        if is_current {
+            draw_batch.print_color(
+                Point::new(centre.x + width / 2 + 2, centre.y),
+                "<- You are here",
+                ColorPair::new(yellow(), black())
+            );
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use rltk::{DijkstraMap, Point, Rltk};
+use specs::prelude::*;
+use crate::{gamelog, spatial, tile_walkable, try_move_player, search_nearby, skip_turn, Door, Faction, FactionAttitudes,
+    Hidden, HungerClock, HungerState, InBackpack, Item, Map, Name, Pools, Position, RunState, State, StatusEffect, Trap, Viewshed,
+    WantsToPickupItem};
+use crate::raws::{attitude_reaction, Reaction, RAWS};
+
+const MAX_AUTO_MOVE_DEPTH: f32 = 2000.0;
+pub const SEARCH_TURNS: i32 = 10;
+// mana only comes back sometimes, so resting for it alone is capped
+const MAX_REST_TURNS: i32 = 500;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AutoMoveMode { Explore, Travel { destination: usize }, Rest, Search { turns: i32 } }
+
+pub struct AutoMove {
+    pub mode: Option<AutoMoveMode>,
+    pub auto_pickup: bool,
+    map_name: String,
+    hit_points: i32,
+    hunger: HungerState,
+    status_effects: Vec<Entity>,
+    turns: i32,
+    rested_hit_points: i32,
+    tried_pickups: Vec<Entity>
+}
+
+impl Default for AutoMove {
+    fn default() -> AutoMove {
+        AutoMove {
+            mode: None,
+            auto_pickup: true,
+            map_name: String::new(),
+            hit_points: 0,
+            hunger: HungerState::Normal,
+            status_effects: Vec::new(),
+            turns: 0,
+            rested_hit_points: 0,
+            tried_pickups: Vec::new()
+        }
+    }
+}
+
+pub fn is_auto_moving(ecs: &World) -> bool {
+    ecs.fetch::<AutoMove>().mode.is_some()
+}
+
+pub fn start_auto_move(ecs: &mut World, mode: AutoMoveMode) -> RunState {
+    let player_entity = *ecs.fetch::<Entity>();
+    let hit_points = ecs.read_storage::<Pools>().get(player_entity).map_or(0, |p| p.hit_points.current);
+    let hunger = ecs.read_storage::<HungerClock>().get(player_entity).map_or(HungerState::Normal, |h| h.state);
+    let map_name = ecs.fetch::<Map>().name.clone();
+
+    if mode == AutoMoveMode::Rest {
+        if matches!(hunger, HungerState::Hungry | HungerState::Starving) {
+            gamelog::Logger::new().append("You are too hungry to rest.").log();
+            return RunState::AwaitingInput;
+        }
+        if is_fully_rested(ecs) {
+            gamelog::Logger::new().append("You are already fully rested.").log();
+            return RunState::AwaitingInput;
+        }
+    }
+
+    let status_effects = player_status_effects(ecs);
+    let mut auto_move = ecs.fetch_mut::<AutoMove>();
+    auto_move.mode = Some(mode);
+    auto_move.map_name = map_name;
+    auto_move.hit_points = hit_points;
+    auto_move.hunger = hunger;
+    auto_move.status_effects = status_effects;
+    auto_move.turns = 0;
+    auto_move.tried_pickups.clear();
+    RunState::AwaitingInput
+}
+
+pub fn stop_auto_move(ecs: &World) {
+    let mut auto_move = ecs.fetch_mut::<AutoMove>();
+    match auto_move.mode {
+        Some(AutoMoveMode::Rest) => {
+            gamelog::Logger::new().append(format!("You rest for {} turns.", auto_move.turns)).log();
+        }
+        Some(AutoMoveMode::Search{..}) => {
+            gamelog::Logger::new().append(format!("You search for {} turns.", auto_move.turns)).log();
+        }
+        _ => {}
+    }
+    auto_move.mode = None;
+}
+
+pub fn toggle_auto_pickup(ecs: &mut World) -> RunState {
+    let mut auto_move = ecs.fetch_mut::<AutoMove>();
+    auto_move.auto_pickup = !auto_move.auto_pickup;
+    let state = if auto_move.auto_pickup { "on" } else { "off" };
+    gamelog::Logger::new().append(format!("Auto pickup is now {}.", state)).log();
+    RunState::AwaitingInput
+}
+
+// takes one step of auto-explore or travel, stopping if anything needs the player's attention
+pub fn auto_move(gs: &mut State, ctx: &mut Rltk) -> RunState {
+    if ctx.key.is_some() {
+        stop_auto_move(&gs.ecs);
+        return RunState::AwaitingInput;
+    }
+    if let Some(reason) = interruption(&gs.ecs) {
+        gamelog::Logger::new().append(reason).log();
+        stop_auto_move(&gs.ecs);
+        return RunState::AwaitingInput;
+    }
+
+    let (mode, auto_pickup) = {
+        let auto_move = gs.ecs.fetch::<AutoMove>();
+        (auto_move.mode.unwrap(), auto_move.auto_pickup && !backpack_full(&gs.ecs))
+    };
+
+    // waiting in place
+    match mode {
+        AutoMoveMode::Rest => {
+            if is_fully_rested(&gs.ecs) || gs.ecs.fetch::<AutoMove>().turns >= MAX_REST_TURNS {
+                stop_auto_move(&gs.ecs);
+                return RunState::AwaitingInput;
+            }
+            // health comes back every turn unless a regeneration penalty cancels it out
+            let (hit_points, max_hit_points) = player_hit_points(&gs.ecs);
+            let mut auto_move = gs.ecs.fetch_mut::<AutoMove>();
+            if auto_move.turns > 0 && hit_points < max_hit_points && hit_points <= auto_move.rested_hit_points {
+                std::mem::drop(auto_move);
+                gamelog::Logger::new().append("You stop because resting isn't helping.").log();
+                stop_auto_move(&gs.ecs);
+                return RunState::AwaitingInput;
+            }
+            auto_move.rested_hit_points = hit_points;
+            auto_move.turns += 1;
+            std::mem::drop(auto_move);
+            return skip_turn(&mut gs.ecs);
+        }
+        AutoMoveMode::Search{ turns } => {
+            if gs.ecs.fetch::<AutoMove>().turns >= turns {
+                stop_auto_move(&gs.ecs);
+                return RunState::AwaitingInput;
+            }
+            gs.ecs.fetch_mut::<AutoMove>().turns += 1;
+            search_nearby(&gs.ecs);
+            return RunState::Ticking;
+        }
+        _ => {}
+    }
+
+    let map = gs.ecs.fetch::<Map>();
+    let player_pos = *gs.ecs.fetch::<Point>();
+    let player_idx = map.xy_idx(player_pos.x, player_pos.y);
+
+    // pick up anything we're standing on
+    if auto_pickup && mode == AutoMoveMode::Explore {
+        if let Some(item) = item_at(&gs.ecs, player_idx) {
+            // an item that couldn't be picked up is left where it is
+            gs.ecs.fetch_mut::<AutoMove>().tried_pickups.push(item);
+            let player_entity = *gs.ecs.fetch::<Entity>();
+            gs.ecs.write_storage::<WantsToPickupItem>()
+                .insert(player_entity, WantsToPickupItem{ collected_by: player_entity, item })
+                .expect("Unable to insert want to pickup");
+            return RunState::Ticking;
+        }
+    }
+
+    let targets = match mode {
+        AutoMoveMode::Explore => exploration_targets(&gs.ecs, &map, player_idx, auto_pickup),
+        AutoMoveMode::Rest | AutoMoveMode::Search{..} => Vec::new(),
+        AutoMoveMode::Travel{ destination } => {
+            let dest_pos = Point::new(destination as i32 % map.width, destination as i32 / map.width);
+            let distance = rltk::DistanceAlg::Pythagoras.distance2d(player_pos, dest_pos);
+            if destination == player_idx || (distance < 1.5 && spatial::is_blocked(destination)) {
+                gamelog::Logger::new().append("You arrive at your destination.").log();
+                std::mem::drop(map);
+                stop_auto_move(&gs.ecs);
+                return RunState::AwaitingInput;
+            }
+            vec![destination]
+        }
+    };
+    if targets.is_empty() {
+        gamelog::Logger::new().append("There is nothing left to explore here.").log();
+        std::mem::drop(map);
+        stop_auto_move(&gs.ecs);
+        return RunState::AwaitingInput;
+    }
+
+    // step straight onto adjacent targets, closed doors can't be pathed through
+    let next_step = targets.iter()
+        .find(|idx| {
+            let pos = Point::new(**idx as i32 % map.width, **idx as i32 / map.width);
+            rltk::DistanceAlg::Pythagoras.distance2d(player_pos, pos) < 1.5
+        })
+        .cloned()
+        .or_else(|| {
+            let dijkstra = DijkstraMap::new(map.width, map.height, &targets, &*map, MAX_AUTO_MOVE_DEPTH);
+            DijkstraMap::find_lowest_exit(&dijkstra, player_idx, &*map)
+                .filter(|idx| dijkstra.map[*idx] < f32::MAX)
+        });
+    std::mem::drop(map);
+
+    let next_step = match next_step {
+        Some(idx) => idx,
+        None => {
+            gamelog::Logger::new().append("You can't find a way there.").log();
+            stop_auto_move(&gs.ecs);
+            return RunState::AwaitingInput;
+        }
+    };
+    if let Some(trap_name) = known_trap_at(&gs.ecs, next_step) {
+        gamelog::Logger::new().append(format!("You stop in front of the {}.", trap_name)).log();
+        stop_auto_move(&gs.ecs);
+        return RunState::AwaitingInput;
+    }
+
+    let width = gs.ecs.fetch::<Map>().width;
+    let delta_x = next_step as i32 % width - player_pos.x;
+    let delta_y = next_step as i32 / width - player_pos.y;
+    let result = try_move_player(delta_x, delta_y, &mut gs.ecs);
+    if result == RunState::AwaitingInput {
+        stop_auto_move(&gs.ecs);
+    }
+    result
+}
+
+fn interruption(ecs: &World) -> Option<String> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let map = ecs.fetch::<Map>();
+    let mut auto_move = ecs.fetch_mut::<AutoMove>();
+
+    if auto_move.map_name != map.name { return Some("You stop.".to_string()); }
+
+    if let Some(pools) = ecs.read_storage::<Pools>().get(player_entity) {
+        if pools.hit_points.current < auto_move.hit_points {
+            return Some("You stop because you are hurt!".to_string());
+        }
+        auto_move.hit_points = pools.hit_points.current;
+    }
+    if let Some(clock) = ecs.read_storage::<HungerClock>().get(player_entity) {
+        if clock.state != auto_move.hunger {
+            // resting stops on any change, moving only once hunger sets in
+            let waiting = matches!(auto_move.mode, Some(AutoMoveMode::Rest) | Some(AutoMoveMode::Search{..}));
+            match clock.state {
+                HungerState::Hungry => return Some("You stop because you are hungry.".to_string()),
+                HungerState::Starving => return Some("You stop because you are starving!".to_string()),
+                _ if waiting => return Some("You stop because you are no longer well fed.".to_string()),
+                _ => auto_move.hunger = clock.state
+            }
+        }
+    }
+    std::mem::drop(auto_move);
+    if player_status_effects(ecs) != ecs.fetch::<AutoMove>().status_effects {
+        return Some("You stop because your condition has changed.".to_string());
+    }
+
+    let viewsheds = ecs.read_storage::<Viewshed>();
+    let pools = ecs.read_storage::<Pools>();
+    let factions = ecs.read_storage::<Faction>();
+    let attitudes = ecs.read_storage::<FactionAttitudes>();
+    let names = ecs.read_storage::<Name>();
+    let viewshed = viewsheds.get(player_entity)?;
+    for tile in viewshed.visible_tiles.iter() {
+        let idx = map.xy_idx(tile.x, tile.y);
+        let mut hostile: Option<String> = None;
+        spatial::for_each_tile_content(idx, |entity| {
+            if entity == player_entity || pools.get(entity).is_none() { return; }
+            if let Some(faction) = factions.get(entity) {
+                let reaction = attitude_reaction(&faction.name, "Player", attitudes.get(player_entity), &RAWS.lock().unwrap());
+                if reaction == Reaction::Attack {
+                    hostile = Some(names.get(entity).map_or("enemy".to_string(), |n| n.name.clone()));
+                }
+            }
+        });
+        if let Some(name) = hostile {
+            return Some(format!("You stop because you see a {}.", name));
+        }
+    }
+
+    None
+}
+
+fn exploration_targets(ecs: &World, map: &Map, player_idx: usize, auto_pickup: bool) -> Vec<usize> {
+    let mut targets: Vec<usize> = Vec::new();
+
+    // walkable tiles on the edge of the unexplored
+    for y in 1..map.height - 1 {
+        for x in 1..map.width - 1 {
+            let idx = map.xy_idx(x, y);
+            if idx == player_idx || !map.revealed_tiles[idx] || !tile_walkable(&map.tiles[idx]) || spatial::is_blocked(idx) { continue; }
+            let frontier = (-1..=1).any(|dy| (-1..=1).any(|dx| !map.revealed_tiles[map.xy_idx(x + dx, y + dy)]));
+            if frontier {
+                targets.push(idx);
+            }
+        }
+    }
+
+    // closed doors and items we know about
+    let positions = ecs.read_storage::<Position>();
+    let doors = ecs.read_storage::<Door>();
+    for (door, pos) in (&doors, &positions).join() {
+        let idx = map.xy_idx(pos.x, pos.y);
+        if !door.open && map.revealed_tiles[idx] {
+            targets.push(idx);
+        }
+    }
+    if auto_pickup {
+        let tried_pickups = &ecs.fetch::<AutoMove>().tried_pickups;
+        let items = ecs.read_storage::<Item>();
+        for (item, _item, pos) in (&ecs.entities(), &items, &positions).join() {
+            let idx = map.xy_idx(pos.x, pos.y);
+            if idx != player_idx && map.visible_tiles[idx] && !tried_pickups.contains(&item) {
+                targets.push(idx);
+            }
+        }
+    }
+
+    targets
+}
+
+// items that have already been tried are skipped
+fn item_at(ecs: &World, idx: usize) -> Option<Entity> {
+    let map = ecs.fetch::<Map>();
+    let entities = ecs.entities();
+    let items = ecs.read_storage::<Item>();
+    let positions = ecs.read_storage::<Position>();
+    let tried_pickups = &ecs.fetch::<AutoMove>().tried_pickups;
+    (&entities, &items, &positions).join()
+        .find(|(entity, _, pos)| map.xy_idx(pos.x, pos.y) == idx && !tried_pickups.contains(entity))
+        .map(|(entity, _, _)| entity)
+}
+
+// inventory letters only go up to z
+fn backpack_full(ecs: &World) -> bool {
+    let player_entity = *ecs.fetch::<Entity>();
+    ecs.read_storage::<InBackpack>().join().filter(|backpack| backpack.owner == player_entity).count() >= 26
+}
+
+fn known_trap_at(ecs: &World, idx: usize) -> Option<String> {
+    let traps = ecs.read_storage::<Trap>();
+    let hidden = ecs.read_storage::<Hidden>();
+    let names = ecs.read_storage::<Name>();
+    let mut trap_name: Option<String> = None;
+    spatial::for_each_tile_content(idx, |entity| {
+        if traps.get(entity).is_some() && hidden.get(entity).is_none() {
+            trap_name = Some(names.get(entity).map_or("trap".to_string(), |n| n.name.clone()));
+        }
+    });
+    trap_name
+}
+
+fn is_fully_rested(ecs: &World) -> bool {
+    let player_entity = *ecs.fetch::<Entity>();
+    match ecs.read_storage::<Pools>().get(player_entity) {
+        Some(pools) => pools.hit_points.current >= pools.hit_points.max && pools.mana.current >= pools.mana.max,
+        None => true
+    }
+}
+
+fn player_hit_points(ecs: &World) -> (i32, i32) {
+    let player_entity = *ecs.fetch::<Entity>();
+    ecs.read_storage::<Pools>().get(player_entity).map_or((0, 0), |pools| (pools.hit_points.current, pools.hit_points.max))
+}
+
+fn player_status_effects(ecs: &World) -> Vec<Entity> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let entities = ecs.entities();
+    let status_effects = ecs.read_storage::<StatusEffect>();
+    (&entities, &status_effects).join()
+        .filter(|(_, effect)| effect.target == player_entity)
+        .map(|(entity, _)| entity)
+        .collect()
+}
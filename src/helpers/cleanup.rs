@@ -1,6 +1,6 @@
 use specs::prelude::*;
 use std::collections::HashMap;
-use crate::{Pools, Player, Name, RunState, Position, LootTable, Boss, ItemQuality};
+use crate::{Pools, Player, Name, RunState, Position, LootTable, Boss, ItemQuality, Map, Key, BOSS_LOCK};
 use crate::raws;
 use crate::gamelog;
 use crate::rng;
@@ -37,7 +37,9 @@ pub fn delete_the_dead(ecs : &mut World) {
 
     // loot
     let mut to_spawn: HashMap<String, Position> = HashMap::new();
+    let mut boss_keys: Vec<Position> = Vec::new();
     {
+        let has_boss_lock = ecs.fetch::<Map>().locks.values().any(|lock_id| lock_id == BOSS_LOCK);
         let positions = ecs.write_storage::<Position>();
         let loot_tables = ecs.read_storage::<LootTable>();
         let bosses = ecs.read_storage::<Boss>();
@@ -45,6 +47,12 @@ pub fn delete_the_dead(ecs : &mut World) {
             // bosses drop more loot
             let is_boss = bosses.get(*victim).is_some();
             let position = positions.get(*victim);
+            // bosses carry the key to their level's locked door
+            if is_boss && has_boss_lock {
+                if let Some(pos) = position {
+                    boss_keys.push(pos.clone());
+                }
+            }
             if let Some(table) = loot_tables.get(*victim) {
                 let max_drops = if is_boss { 6 } else { 4 };
                 for _ in 0..max_drops {
@@ -77,6 +85,20 @@ pub fn delete_the_dead(ecs : &mut World) {
                 ItemQuality::Random
             );
         }
+        for pos in boss_keys.iter() {
+            let key = raws::spawn_named_item(
+                &raws::RAWS.lock().unwrap(),
+                ecs,
+                "Skull Key",
+                raws::SpawnType::AtPosition{ x: pos.x, y: pos.y },
+                ItemQuality::Standard
+            );
+            if let Some(key) = key {
+                if let Some(key) = ecs.write_storage::<Key>().get_mut(key) {
+                    key.lock_id = BOSS_LOCK.to_string();
+                }
+            }
+        }
     }
 
     for victim in dead {
@@ -0,0 +1,142 @@
+use specs::prelude::*;
+use crate::{gamelog, spatial, tile_walkable, Companion, CompanionMode, Equipped, Faction, Hireable, InBackpack, LootTable, Map,
+    MoveMode, Name, Pools, Position, Quips, RunState, State, Viewshed};
+
+pub fn make_companion(ecs: &mut World, entity: Entity, summoned_by: Option<String>) {
+    ecs.write_storage::<Companion>().insert(entity, Companion{ mode: CompanionMode::Follow, summoned_by }).expect("Unable to insert");
+    ecs.write_storage::<Faction>().insert(entity, Faction{ name: "Player".to_string() }).expect("Unable to insert");
+    // companions are moved by the companion AI and don't drop loot
+    ecs.write_storage::<MoveMode>().remove(entity);
+    ecs.write_storage::<Hireable>().remove(entity);
+    ecs.write_storage::<Quips>().remove(entity);
+    ecs.write_storage::<LootTable>().remove(entity);
+}
+
+pub fn hire_mercenary(gs: &mut State, mercenary: Entity) {
+    let cost = match gs.ecs.read_storage::<Hireable>().get(mercenary) {
+        Some(hireable) => hireable.cost,
+        None => return
+    };
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    {
+        let mut pools = gs.ecs.write_storage::<Pools>();
+        let player_pools = pools.get_mut(player_entity).unwrap();
+        if player_pools.gold < cost {
+            gamelog::Logger::new().append("You cannot afford that.").log();
+            return;
+        }
+        player_pools.gold -= cost;
+    }
+
+    make_companion(&mut gs.ecs, mercenary, None);
+    let name = gs.ecs.read_storage::<Name>().get(mercenary).map_or("mercenary".to_string(), |name| name.name.clone());
+    gamelog::Logger::new()
+        .append("You hire the")
+        .character_name(name)
+        .append(format!("for {} gold.", cost))
+        .log();
+}
+
+// remove a previous summon so each ability only keeps one companion around
+pub fn dismiss_summon(ecs: &mut World, source: &str) {
+    let mut dismissed: Vec<Entity> = Vec::new();
+    {
+        let entities = ecs.entities();
+        let companions = ecs.read_storage::<Companion>();
+        let positions = ecs.read_storage::<Position>();
+        let map = ecs.fetch::<Map>();
+        for (entity, companion) in (&entities, &companions).join() {
+            if companion.summoned_by.as_deref() == Some(source) {
+                if let Some(pos) = positions.get(entity) {
+                    spatial::remove_entity(entity, map.xy_idx(pos.x, pos.y));
+                }
+                dismissed.push(entity);
+            }
+        }
+
+        // summoned gear vanishes along with its owner
+        let equipped = ecs.read_storage::<Equipped>();
+        let backpacks = ecs.read_storage::<InBackpack>();
+        let items: Vec<Entity> = (&entities).join()
+            .filter(|item| {
+                equipped.get(*item).is_some_and(|equipped| dismissed.contains(&equipped.owner))
+                    || backpacks.get(*item).is_some_and(|backpack| dismissed.contains(&backpack.owner))
+            })
+            .collect();
+        dismissed.extend(items);
+    }
+    for entity in dismissed {
+        ecs.delete_entity(entity).expect("Unable to delete");
+    }
+}
+
+pub fn cycle_companion_mode(ecs: &mut World) -> RunState {
+    let player_pos = *ecs.fetch::<rltk::Point>();
+    let mut companions = ecs.write_storage::<Companion>();
+    let mut new_mode = None;
+    for companion in (&mut companions).join() {
+        if new_mode.is_none() {
+            new_mode = Some(match companion.mode {
+                CompanionMode::Follow => CompanionMode::Guard{ x: player_pos.x, y: player_pos.y },
+                CompanionMode::Guard{..} => CompanionMode::Attack,
+                CompanionMode::Attack => CompanionMode::Follow
+            });
+        }
+        companion.mode = new_mode.unwrap();
+    }
+
+    match new_mode {
+        None => gamelog::Logger::new().append("You have no companions.").log(),
+        Some(mode) => {
+            let description = match mode {
+                CompanionMode::Follow => "follow you",
+                CompanionMode::Guard{..} => "guard this position",
+                CompanionMode::Attack => "attack your target"
+            };
+            gamelog::Logger::new().append(format!("Your companions will now {}.", description)).log();
+        }
+    }
+    RunState::AwaitingInput
+}
+
+// the nearest walkable tile around a point that is_free allows, searching outwards
+pub fn free_tile_near<F>(map: &Map, x: i32, y: i32, is_free: F) -> Option<(i32, i32)> where F: Fn(usize) -> bool {
+    for radius in 1..5 {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let (tx, ty) = (x + dx, y + dy);
+                if tx < 1 || tx > map.width - 2 || ty < 1 || ty > map.height - 2 { continue; }
+                let idx = map.xy_idx(tx, ty);
+                if tile_walkable(&map.tiles[idx]) && is_free(idx) {
+                    return Some((tx, ty));
+                }
+            }
+        }
+    }
+    None
+}
+
+// companions travel between maps with the player
+pub fn move_companions_with_player(ecs: &mut World) {
+    let player_pos = *ecs.fetch::<rltk::Point>();
+    let entities = ecs.entities();
+    let mut companions = ecs.write_storage::<Companion>();
+    let mut positions = ecs.write_storage::<Position>();
+    let mut viewsheds = ecs.write_storage::<Viewshed>();
+    let map = ecs.fetch::<Map>();
+
+    let mut taken = vec![map.xy_idx(player_pos.x, player_pos.y)];
+    for (entity, companion, pos) in (&entities, &mut companions, &mut positions).join() {
+        if let CompanionMode::Guard{..} = companion.mode {
+            companion.mode = CompanionMode::Follow;
+        }
+        if let Some((x, y)) = free_tile_near(&map, player_pos.x, player_pos.y, |idx| !taken.contains(&idx)) {
+            pos.x = x;
+            pos.y = y;
+            taken.push(map.xy_idx(x, y));
+        }
+        if let Some(viewshed) = viewsheds.get_mut(entity) {
+            viewshed.dirty = true;
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use specs::prelude::*;
+use crate::{EquipmentChanged, InBackpack, Item, ItemQuality, Skills, State, identify_item};
+use crate::raws::{self, RecipeData, SpawnType};
+use crate::gui::{backpack_item_counts, has_ingredients};
+use crate::gamelog;
+use crate::rng;
+
+pub fn craft_item(gs: &mut State, recipe_name: &str) {
+    let recipe = match raws::get_recipe(recipe_name, &raws::RAWS.lock().unwrap()) {
+        Some(recipe) => recipe,
+        None => return
+    };
+    let player_entity = *gs.ecs.fetch::<Entity>();
+
+    if !has_ingredients(&recipe, &backpack_item_counts(&gs.ecs)) {
+        gamelog::Logger::new().append("You don't have the ingredients for that.").log();
+        return;
+    }
+
+    consume_ingredients(&mut gs.ecs, &recipe, player_entity);
+
+    let quality = crafted_quality(&gs.ecs, &recipe, player_entity);
+    let item_entity = raws::spawn_named_item(
+        &raws::RAWS.lock().unwrap(),
+        &mut gs.ecs,
+        &recipe.output,
+        SpawnType::Carried{ by: player_entity },
+        quality
+    );
+
+    if let Some(item_entity) = item_entity {
+        identify_item(&gs.ecs, item_entity);
+        gamelog::Logger::new()
+            .append("You craft")
+            .item_name(gs.ecs.read_storage::<Item>().get(item_entity).unwrap())
+            .log();
+    }
+    gs.ecs.write_storage::<EquipmentChanged>().insert(player_entity, EquipmentChanged{}).expect("Unable to insert");
+}
+
+fn consume_ingredients(ecs: &mut World, recipe: &RecipeData, player_entity: Entity) {
+    let mut to_delete: Vec<Entity> = Vec::new();
+    {
+        let entities = ecs.entities();
+        let items = ecs.read_storage::<Item>();
+        let backpacks = ecs.read_storage::<InBackpack>();
+
+        for ingredient in recipe.ingredients.iter() {
+            let mut remaining = ingredient.count;
+            for (entity, item, backpack) in (&entities, &items, &backpacks).join() {
+                if remaining < 1 { break; }
+                if backpack.owner == player_entity && item.name == ingredient.name && !to_delete.contains(&entity) {
+                    to_delete.push(entity);
+                    remaining -= 1;
+                }
+            }
+        }
+    }
+    for entity in to_delete {
+        ecs.delete_entity(entity).expect("Unable to delete");
+    }
+}
+
+fn crafted_quality(ecs: &World, recipe: &RecipeData, player_entity: Entity) -> ItemQuality {
+    // consumables are always brewed to the standard recipe
+    if let Some(item) = raws::get_item_template(&recipe.output, &raws::RAWS.lock().unwrap()) {
+        if item.consumable.is_some() { return ItemQuality::Standard; }
+    }
+
+    let mut skill_bonus = 0;
+    if let Some(skills) = ecs.read_storage::<Skills>().get(player_entity) {
+        skill_bonus = match recipe.skill.as_deref() {
+            Some("melee") => skills.melee.bonus(),
+            Some("defence") => skills.defence.bonus(),
+            Some("ranged") => skills.ranged.bonus(),
+            Some("magic") => skills.magic.bonus(),
+            _ => 0
+        };
+    }
+
+    let roll = rng::roll_dice(1, 20) + skill_bonus;
+    match roll {
+        i32::MIN..=5 => ItemQuality::Damaged,
+        6..=9 => ItemQuality::Worn,
+        10..=15 => ItemQuality::Standard,
+        16..=19 => ItemQuality::Improved,
+        _ => ItemQuality::Exceptional
+    }
+}
@@ -1,4 +1,5 @@
-use crate::Attribute;
+use specs::prelude::*;
+use crate::{Attribute, CharacterClass, Pools, RunState, WantsToLevelUp};
 use crate::rng;
 use regex::Regex;
 
@@ -18,6 +19,23 @@ pub fn player_xp_for_level(level: i32) -> i32 {
     level * (1000+level*200)
 }
 
+// adds the xp to the player and starts a level up once they have enough
+pub fn grant_xp(
+    player_entity: Entity,
+    player_pools: &mut Pools,
+    xp: i32,
+    character_classes: &ReadStorage<CharacterClass>,
+    level_ups: &mut WriteStorage<WantsToLevelUp>,
+    runstate: &mut RunState
+) {
+    player_pools.xp += xp;
+    if player_pools.xp >= player_xp_for_level(player_pools.level) {
+        let player_class = character_classes.get(player_entity).unwrap();
+        level_ups.insert(player_entity, WantsToLevelUp::new(player_class.passives.clone())).expect("Unable to insert");
+        *runstate = RunState::LevelUp;
+    }
+}
+
 pub fn mana_per_level(intelligence: i32) -> i32 {
     4 + attr_bonus(intelligence)
 }
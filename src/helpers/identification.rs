@@ -0,0 +1,32 @@
+use specs::prelude::*;
+use crate::{Consumable, Cursed, Item, MasterDungeonMap};
+
+pub fn identify_item_type(dungeon_master: &mut MasterDungeonMap, items: &mut WriteStorage<Item>, item_name: &str) {
+    dungeon_master.identify_item(item_name);
+    for item in (items).join() {
+        if item.name == item_name {
+            item.unidentified_name = None;
+        }
+    }
+}
+
+pub fn identify_item(ecs: &World, item_entity: Entity) -> bool {
+    let mut items = ecs.write_storage::<Item>();
+    let item = match items.get_mut(item_entity) {
+        Some(item) if item.unidentified_name.is_some() => item,
+        _ => return false
+    };
+
+    // gear is identified piece by piece since each one rolls its own affixes
+    if ecs.read_storage::<Consumable>().get(item_entity).is_none() {
+        item.unidentified_name = None;
+        // the cursed affix is in the name now so the curse can't stay hidden
+        if let Some(curse) = ecs.write_storage::<Cursed>().get_mut(item_entity) {
+            curse.known = true;
+        }
+        return true;
+    }
+    let item_name = item.name.clone();
+    identify_item_type(&mut ecs.fetch_mut::<MasterDungeonMap>(), &mut items, &item_name);
+    true
+}
@@ -1,13 +1,21 @@
+pub mod auto_move;
 pub mod cleanup;
+pub mod companion;
+pub mod crafting;
 pub mod gamesystem;
+pub mod identification;
 pub mod player;
 pub mod random_table;
 pub mod rect;
 pub mod spawner;
 pub mod vendor;
 
+pub use auto_move::*;
 pub use cleanup::*;
+pub use companion::*;
+pub use crafting::*;
 pub use gamesystem::*;
+pub use identification::*;
 pub use player::*;
 pub use random_table::*;
 pub use rect::*;
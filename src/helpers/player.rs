@@ -3,16 +3,18 @@ use specs::prelude::*;
 use std::cmp::{max, min};
 
 use crate::{gamelog, spatial, RegenBonus};
-use crate::raws::{faction_reaction, Reaction, RAWS};
-use crate::effects::{add_effect, EffectType, Targets};
+use crate::raws::{attitude_reaction, Reaction, RAWS};
+use crate::effects::{add_effect, EffectType, Targets, SECRET_DOOR_DIFFICULTY};
 use crate::rng;
 
 use crate::{Position, Player, Viewshed, State, Map, RunState, Item, InBackpack, WantsToUseItem,
     TileType, particle_system::ParticleBuilder, Pools, WantsToMelee, WantsToPickupItem,
     HungerState, HungerClock, Door, BlocksVisibility, BlocksTile, Renderable, EntityMoved,
-    Consumable, Ranged, Faction, Vendor, gui::VendorMode, KnownAbilities, WantsToUseAbility,
+    Consumable, Ranged, Faction, Vendor, gui::{VendorMode, WorldMapTab}, KnownAbilities, WantsToUseAbility,
     Equipped, Weapon, Target, WantsToShoot, Name, Chest, KnownAbility, AbilityType, QuestGiver,
-    Attributes};
+    Attributes, Gatherable, CraftingStation, FactionAttitudes, Hireable, cycle_companion_mode, Trap, Hidden,
+    WantsToDisarm, start_auto_move, toggle_auto_pickup, AutoMoveMode,
+    SEARCH_TURNS, GameClock, Swimmer, Boat, Digger, Breakable, Locked, Key, Lockpick, WantsToUnlock};
 
 pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState {
     let mut result = RunState::AwaitingInput;
@@ -29,30 +31,73 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState
     let mut blocks_movement = ecs.write_storage::<BlocksTile>();
     let mut renderables = ecs.write_storage::<Renderable>();
     let factions = ecs.read_storage::<Faction>();
+    let attitudes = ecs.read_storage::<FactionAttitudes>();
     let mut entity_moved = ecs.write_storage::<EntityMoved>();
     let mut swap_entities: Vec<(Entity, i32, i32)> = Vec::new();
     let vendors = ecs.read_storage::<Vendor>();
     let chests = ecs.read_storage::<Chest>();
     let quest_givers = ecs.read_storage::<QuestGiver>();
+    let gatherables = ecs.read_storage::<Gatherable>();
+    let crafting_stations = ecs.read_storage::<CraftingStation>();
+    let hireables = ecs.read_storage::<Hireable>();
+    let names = ecs.read_storage::<Name>();
+    let clock = ecs.fetch::<GameClock>();
+    let swimmers = ecs.read_storage::<Swimmer>();
+    let boats = ecs.read_storage::<Boat>();
+    let mut boat_move: Option<(Entity, usize, usize)> = None;
+    let breakables = ecs.read_storage::<Breakable>();
+    let player_entity = *ecs.fetch::<Entity>();
+    let digging_power = (&ecs.read_storage::<Equipped>(), &ecs.read_storage::<Digger>()).join()
+        .filter(|(equipped, _digger)| equipped.owner == player_entity)
+        .map(|(_equipped, digger)| digger.power)
+        .max();
+    let smash_damage = i32::max(1, rng::roll_dice(1, 6)
+        + ecs.read_storage::<Attributes>().get(player_entity).map_or(0, |attr| attr.strength.bonus));
+    let locks = ecs.read_storage::<Locked>();
+    let mut wants_unlock = ecs.write_storage::<WantsToUnlock>();
+    let carried_keys: Vec<String> = (&ecs.read_storage::<Key>(), &ecs.read_storage::<InBackpack>()).join()
+        .filter(|(_key, carried)| carried.owner == player_entity)
+        .map(|(key, _carried)| key.lock_id.clone())
+        .collect();
+    let has_lockpick = (&ecs.read_storage::<Lockpick>(), &ecs.read_storage::<InBackpack>()).join()
+        .any(|(_lockpick, carried)| carried.owner == player_entity);
 
     for (entity, _player, pos, viewshed) in (&entities, &players, &mut positions, &mut viewsheds).join() {
         if pos.x + delta_x < 1 || pos.x + delta_x > map.width-1 || pos.y + delta_y < 1 || pos.y + delta_y > map.height-1 { return RunState::AwaitingInput; }
         let destination_idx = map.xy_idx(pos.x + delta_x, pos.y + delta_y);
 
         result = spatial::for_each_tile_content_with_gamemode(destination_idx, |potential_target| {
-            if vendors.get(potential_target).is_some() {
-                return Some(RunState::ShowVendor{ vendor: potential_target, mode: VendorMode::Sell });
+            if let Some(vendor) = vendors.get(potential_target) {
+                if let Some((open, close)) = vendor.hours {
+                    if !clock.is_between(open, close) {
+                        let name = names.get(potential_target).map_or("shop".to_string(), |n| n.name.clone());
+                        gamelog::Logger::new()
+                            .append(format!("The {} is closed. Come back between {}:00 and {}:00.", name, open, close))
+                            .log();
+                        return Some(RunState::AwaitingInput);
+                    }
+                }
+                // the temple offers to lift curses first
+                let mode = if vendor.category == "temple" { VendorMode::Uncurse } else { VendorMode::Sell };
+                return Some(RunState::ShowVendor{ vendor: potential_target, mode });
             }
             if quest_givers.get(potential_target).is_some() {
                 return Some(RunState::ShowQuestMenu { quest_giver: potential_target, index: 0 });
             }
+            if crafting_stations.get(potential_target).is_some() {
+                return Some(RunState::ShowCrafting { station: potential_target });
+            }
+            if hireables.get(potential_target).is_some() {
+                return Some(RunState::ShowHireMenu { mercenary: potential_target });
+            }
 
             let mut hostile = true;
             if pools.get(potential_target).is_some() {
                 if let Some(faction) = factions.get(potential_target) {
-                    let reaction = faction_reaction(
+                    let reaction = attitude_reaction(
                         &faction.name,
                         "Player",
+                        attitudes.get(entity),
                         &RAWS.lock().unwrap()
                     );
                     if reaction != Reaction::Attack { hostile = false; }
@@ -77,6 +122,25 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState
                 }
             }
 
+            // locked doors and chests need a key or a lockpick, doors can also be broken down
+            if let Some(lock) = locks.get(potential_target) {
+                if carried_keys.contains(&lock.lock_id) || has_lockpick {
+                    wants_unlock.insert(entity, WantsToUnlock{ target: potential_target }).expect("Unable to insert");
+                    return Some(RunState::Ticking);
+                }
+                let name = names.get(potential_target).map_or("door".to_string(), |n| n.name.clone());
+                gamelog::Logger::new().append(format!("The {} is locked.", name)).log();
+                if breakables.get(potential_target).is_some() {
+                    add_effect(
+                        Some(entity),
+                        EffectType::Smash{ damage: smash_damage },
+                        Targets::Single{ target: potential_target }
+                    );
+                    return Some(RunState::Ticking);
+                }
+                return Some(RunState::AwaitingInput);
+            }
+
             if let Some(door) = doors.get_mut(potential_target) {
                 if !door.open {
                     door.open = true;
@@ -89,7 +153,17 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState
                 }
             }
 
-            if chests.get(potential_target).is_some() {
+            // barricades have to be broken down
+            if breakables.get(potential_target).is_some() && doors.get(potential_target).is_none() {
+                add_effect(
+                    Some(entity),
+                    EffectType::Smash{ damage: smash_damage },
+                    Targets::Single{ target: potential_target }
+                );
+                return Some(RunState::Ticking);
+            }
+
+            if chests.get(potential_target).is_some() || gatherables.get(potential_target).is_some() {
                 add_effect(
                     Some(entity),
                     EffectType::TriggerFire{ trigger: potential_target },
@@ -100,7 +174,12 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState
             None
         });
 
-        if !spatial::is_blocked(destination_idx) {
+        // a boat carries the player across deep water whether they can swim or not
+        let boat_here = boat_at(&boats, map.xy_idx(pos.x, pos.y));
+        let boat_there = boat_at(&boats, destination_idx);
+        let can_swim = swimmers.get(entity).is_some() || boat_here.is_some() || boat_there.is_some();
+
+        if map.can_enter(destination_idx, can_swim) {
             let old_idx = map.xy_idx(pos.x, pos.y);
             pos.x = min(map.width-1 , max(0, pos.x + delta_x));
             pos.y = min(map.height-1, max(0, pos.y + delta_y));
@@ -108,10 +187,33 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState
             entity_moved.insert(entity, EntityMoved{}).expect("Unable to insert marker");
             spatial::move_entity(entity, old_idx, new_idx);
 
+            // paddle the boat along while staying on the water
+            if let Some(boat) = boat_here {
+                if boat_there.is_none() && map.tiles[new_idx] == TileType::DeepWater {
+                    boat_move = Some((boat, old_idx, new_idx));
+                }
+            }
+
             viewshed.dirty = true;
             playerpos.x = pos.x;
             playerpos.y = pos.y;
             result = RunState::Ticking;
+        } else if map.tiles[destination_idx] == TileType::DeepWater && !spatial::is_blocked_by_entity(destination_idx) {
+            gamelog::Logger::new().append("The water is too deep and you cannot swim.").log();
+        } else if map.tiles[destination_idx] == TileType::Wall {
+            // dig into walls with a digging tool
+            if let Some(power) = digging_power {
+                add_effect(Some(entity), EffectType::Dig{ power }, Targets::Tile{ tile_idx: destination_idx as i32 });
+                result = RunState::Ticking;
+            }
+        }
+    }
+
+    if let Some((boat, old_idx, new_idx)) = boat_move {
+        if let Some(boat_pos) = positions.get_mut(boat) {
+            boat_pos.x = new_idx as i32 % map.width;
+            boat_pos.y = new_idx as i32 / map.width;
+            spatial::move_entity(boat, old_idx, new_idx);
         }
     }
 
@@ -130,6 +232,14 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState
     result
 }
 
+pub fn boat_at(boats: &ReadStorage<Boat>, idx: usize) -> Option<Entity> {
+    let mut boat = None;
+    spatial::for_each_tile_content(idx, |entity| {
+        if boats.get(entity).is_some() { boat = Some(entity); }
+    });
+    boat
+}
+
 fn get_item(ecs: &mut World) {
     let player_pos = ecs.fetch::<Point>();
     let player_entity = ecs.fetch::<Entity>();
@@ -198,12 +308,21 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
             VirtualKeyCode::N => return try_move_player(1, 1, &mut gs.ecs), // move south-west
             VirtualKeyCode::V => return cycle_ranged_target(&mut gs.ecs),
             VirtualKeyCode::F => return fire_on_target(&mut gs.ecs),
+            VirtualKeyCode::C => return cycle_companion_mode(&mut gs.ecs),
             VirtualKeyCode::Space => return skip_turn(&mut gs.ecs),
+            VirtualKeyCode::S if ctx.shift => return start_auto_move(&mut gs.ecs, AutoMoveMode::Search{ turns: SEARCH_TURNS }),
+            VirtualKeyCode::S => return search_for_traps(&mut gs.ecs),
+            VirtualKeyCode::E => return disarm_nearby_trap(&mut gs.ecs),
+            VirtualKeyCode::Z => return start_auto_move(&mut gs.ecs, AutoMoveMode::Rest), // rest until healed
             VirtualKeyCode::Period => return try_transition_level(&mut gs.ecs),
             VirtualKeyCode::G => get_item(&mut gs.ecs), // pickup item
             VirtualKeyCode::I => return RunState::ShowInventory, // open inventory
             VirtualKeyCode::D => return RunState::ShowDropItem, // open item dropper
             VirtualKeyCode::R => return RunState::ShowUnequipItem, // open unequip menu
+            VirtualKeyCode::M => return RunState::ShowWorldMap{ tab: WorldMapTab::Level }, // open world map
+            VirtualKeyCode::X => return start_auto_move(&mut gs.ecs, AutoMoveMode::Explore), // auto-explore
+            VirtualKeyCode::T => return RunState::ShowTravelMenu, // travel to a known location
+            VirtualKeyCode::P => return toggle_auto_pickup(&mut gs.ecs),
             VirtualKeyCode::Escape => return RunState::SaveGame, // open main menu and save the game
             VirtualKeyCode::Backslash => return RunState::ShowCheatMenu,
             _ => { return RunState::AwaitingInput }
@@ -257,24 +376,30 @@ fn use_ability_hotkey(gs: &mut State, key: i32) -> RunState {
         let player_pools = pools.get(*player_entity).unwrap();
         let known_ability_entity = active_abilities[key as usize].0;
         let known_ability = active_abilities[key as usize].1;
-        if player_pools.mana.current >= known_ability.mana_cost {
-            if let Some(ranged) = gs.ecs.read_storage::<Ranged>().get(known_ability_entity) {
-                return RunState::ShowTargeting { min_range: ranged.min_range, max_range: ranged.max_range, source: known_ability_entity };
-            }
-            let mut intent = gs.ecs.write_storage::<WantsToUseAbility>();
-            intent.insert(
-                *player_entity,
-                WantsToUseAbility{ ability: known_ability_entity, target: None }
-            ).expect("Unable to insert intent");
-            return RunState::Ticking;
-        } else {
+        if !known_ability.is_ready() {
+            gamelog::Logger::new()
+                .ability_name(known_ability.name.clone())
+                .append(format!("is recharging ({} turns)!", known_ability.recharge_turns))
+                .log();
+            return RunState::AwaitingInput;
+        }
+        if let Some(resource) = known_ability.missing_resource(player_pools) {
             gamelog::Logger::new()
-                .append("You don't have enough mana to cast")
+                .append(format!("You don't have enough {} to cast", resource))
                 .ability_name(known_ability.name.clone())
                 .append("!")
                 .log();
             return RunState::AwaitingInput;
         }
+        if let Some(ranged) = gs.ecs.read_storage::<Ranged>().get(known_ability_entity) {
+            return RunState::ShowTargeting { min_range: ranged.min_range, max_range: ranged.max_range, source: known_ability_entity };
+        }
+        let mut intent = gs.ecs.write_storage::<WantsToUseAbility>();
+        intent.insert(
+            *player_entity,
+            WantsToUseAbility{ ability: known_ability_entity, target: None }
+        ).expect("Unable to insert intent");
+        return RunState::Ticking;
     }
     RunState::Ticking
 }
@@ -294,10 +419,81 @@ pub fn try_transition_level(ecs: &mut World) -> RunState {
     }
 }
 
+pub fn search_for_traps(ecs: &mut World) -> RunState {
+    gamelog::Logger::new().append("You search the area.").log();
+    search_nearby(ecs);
+    RunState::Ticking
+}
+
+// known traps are only disarmed on purpose so they can still be stepped onto
+pub fn disarm_nearby_trap(ecs: &mut World) -> RunState {
+    let player_entity = *ecs.fetch::<Entity>();
+    let player_pos = *ecs.fetch::<Point>();
+    let positions = ecs.read_storage::<Position>();
+    let traps = ecs.read_storage::<Trap>();
+    let hidden = ecs.read_storage::<Hidden>();
+
+    let trap = (&ecs.entities(), &traps, &positions, !&hidden).join()
+        .find(|(_, _, pos, _)| rltk::DistanceAlg::Pythagoras.distance2d(player_pos, Point::new(pos.x, pos.y)) < 1.5)
+        .map(|(entity, _, _, _)| entity);
+
+    match trap {
+        Some(trap) => {
+            ecs.write_storage::<WantsToDisarm>().insert(player_entity, WantsToDisarm{ trap }).expect("Unable to insert");
+            RunState::Ticking
+        }
+        None => {
+            gamelog::Logger::new().append("There is no trap nearby to disarm.").log();
+            RunState::AwaitingInput
+        }
+    }
+}
+
+// searching is more thorough than passive perception
+pub fn search_nearby(ecs: &World) {
+    let player_entity = ecs.fetch::<Entity>();
+    let player_pos = ecs.fetch::<Point>();
+    let entities = ecs.entities();
+    let positions = ecs.read_storage::<Position>();
+    let traps = ecs.read_storage::<Trap>();
+    let names = ecs.read_storage::<Name>();
+    let attributes = ecs.read_storage::<Attributes>();
+    let mut hidden = ecs.write_storage::<Hidden>();
+
+    let bonus = attributes.get(*player_entity).map_or(0, |attr| attr.intelligence.bonus) + 5;
+
+    let mut found: Vec<Entity> = Vec::new();
+    for (entity, trap, pos, _hidden) in (&entities, &traps, &positions, &hidden).join() {
+        if rltk::DistanceAlg::Pythagoras.distance2d(*player_pos, Point::new(pos.x, pos.y)) > 4.0 { continue; }
+        if rng::roll_dice(1, 20) + bonus >= trap.difficulty {
+            found.push(entity);
+        }
+    }
+    for entity in found {
+        hidden.remove(entity);
+        if let Some(name) = names.get(entity) {
+            gamelog::Logger::new()
+                .colour(rltk::RGB::named(rltk::ORANGE))
+                .append(format!("You find a {}!", name.name))
+                .log();
+        }
+    }
+
+    let map = ecs.fetch::<Map>();
+    for idx in map.secret_doors.iter() {
+        let (x, y) = map.idx_xy(*idx);
+        if rltk::DistanceAlg::Pythagoras.distance2d(*player_pos, Point::new(x, y)) > 4.0 { continue; }
+        if rng::roll_dice(1, 20) + bonus >= SECRET_DOOR_DIFFICULTY {
+            add_effect(None, EffectType::RevealSecretDoor, Targets::Tile{ tile_idx: *idx as i32 });
+        }
+    }
+}
+
 pub fn skip_turn(ecs: &mut World) -> RunState {
     let player_entity = ecs.fetch::<Entity>();
     let viewsheds = ecs.read_storage::<Viewshed>();
     let factions = ecs.read_storage::<Faction>();
+    let attitudes = ecs.read_storage::<FactionAttitudes>();
     let worldmap = ecs.fetch::<Map>();
     let positions = ecs.read_storage::<Position>();
     let mut particle_builder = ecs.fetch_mut::<ParticleBuilder>();
@@ -312,9 +508,10 @@ pub fn skip_turn(ecs: &mut World) -> RunState {
             match faction {
                 None => {},
                 Some(faction) => {
-                    let reaction = faction_reaction(
+                    let reaction = attitude_reaction(
                         &faction.name,
                         "Player",
+                        attitudes.get(*player_entity),
                         &RAWS.lock().unwrap()
                     );
                     if reaction == Reaction::Attack {
@@ -396,6 +593,7 @@ fn get_target_list(ecs: &mut World) -> Vec<(f32, Entity)> {
     let map = ecs.fetch::<Map>();
     let positions = ecs.read_storage::<Position>();
     let factions = ecs.read_storage::<Faction>();
+    let attitudes = ecs.read_storage::<FactionAttitudes>();
     for (equipped, weapon) in (&equipped, &weapons).join() {
         if equipped.owner == *player_entity && weapon.range.is_some() {
             let range = weapon.range.unwrap();
@@ -409,9 +607,10 @@ fn get_target_list(ecs: &mut World) -> Vec<(f32, Entity)> {
                         spatial::for_each_tile_content(tile_idx, |possible_target| {
                             if possible_target != *player_entity {
                                 if let Some(faction) = factions.get(possible_target) {
-                                    let reaction = faction_reaction(
+                                    let reaction = attitude_reaction(
                                         &faction.name,
                                         "Player",
+                                        attitudes.get(*player_entity),
                                         &RAWS.lock().unwrap()
                                     );
                                     if reaction == Reaction::Attack {
@@ -426,6 +625,13 @@ fn get_target_list(ecs: &mut World) -> Vec<(f32, Entity)> {
         }
     }
     possible_targets.sort_by(|a,b| a.0.partial_cmp(&b.0).unwrap());
+    // large entities are only listed once, at their closest tile
+    let mut seen: Vec<Entity> = Vec::new();
+    possible_targets.retain(|(_, entity)| {
+        if seen.contains(entity) { return false; }
+        seen.push(*entity);
+        true
+    });
     possible_targets
 }
 
@@ -3,14 +3,14 @@ use specs::prelude::*;
 use specs::saveload::{SimpleMarker, MarkedBuilder};
 use std::collections::HashMap;
 use crate::raws::*;
-use crate::{MasterDungeonMap, OtherLevelPosition, StatusEffectChanged};
+use crate::{identify_item, MasterDungeonMap, OtherLevelPosition, StatusEffectChanged};
 use crate::{Pools, Player, Renderable, Name, Position, Viewshed,
     Rect, SerializeMe, random_table::RandomTable, HungerClock, HungerState,
     Map, TileType, Attributes, Skills, Pool, LightSource, Faction,
     Initiative, EquipmentChanged, Point, EntryTrigger, TeleportTo,
     SingleActivation, mana_at_level, hp_at_level, StatusEffect,
     Duration, AttributeBonus, KnownAbilities, EntityVec, InitiativePenalty,
-    MapMarker, ItemQuality
+    MapMarker, ItemQuality, Key, Locked, TileSize, tile_walkable
 };
 use crate::rng;
 use crate::map::Marker;
@@ -44,6 +44,8 @@ pub fn player(ecs: &mut World, player_x: i32, player_y: i32) -> Entity {
                 current: mana_at_level(intelligence, 1),
                 max: mana_at_level(intelligence, 1)
             },
+            rage: Pool::empty(),
+            focus: Pool::empty(),
             xp: 0,
             level: 1,
             total_weight: 0.0,
@@ -80,11 +82,17 @@ pub fn player(ecs: &mut World, player_x: i32, player_y: i32) -> Entity {
 
 pub fn spawn_starting_gear(ecs: &mut World, raws: &RawMaster, equipment: &Vec<String>, items: &Vec<String>) {
     let player = *ecs.read_resource::<Entity>();
+    let mut starting_items: Vec<Entity> = Vec::new();
     for item in equipment.iter() {
-        spawn_named_item(raws, ecs, item.as_str(), SpawnType::Equipped{ by: player }, ItemQuality::Worn);
+        starting_items.extend(spawn_named_item(raws, ecs, item.as_str(), SpawnType::Equipped{ by: player }, ItemQuality::Worn));
     }
     for item in items.iter() {
-        spawn_named_entity(raws, ecs, item.as_str(), SpawnType::Carried { by: player });
+        starting_items.extend(spawn_named_entity(raws, ecs, item.as_str(), SpawnType::Carried { by: player }));
+    }
+
+    // the player knows what they start with
+    for item_entity in starting_items {
+        identify_item(ecs, item_entity);
     }
 }
 
@@ -108,6 +116,15 @@ pub fn spawn_region(map: &mut Map, area: &[usize]) {
     let mut spawn_points: HashMap<usize, String> = HashMap::new();
     let mut areas: Vec<usize> = Vec::from(area);
 
+    // deeper areas are more likely to hide a trap
+    if !map.is_town && !areas.is_empty() && rng::roll_dice(1, 100) <= 20 + map.area_level * 5 {
+        let array_index = (rng::roll_dice(1, areas.len() as i32)-1) as usize;
+        if let Some(trap) = get_trap_table(&RAWS.lock().unwrap(), map.area_level).roll() {
+            map.spawn_list.push((areas[array_index], trap));
+            areas.remove(array_index);
+        }
+    }
+
     {
         // use min to avoid spawning more entites than we have room for
         let num_spawns = i32::min(areas.len() as i32 / 3, rng::roll_dice(1, 7) + (map.area_level / 2) - 3);
@@ -162,9 +179,67 @@ pub fn spawn_entity(ecs: &mut World, spawn: &(&usize, &String)) -> Option<Entity
         });
     }
 
+    // match keys and locks placed by the map builders
+    if let Some(lock_id) = map.locks.get(spawn.0) {
+        let entity = spawn_result.unwrap();
+        if let Some(key) = ecs.write_storage::<Key>().get_mut(entity) {
+            key.lock_id = lock_id.clone();
+        } else {
+            ecs.write_storage::<Locked>().insert(entity, Locked{ lock_id: lock_id.clone() }).expect("Unable to insert");
+        }
+    }
+    std::mem::drop(map);
+    let fits = fit_footprint(ecs, spawn_result.unwrap());
+    std::mem::drop(markers);
+
+    // large entities that can't fit anywhere nearby would be stuck in the walls
+    if !fits {
+        rltk::console::log(format!("WARNING - no room to spawn [{}] at {}", spawn.1, spawn.0));
+        ecs.delete_entity(spawn_result.unwrap()).expect("Unable to delete");
+        return None;
+    }
+
     spawn_result
 }
 
+// how far large entities can be moved to find room for their footprint
+const FOOTPRINT_SEARCH_RADIUS: i32 = 3;
+
+// large entities are shuffled out of any walls they were spawned into
+fn fit_footprint(ecs: &World, entity: Entity) -> bool {
+    let sizes = ecs.read_storage::<TileSize>();
+    let size = match sizes.get(entity) {
+        Some(size) => size,
+        None => return true
+    };
+    let mut positions = ecs.write_storage::<Position>();
+    let pos = match positions.get_mut(entity) {
+        Some(pos) => pos,
+        None => return true
+    };
+    let map = ecs.fetch::<Map>();
+
+    // try the closest spots first
+    for radius in 0..=FOOTPRINT_SEARCH_RADIUS {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                if i32::max(dx.abs(), dy.abs()) != radius { continue; }
+                let (x, y) = (pos.x + dx, pos.y + dy);
+                let fits = (y..y + size.y).all(|ty| (x..x + size.x).all(|tx| {
+                    tx > 0 && tx < map.width - 1 && ty > 0 && ty < map.height - 1
+                        && tile_walkable(&map.tiles[map.xy_idx(tx, ty)])
+                }));
+                if fits {
+                    pos.x = x;
+                    pos.y = y;
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
 pub fn spawn_town_portal(ecs: &mut World) -> (i32, i32) {
     let map = ecs.fetch::<Map>();
     let player_pos = ecs.fetch::<Point>();
@@ -1,12 +1,13 @@
 use specs::prelude::*;
-use crate::{EquipmentChanged, InBackpack, Item, ItemQuality, Pools, State};
+use crate::{Cursed, Durability, EquipmentChanged, Equipped, InBackpack, Item, ItemQuality, Pools, State, identify_item,
+    Attributes, CharacterClass, KnownAbilities, KnownAbility, Skills, WantsToLevelUp};
 use crate::raws::{self, SpawnType};
 use crate::gamelog;
 
 pub fn sell_item(gs: &mut State, item_entity: Entity) {
     let items = gs.ecs.read_storage::<Item>();
     let item = items.get(item_entity).unwrap();
-    let price = item.base_value as f32 * 0.8;
+    let price = item.value() as f32 * 0.8;
 
     gs.ecs.write_storage::<Pools>().get_mut(*gs.ecs.fetch::<Entity>()).unwrap().gold += price as i32;
     gamelog::Logger::new().append("You sell").item_name(item).append(format!("for {} gold", price)).log();
@@ -37,6 +38,9 @@ pub fn buy_item(gs: &mut State, item_name: String, item_price: i32) {
             raws::SpawnType::Carried{ by: player_entity },
             ItemQuality::Standard
         );
+        if let Some(item_entity) = item_entity {
+            identify_item(&gs.ecs, item_entity);
+        }
         gamelog::Logger::new()
             .append("You buy")
             .item_name(gs.ecs.read_storage::<Item>().get(item_entity.unwrap()).unwrap())
@@ -66,16 +70,34 @@ pub fn improve_item(gs: &mut State, item_entity: Entity, improve_cost: i32) {
             _ => ItemQuality::Exceptional
         };
         std::mem::drop(items);
+        let durability = gs.ecs.read_storage::<Durability>().get(item_entity).cloned();
+        let curse = gs.ecs.read_storage::<Cursed>().get(item_entity).cloned();
 
         gs.ecs.entities().delete(item_entity).expect("Unable to delete item entity");
 
-        raws::spawn_named_item(
+        let new_item_entity = raws::spawn_named_item_with_affixes(
             &raws::RAWS.lock().unwrap(),
             &mut gs.ecs,
             &item.name,
             SpawnType::Carried { by: player_entity },
-            new_item_quality
+            new_item_quality,
+            item.prefix.clone(),
+            item.suffix.clone()
         );
+        if let Some(new_item_entity) = new_item_entity {
+            identify_item(&gs.ecs, new_item_entity);
+
+            // improving an item doesn't repair it
+            if let Some(durability) = durability {
+                gs.ecs.write_storage::<Durability>().insert(new_item_entity, durability).expect("Unable to insert");
+                raws::refresh_item_condition(&raws::RAWS.lock().unwrap(), &gs.ecs, new_item_entity);
+            }
+            // a lifted curse stays lifted
+            match curse {
+                Some(curse) => { gs.ecs.write_storage::<Cursed>().insert(new_item_entity, curse).expect("Unable to insert"); }
+                None => { gs.ecs.write_storage::<Cursed>().remove(new_item_entity); }
+            }
+        }
 
         gamelog::Logger::new().append("Quality of").item_name(&item).append("improved").log();
         gs.ecs.write_storage::<EquipmentChanged>().insert(player_entity, EquipmentChanged{}).expect("Unable to insert");
@@ -83,3 +105,152 @@ pub fn improve_item(gs: &mut State, item_entity: Entity, improve_cost: i32) {
         gamelog::Logger::new().append("You cannot afford that.").log();
     }
 }
+
+pub fn repair_item(gs: &mut State, item_entity: Entity, repair_cost: i32) {
+    let mut pools = gs.ecs.write_storage::<Pools>();
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    let player_pools = pools.get_mut(player_entity).unwrap();
+
+    if player_pools.gold >= repair_cost {
+        player_pools.gold -= repair_cost;
+        std::mem::drop(pools);
+
+        if let Some(durability) = gs.ecs.write_storage::<Durability>().get_mut(item_entity) {
+            durability.current = durability.max;
+        }
+        raws::refresh_item_condition(&raws::RAWS.lock().unwrap(), &gs.ecs, item_entity);
+
+        gamelog::Logger::new()
+            .append("You repair")
+            .item_name(gs.ecs.read_storage::<Item>().get(item_entity).unwrap())
+            .append(format!("for {} gold", repair_cost))
+            .log();
+        if gs.ecs.read_storage::<Equipped>().get(item_entity).is_some() {
+            gs.ecs.write_storage::<EquipmentChanged>().insert(player_entity, EquipmentChanged{}).expect("Unable to insert");
+        }
+    } else {
+        gamelog::Logger::new().append("You cannot afford that.").log();
+    }
+}
+
+pub fn identify_item_service(gs: &mut State, item_entity: Entity, identify_cost: i32) {
+    let mut pools = gs.ecs.write_storage::<Pools>();
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    let player_pools = pools.get_mut(player_entity).unwrap();
+
+    if player_pools.gold >= identify_cost {
+        player_pools.gold -= identify_cost;
+        std::mem::drop(pools);
+
+        identify_item(&gs.ecs, item_entity);
+        gamelog::Logger::new()
+            .append("You identify")
+            .item_name(gs.ecs.read_storage::<Item>().get(item_entity).unwrap())
+            .append(format!("for {} gold", identify_cost))
+            .log();
+    } else {
+        gamelog::Logger::new().append("You cannot afford that.").log();
+    }
+}
+
+pub fn uncurse_item(gs: &mut State, item_entity: Entity, uncurse_cost: i32) {
+    let mut pools = gs.ecs.write_storage::<Pools>();
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    let player_pools = pools.get_mut(player_entity).unwrap();
+
+    if player_pools.gold >= uncurse_cost {
+        player_pools.gold -= uncurse_cost;
+        std::mem::drop(pools);
+
+        gs.ecs.write_storage::<Cursed>().remove(item_entity);
+        gamelog::Logger::new()
+            .append("The curse on")
+            .item_name(gs.ecs.read_storage::<Item>().get(item_entity).unwrap())
+            .append(format!("is lifted for {} gold", uncurse_cost))
+            .log();
+    } else {
+        gamelog::Logger::new().append("You cannot afford that.").log();
+    }
+}
+
+pub fn respec_passives(gs: &mut State, respec_cost: i32) -> bool {
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    let refunded: i32 = gs.ecs.read_storage::<CharacterClass>().get(player_entity)
+        .map_or(0, |class| class.passives.values().map(|passive| passive.current_level).sum());
+
+    if refunded < 1 {
+        gamelog::Logger::new().append("You have nothing to unlearn.").log();
+        return false;
+    }
+    {
+        let mut pools = gs.ecs.write_storage::<Pools>();
+        let player_pools = pools.get_mut(player_entity).unwrap();
+        if player_pools.gold < respec_cost {
+            gamelog::Logger::new().append("You cannot afford that.").log();
+            return false;
+        }
+        player_pools.gold -= respec_cost;
+    }
+
+    // remove the bonuses granted by every passive level
+    {
+        let mut character_classes = gs.ecs.write_storage::<CharacterClass>();
+        let mut attributes = gs.ecs.write_storage::<Attributes>();
+        let mut skills = gs.ecs.write_storage::<Skills>();
+        let player_class = character_classes.get_mut(player_entity).unwrap();
+        let player_attributes = attributes.get_mut(player_entity).unwrap();
+        let player_skills = skills.get_mut(player_entity).unwrap();
+
+        for passive in player_class.passives.values_mut() {
+            for level in 1..=passive.current_level {
+                if let Some(attribute_bonus) = &passive.levels[&level].attribute_bonus {
+                    player_attributes.strength.base -= attribute_bonus.strength.unwrap_or(0);
+                    player_attributes.dexterity.base -= attribute_bonus.dexterity.unwrap_or(0);
+                    player_attributes.constitution.base -= attribute_bonus.constitution.unwrap_or(0);
+                    player_attributes.intelligence.base -= attribute_bonus.intelligence.unwrap_or(0);
+                }
+                if let Some(skill_bonus) = &passive.levels[&level].skill_bonus {
+                    player_skills.melee.base -= skill_bonus.melee.unwrap_or(0);
+                    player_skills.defence.base -= skill_bonus.defence.unwrap_or(0);
+                    player_skills.ranged.base -= skill_bonus.ranged.unwrap_or(0);
+                    player_skills.magic.base -= skill_bonus.magic.unwrap_or(0);
+                }
+            }
+            passive.current_level = 0;
+        }
+    }
+
+    forget_passive_abilities(gs, player_entity);
+
+    let passives = gs.ecs.read_storage::<CharacterClass>().get(player_entity).unwrap().passives.clone();
+    gs.ecs.write_storage::<WantsToLevelUp>()
+        .insert(player_entity, WantsToLevelUp::respec(passives, refunded))
+        .expect("Unable to insert");
+    gs.ecs.write_storage::<EquipmentChanged>().insert(player_entity, EquipmentChanged{}).expect("Unable to insert");
+    gamelog::Logger::new()
+        .append(format!("You unlearn your training for {} gold", respec_cost))
+        .log();
+    true
+}
+
+// abilities from items, quests or the starting class are kept
+fn forget_passive_abilities(gs: &mut State, player_entity: Entity) {
+    let mut forgotten: Vec<Entity> = Vec::new();
+    {
+        let mut known_ability_lists = gs.ecs.write_storage::<KnownAbilities>();
+        let known_abilities = gs.ecs.read_storage::<KnownAbility>();
+        let player_abilities = &mut known_ability_lists.get_mut(player_entity).unwrap().abilities;
+
+        for ability_entity in player_abilities.iter() {
+            if let Some(known_ability) = known_abilities.get(*ability_entity) {
+                if !known_ability.from_passive { continue; }
+                forgotten.push(*ability_entity);
+            }
+        }
+        // order is kept so the remaining ability hotkeys don't move around
+        player_abilities.retain(|ability_entity| !forgotten.contains(ability_entity));
+    }
+    for ability_entity in forgotten {
+        gs.ecs.delete_entity(ability_entity).expect("Unable to delete");
+    }
+}
@@ -43,7 +43,8 @@ pub enum RunState {
     ShowTargeting { min_range: f32, max_range: f32, source : Entity},
     MainMenu { menu_selection: gui::MainMenuSelection },
     InGameMenu { menu_selection: gui::InGameMenuSelection },
-    CharacterClassSelectMenu { menu_selection: gui::CharacterClassSelection },
+    CharacterClassSelectMenu { menu_selection: usize },
+    SpeciesSelectMenu { menu_selection: usize, character_class: String },
     SaveGame,
     MagicMapReveal { row: i32 },
     GameOver,
@@ -52,7 +53,11 @@ pub enum RunState {
     TownPortal,
     TeleportingToOtherLevel { x: i32, y: i32, map_name: String },
     LevelUp,
-    ShowQuestMenu { quest_giver: Entity, index: i32}
+    ShowQuestMenu { quest_giver: Entity, index: i32},
+    ShowCrafting { station: Entity },
+    ShowHireMenu { mercenary: Entity },
+    ShowWorldMap { tab: gui::WorldMapTab },
+    ShowTravelMenu
 }
 
 pub struct State {
@@ -118,7 +123,9 @@ impl GameState for State {
             RunState::MainMenu{..} => {}
             RunState::InGameMenu{..} => {}
             RunState::CharacterClassSelectMenu{..} => {}
+            RunState::SpeciesSelectMenu{..} => {}
             RunState::GameOver{..} => {}
+            RunState::ShowWorldMap{..} => {}
             _ => {
                 camera::render_camera(&self.ecs, ctx);
                 gui::draw_ui(&self.ecs, ctx);
@@ -131,7 +138,7 @@ impl GameState for State {
                 newrunstate = RunState::AwaitingInput;
             }
             RunState::AwaitingInput => {
-                newrunstate = player_input(self, ctx);
+                newrunstate = if is_auto_moving(&self.ecs) { auto_move(self, ctx) } else { player_input(self, ctx) };
                 if newrunstate != RunState::AwaitingInput {
                     gamelog::record_event("Turn", 1)
                 }
@@ -152,6 +159,7 @@ impl GameState for State {
                             newrunstate = RunState::TeleportingToOtherLevel { x: *x, y: *y, map_name: map_name.clone() }
                         }
                         RunState::LevelUp => newrunstate = RunState::LevelUp,
+                        RunState::TransitionMap{ map_name } => newrunstate = RunState::TransitionMap{ map_name: map_name.clone() },
                         _ => newrunstate = RunState::Ticking
                     }
                 }
@@ -240,7 +248,7 @@ impl GameState for State {
                     gui::MainMenuResult::NoSelection{ selected } => newrunstate = RunState::MainMenu{ menu_selection: selected },
                     gui::MainMenuResult::Selected{ selected } => {
                         match selected {
-                            gui::MainMenuSelection::NewGame => { newrunstate = RunState::CharacterClassSelectMenu { menu_selection: gui::CharacterClassSelection::Warrior } }
+                            gui::MainMenuSelection::NewGame => { newrunstate = RunState::CharacterClassSelectMenu { menu_selection: 0 } }
                             gui::MainMenuSelection::LoadGame => {
                                 saveload_system::load_game(&mut self.ecs);
                                 newrunstate = RunState::AwaitingInput;
@@ -270,17 +278,17 @@ impl GameState for State {
                 match result {
                     gui::CharacterClassResult::NoSelection { selected } => newrunstate = RunState::CharacterClassSelectMenu { menu_selection: selected },
                     gui::CharacterClassResult::Selected { selected } => {
-                        match selected {
-                            gui::CharacterClassSelection::Warrior => {
-                                raws::spawn_named_character_class(&raws::RAWS.lock().unwrap(), &mut self.ecs, "Warrior");
-                            }
-                            gui::CharacterClassSelection::Sorceror => {
-                                raws::spawn_named_character_class(&raws::RAWS.lock().unwrap(), &mut self.ecs, "Sorceror");
-                            }
-                            gui::CharacterClassSelection::Ranger => {
-                                raws::spawn_named_character_class(&raws::RAWS.lock().unwrap(), &mut self.ecs, "Ranger");
-                            }
-                        }
+                        newrunstate = RunState::SpeciesSelectMenu { menu_selection: 0, character_class: selected };
+                    }
+                }
+            }
+            RunState::SpeciesSelectMenu { ref character_class, .. } => {
+                let result = gui::species_select_menu(self, ctx);
+                match result {
+                    gui::SpeciesResult::NoSelection { selected } => newrunstate = RunState::SpeciesSelectMenu { menu_selection: selected, character_class: character_class.clone() },
+                    gui::SpeciesResult::Selected { selected } => {
+                        raws::spawn_named_character_class(&raws::RAWS.lock().unwrap(), &mut self.ecs, character_class);
+                        raws::spawn_named_species(&raws::RAWS.lock().unwrap(), &mut self.ecs, &selected);
                         gamelog::clear_log();
                         self.transition_to_start_map();
                         newrunstate = RunState::PreRun;
@@ -295,6 +303,24 @@ impl GameState for State {
                 self.change_map(&map_name, None);
                 newrunstate = RunState::PreRun;
             }
+            RunState::ShowWorldMap{ tab } => {
+                let result = gui::show_world_map(&self.ecs, ctx, tab);
+                match result {
+                    gui::WorldMapResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::WorldMapResult::NoResponse => {}
+                    gui::WorldMapResult::SwitchTab{ tab } => newrunstate = RunState::ShowWorldMap{ tab }
+                }
+            }
+            RunState::ShowTravelMenu => {
+                let result = gui::show_travel_menu(&self.ecs, ctx);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::ItemMenuResult::NoResponse => {}
+                    gui::ItemMenuResult::Selected => {
+                        newrunstate = start_auto_move(&mut self.ecs, AutoMoveMode::Travel{ destination: result.1.unwrap() });
+                    }
+                }
+            }
             RunState::GameOver => {
                 let result = gui::game_over(ctx);
                 match result {
@@ -365,7 +391,7 @@ impl GameState for State {
                         let character_classes = self.ecs.read_storage::<CharacterClass>();
                         let player_class = character_classes.get(*player).unwrap();
                         let mut level_ups = self.ecs.write_storage::<WantsToLevelUp>();
-                        level_ups.insert(*player, WantsToLevelUp{ passives: player_class.passives.clone() }).expect("Unable to insert");
+                        level_ups.insert(*player, WantsToLevelUp::new(player_class.passives.clone())).expect("Unable to insert");
                         gamelog::Logger::new().append("You level up").log();
                         newrunstate = RunState::LevelUp;
                     }
@@ -428,12 +454,57 @@ impl GameState for State {
                             result.1.unwrap(), result.3.unwrap()
                         );
                     }
+                    gui::VendorResult::Repair => {
+                        vendor::repair_item(self,
+                            result.1.unwrap(), result.3.unwrap()
+                        );
+                    }
+                    gui::VendorResult::Identify => {
+                        vendor::identify_item_service(self,
+                            result.1.unwrap(), result.3.unwrap()
+                        );
+                    }
+                    gui::VendorResult::Uncurse => {
+                        vendor::uncurse_item(self,
+                            result.1.unwrap(), result.3.unwrap()
+                        );
+                    }
+                    gui::VendorResult::Respec => {
+                        if vendor::respec_passives(self, result.3.unwrap()) {
+                            newrunstate = RunState::LevelUp;
+                        }
+                    }
                     gui::VendorResult::BuyMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Buy },
                     gui::VendorResult::SellMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Sell },
-                    gui::VendorResult::ImproveMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Improve }
+                    gui::VendorResult::ImproveMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Improve },
+                    gui::VendorResult::RepairMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Repair },
+                    gui::VendorResult::IdentifyMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Identify },
+                    gui::VendorResult::UncurseMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Uncurse },
+                    gui::VendorResult::RespecMode => newrunstate = RunState::ShowVendor { vendor, mode: gui::VendorMode::Respec }
                 }
                 self.run_systems(); // TODO set runstate to AwaitingInput instead?
             }
+            RunState::ShowCrafting{station} => {
+                let result = gui::show_crafting_menu(self, ctx, station);
+                match result.0 {
+                    gui::CraftingResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::CraftingResult::NoResponse => {}
+                    gui::CraftingResult::Craft => {
+                        crafting::craft_item(self, &result.1.unwrap());
+                    }
+                }
+            }
+            RunState::ShowHireMenu{mercenary} => {
+                let result = gui::show_hire_menu(self, ctx, mercenary);
+                match result {
+                    gui::HireResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::HireResult::NoResponse => {}
+                    gui::HireResult::Hire => {
+                        hire_mercenary(self, mercenary);
+                        newrunstate = RunState::AwaitingInput;
+                    }
+                }
+            }
             RunState::ShowQuestMenu{quest_giver, index} => {
                 let result = gui::show_quest_giver_menu(self, ctx, quest_giver, index);
                 match result {
@@ -539,7 +610,10 @@ fn main() -> rltk::BError {
         PartOfSet, Target, WantsToShoot, Stun, StatusEffectChanged, Boss, Chest, CharacterClass, Ability,
         KnownAbility, MyTurn, KnownAbilities, WantsToUseAbility, SelfDamage, Rage, Block, Fortress, FrostShield,
         Dodge, WantsToLearnAbility, WantsToLevelAbility, Quests, ActiveQuests, QuestProgress, QuestGiver,
-        WantsToTurnInQuest, MapMarker, Species, Repeat, WantsToRepeatAbility, RegenBonus
+        WantsToTurnInQuest, MapMarker, Species, Repeat, WantsToRepeatAbility, RegenBonus, MagicItem, Identify,
+        Cursed, RemoveCurse, Durability, Gatherable, CraftingStation,
+        RacialPassive, FactionAttitudes, PassiveBonus, Hireable, Companion, Summon, CreatesHazard, Hidden, Trap, WantsToDisarm, Pit, Alarm, RandomTeleport, Nocturnal, Swimmer, Swimming, WaterBreathing, Boat, Digger, Tunnels,
+        Breakable, Smasher, Locked, Key, Lockpick, WantsToUnlock, WaterDamageable
     ]);
     gs.ecs.insert(SimpleMarkerAllocator::<SerializeMe>::new());
 
@@ -554,6 +628,7 @@ fn initialise_resources(ecs: &mut World) {
     let player_entity = spawner::player(ecs, 0, 0);
     ecs.insert(player_entity);
     ecs.insert(particle_system::ParticleBuilder::new());
+    ecs.insert(AutoMove::default());
     ecs.insert(RunState::MainMenu { menu_selection: gui::MainMenuSelection::NewGame });
 
     raws::store_world_maps(ecs);
@@ -564,5 +639,6 @@ fn initialise_resources(ecs: &mut World) {
 
     ecs.insert(Quests{ quests: Vec::new() });
     ecs.insert(ActiveQuests{ quests: Vec::new() });
+    ecs.insert(GameClock{ turn: 8 * TURNS_PER_HOUR });
     raws::store_all_quests(ecs);
 }
@@ -1,7 +1,7 @@
 use specs::prelude::*;
 use rltk::prelude::*;
 use crate::tile_glyph;
-use crate::{Map, Position, Renderable, World, TileSize, Target};
+use crate::{Map, Position, Renderable, World, TileSize, Target, Hidden};
 
 const SHOW_BOUNDARIES: bool = true;
 const WINDOW_BOUNDARIES: (i32, i32) = (88, 80);
@@ -57,8 +57,11 @@ pub fn render_camera(ecs: &World, ctx: &mut Rltk) {
     let sizes = ecs.read_storage::<TileSize>();
     let entities = ecs.entities();
     let targets = ecs.read_storage::<Target>();
+    let hidden = ecs.read_storage::<Hidden>();
 
-    let mut data = (&positions, &renderables, &entities).join().collect::<Vec<_>>();
+    let mut data = (&positions, &renderables, &entities, !&hidden).join()
+        .map(|(pos, render, entity, _)| (pos, render, entity))
+        .collect::<Vec<_>>();
     data.sort_by(|&a, &b| b.1.render_order.cmp(&a.1.render_order));
     for (pos, render, entity) in data.iter() {
         if let Some(size) = sizes.get(*entity) {
@@ -109,18 +112,22 @@ pub fn render_camera(ecs: &World, ctx: &mut Rltk) {
         }
 
         if targets.get(*entity).is_some() {
+            // bracket every row of the target's footprint
+            let (width, height) = sizes.get(*entity).map_or((1, 1), |size| (size.x, size.y));
             let entity_screen_x = pos.x - min_x;
-            let entity_screen_y = pos.y - min_y;
-            draw_batch.set(
-                Point::new(entity_screen_x, entity_screen_y + 1),
-                ColorPair::new(RGB::named(rltk::RED), RGB::named(rltk::YELLOW)),
-                rltk::to_cp437('[')
-            );
-            draw_batch.set(
-                Point::new(entity_screen_x + 2, entity_screen_y + 1),
-                ColorPair::new(RGB::named(rltk::RED), RGB::named(rltk::YELLOW)),
-                rltk::to_cp437(']')
-            );
+            for cy in 0..height {
+                let entity_screen_y = pos.y + cy - min_y;
+                draw_batch.set(
+                    Point::new(entity_screen_x, entity_screen_y + 1),
+                    ColorPair::new(RGB::named(rltk::RED), RGB::named(rltk::YELLOW)),
+                    rltk::to_cp437('[')
+                );
+                draw_batch.set(
+                    Point::new(entity_screen_x + width + 1, entity_screen_y + 1),
+                    ColorPair::new(RGB::named(rltk::RED), RGB::named(rltk::YELLOW)),
+                    rltk::to_cp437(']')
+                );
+            }
         }
     }
 
@@ -1,20 +1,25 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
 use specs::prelude::*;
-use super::{World, Map, Point, Entity};
-use crate::{components::{OtherLevelPosition, Position, Viewshed}, spawner};
+use super::{World, Map, Point, Entity, TileType};
+use crate::{components::{Companion, GameClock, OtherLevelPosition, Position, Viewshed}, spawner, rng, move_companions_with_player};
+use crate::raws::{spawns_at_time, RAWS};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MasterDungeonMap {
     maps: HashMap<String, Map>,
-    visited: Vec<String>
+    visited: Vec<String>,
+    identified_items: HashSet<String>,
+    item_appearances: HashMap<String, String>
 }
 
 impl MasterDungeonMap {
     pub fn new() -> MasterDungeonMap {
         MasterDungeonMap {
             maps: HashMap::new(),
-            visited: Vec::new()
+            visited: Vec::new(),
+            identified_items: HashSet::new(),
+            item_appearances: HashMap::new()
         }
     }
 
@@ -38,8 +43,69 @@ impl MasterDungeonMap {
         self.visited.contains(&map_name.to_string())
     }
 
+    pub fn get_start_map(&self) -> Option<String> {
+        self.maps.values().find(|map| map.is_start).map(|map| map.name.clone())
+    }
+
+    pub fn get_area_level(&self, map_name: &str) -> Option<i32> {
+        self.maps.get(map_name).map(|map| map.area_level)
+    }
+
+    pub fn get_transitions(&self, map_name: &str) -> Vec<String> {
+        let mut transitions: Vec<String> = match self.maps.get(map_name) {
+            Some(map) => map.transitions.keys().cloned().collect(),
+            None => Vec::new()
+        };
+        transitions.sort();
+        transitions
+    }
+
     pub fn reset(&mut self) {
         self.visited = Vec::new();
+        self.identified_items = HashSet::new();
+        self.item_appearances = HashMap::new();
+    }
+
+    pub fn is_identified(&self, item_name: &str) -> bool {
+        self.identified_items.contains(item_name)
+    }
+
+    pub fn identify_item(&mut self, item_name: &str) {
+        self.identified_items.insert(item_name.to_string());
+    }
+
+    pub fn get_item_appearance(&mut self, item_name: &str, naming: &str) -> String {
+        if let Some(appearance) = self.item_appearances.get(item_name) {
+            return appearance.clone();
+        }
+
+        let mut appearance = random_item_appearance(naming);
+        let mut attempts = 0;
+        while attempts < 20 && self.item_appearances.values().any(|a| *a == appearance) {
+            appearance = random_item_appearance(naming);
+            attempts += 1;
+        }
+        self.item_appearances.insert(item_name.to_string(), appearance.clone());
+        appearance
+    }
+}
+
+fn random_item_appearance(naming: &str) -> String {
+    match naming {
+        "potion" => {
+            let adjectives = ["Murky", "Bubbling", "Swirling", "Cloudy", "Fizzy", "Glowing", "Viscous",
+                "Smoky", "Sparkling", "Oily", "Milky", "Pungent", "Shimmering", "Frothy"];
+            format!("{} Potion", adjectives[rng::roll_dice(1, adjectives.len() as i32) as usize - 1])
+        }
+        "scroll" => {
+            let syllables = ["XY", "ZZY", "KLA", "ATU", "NOR", "FRE", "BLA", "ORG", "ITH", "MOR", "VEX", "QUA"];
+            let mut label = String::new();
+            for _ in 0..rng::roll_dice(1, 2) + 1 {
+                label += syllables[rng::roll_dice(1, syllables.len() as i32) as usize - 1];
+            }
+            format!("Scroll labelled {}", label)
+        }
+        _ => format!("Strange {}", naming)
     }
 }
 
@@ -99,12 +165,28 @@ pub fn transition_map(ecs: &mut World, map_name: &str, player_position: Option<(
             let mut dungeon_master = ecs.write_resource::<MasterDungeonMap>();
             dungeon_master.visited.push(new_map.name);
         }
+        move_companions_with_player(ecs);
+    }
+}
+
+// changes a tile on the current map, keeping pathing, vision and the stored map up to date
+pub fn change_tile(ecs: &mut World, idx: usize, tile: TileType) {
+    let mut map = ecs.fetch_mut::<Map>();
+    map.tiles[idx] = tile;
+    map.populate_blocked();
+    ecs.fetch_mut::<MasterDungeonMap>().store_map(&map);
+    std::mem::drop(map);
+
+    for viewshed in (&mut ecs.write_storage::<Viewshed>()).join() {
+        viewshed.dirty = true;
     }
 }
 
 pub fn spawn_entities(ecs: &mut World) {
     let spawn_list = ecs.fetch::<Map>().spawn_list.clone();
+    let time_of_day = ecs.fetch::<GameClock>().time_of_day();
     for (location, name) in spawn_list.iter() {
+        if !spawns_at_time(&RAWS.lock().unwrap(), name, time_of_day) { continue; }
         spawner::spawn_entity(ecs, &(location, name));
     }
 
@@ -118,12 +200,13 @@ pub fn freeze_level_entities(ecs: &mut World) {
     let entities = ecs.entities();
     let mut positions = ecs.write_storage::<Position>();
     let mut other_level_positions = ecs.write_storage::<OtherLevelPosition>();
+    let companions = ecs.read_storage::<Companion>();
     let player_entity = ecs.fetch::<Entity>();
     let map = ecs.fetch::<Map>();
 
-    // store level positions of entities as OtherLevelPositions
+    // store level positions of entities as OtherLevelPositions, companions travel with the player
     let mut pos_to_delete: Vec<Entity> = Vec::new();
-    for (entity, pos) in (&entities, &positions).join() {
+    for (entity, pos, _companion) in (&entities, &positions, !&companions).join() {
         if entity != *player_entity {
             other_level_positions.insert(entity, OtherLevelPosition{
                 x: pos.x,
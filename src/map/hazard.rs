@@ -0,0 +1,115 @@
+use serde::{Serialize, Deserialize};
+use super::{Map, TileType};
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum HazardType { Fire, Ice, PoisonGas, Lightning }
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Hazard {
+    pub hazard_type: HazardType,
+    pub turns: i32,
+    // the tile to restore once the hazard is gone, used for frozen water
+    pub original_tile: Option<TileType>
+}
+
+pub fn parse_hazard_type(name: &str) -> Option<HazardType> {
+    match name {
+        "fire" => Some(HazardType::Fire),
+        "ice" => Some(HazardType::Ice),
+        "poison" => Some(HazardType::PoisonGas),
+        "lightning" => Some(HazardType::Lightning),
+        _ => None
+    }
+}
+
+pub fn tile_flammable(tt: &TileType) -> bool {
+    matches!(tt, TileType::Grass)
+}
+
+pub fn tile_water(tt: &TileType) -> bool {
+    matches!(tt, TileType::ShallowWater | TileType::DeepWater)
+}
+
+impl Map {
+    // whether a hazard of the given type can exist on a tile
+    pub fn hazard_allowed(&self, idx: usize, hazard_type: HazardType) -> bool {
+        let tt = &self.tiles[idx];
+        match hazard_type {
+            HazardType::Fire => tile_flammable(tt),
+            HazardType::Ice | HazardType::Lightning => tile_water(tt),
+            HazardType::PoisonGas => super::tile_walkable(tt)
+        }
+    }
+
+    pub fn add_hazard(&mut self, idx: usize, hazard_type: HazardType, turns: i32) {
+        if let Some(existing) = self.hazards.get_mut(&idx) {
+            match (existing.hazard_type, hazard_type) {
+                (existing_type, new_type) if existing_type == new_type => {
+                    existing.turns = i32::max(existing.turns, turns);
+                    return;
+                }
+                // fire melts ice back into water
+                (HazardType::Ice, HazardType::Fire) => {
+                    self.remove_hazard(idx);
+                    return;
+                }
+                // freezing water grounds any lightning in it
+                (HazardType::Lightning, HazardType::Ice) => self.remove_hazard(idx),
+                _ => return
+            }
+        }
+        if !self.hazard_allowed(idx, hazard_type) { return; }
+
+        let mut original_tile = None;
+        if hazard_type == HazardType::Ice {
+            original_tile = Some(self.tiles[idx].clone());
+            self.tiles[idx] = TileType::Ice;
+        }
+        self.hazards.insert(idx, Hazard{ hazard_type, turns, original_tile });
+    }
+
+    pub fn remove_hazard(&mut self, idx: usize) {
+        if let Some(hazard) = self.hazards.remove(&idx) {
+            if let Some(tile) = hazard.original_tile {
+                self.tiles[idx] = tile;
+            }
+        }
+    }
+
+    // lightning travels through all water connected to where it strikes
+    pub fn electrify_water(&mut self, start_idx: usize, turns: i32, limit: usize) {
+        let mut open = vec![start_idx];
+        let mut electrified = 0;
+        while let Some(idx) = open.pop() {
+            if electrified >= limit { break; }
+            if !self.hazard_allowed(idx, HazardType::Lightning) { continue; }
+            if let Some(hazard) = self.hazards.get(&idx) {
+                if hazard.hazard_type == HazardType::Lightning { continue; }
+            }
+            self.add_hazard(idx, HazardType::Lightning, turns);
+            electrified += 1;
+
+            let (x, y) = self.idx_xy(idx);
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx > 0 && nx < self.width - 1 && ny > 0 && ny < self.height - 1 {
+                    open.push(self.xy_idx(nx, ny));
+                }
+            }
+        }
+    }
+
+    pub fn blocks_pathing(&self, idx: usize) -> bool {
+        match self.hazards.get(&idx) {
+            Some(hazard) => matches!(hazard.hazard_type, HazardType::Fire | HazardType::Lightning),
+            None => false
+        }
+    }
+
+    pub fn hazard_cost(&self, idx: usize) -> f32 {
+        match self.hazards.get(&idx) {
+            Some(hazard) if hazard.hazard_type == HazardType::PoisonGas => 3.0,
+            _ => 0.0
+        }
+    }
+}
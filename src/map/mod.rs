@@ -4,13 +4,19 @@ use serde::{Serialize, Deserialize};
 use std::collections::{HashSet, HashMap};
 mod tile_type;
 mod themes;
+mod hazard;
+mod weather;
 mod dungeon;
+mod pathing;
 pub mod camera;
 use super::spatial;
 pub use tile_type::{TileType, tile_walkable, tile_opaque, tile_cost};
-pub use dungeon::{MasterDungeonMap, transition_map, spawn_entities, freeze_level_entities, thaw_level_entities};
+pub use dungeon::{MasterDungeonMap, transition_map, change_tile, spawn_entities, freeze_level_entities, thaw_level_entities};
 pub use themes::*;
-use crate::{raws::MapData, Position};
+pub use hazard::{Hazard, HazardType, parse_hazard_type, tile_flammable, tile_water};
+pub use weather::{Weather, roll_weather};
+pub use pathing::PathingMap;
+use crate::{raws::MapData, Position, TileSize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Marker {
@@ -33,12 +39,17 @@ pub struct Map {
     pub depth: Option<i32>,
     pub area_level: i32,
     pub bloodstains: HashSet<usize>,
+    pub hazards: HashMap<usize, Hazard>,
     pub view_blocked: HashSet<usize>,
     pub indoors: bool,
     pub light: Vec<RGB>,
     pub markers: HashMap<usize, Marker>,
     pub is_start: bool,
-    pub is_town: bool
+    pub is_town: bool,
+    pub weather: Weather,
+    pub wall_damage: HashMap<usize, i32>,
+    pub locks: HashMap<usize, String>,
+    pub secret_doors: HashSet<usize>
 }
 
 impl Map {
@@ -59,12 +70,17 @@ impl Map {
             depth: None,
             area_level: map_data.area_level,
             bloodstains: HashSet::new(),
+            hazards: HashMap::new(),
             view_blocked: HashSet::new(),
             indoors: map_data.indoors,
             light: vec![RGB::named(rltk::BLACK); map_tile_count],
             markers: HashMap::new(),
             is_start: map_data.start,
-            is_town: map_data.town
+            is_town: map_data.town,
+            weather: roll_weather(map_data),
+            wall_damage: HashMap::new(),
+            locks: HashMap::new(),
+            secret_doors: HashSet::new()
         }
     }
 
@@ -79,36 +95,67 @@ impl Map {
     fn is_exit_valid(&self, x:i32, y:i32) -> bool {
         if x < 1 || x > self.width - 1 || y < 1 || y > self.height - 1 { return false; }
         let idx = self.xy_idx(x, y);
+        self.can_enter(idx, false) && !self.blocks_pathing(idx)
+    }
+
+    // deep water can only be entered by those that can swim
+    pub fn can_enter(&self, idx: usize, swims: bool) -> bool {
+        if swims && self.tiles[idx] == TileType::DeepWater {
+            return !spatial::is_blocked_by_entity(idx);
+        }
         !spatial::is_blocked(idx)
     }
 
-    pub fn populate_blocked(&mut self) {
-        spatial::populate_blocked_from_map(self);
+    pub fn find_path(&self, start: usize, end: usize, swims: bool) -> rltk::NavigationPath {
+        PathingMap::new(self, swims).find_path(start, end)
     }
 
-    pub fn populate_blocked_multi(&mut self, width: i32, height: i32) {
-        self.populate_blocked();
-        for y in 1 .. self.height-1 {
-            for x in 1 .. self.width - 1 {
-                let idx = self.xy_idx(x, y);
-                if !spatial::is_blocked(idx) {
-                    for cy in 0..height {
-                        for cx in 0..width {
-                            let tx = x + cx;
-                            let ty = y + cy;
-                            if tx < self.width-1 && ty < self.height-1 {
-                                let tidx = self.xy_idx(tx, ty);
-                                if spatial::is_blocked(tidx) {
-                                    spatial::set_blocked(idx, true);
-                                }
-                            } else {
-                                spatial::set_blocked(idx, true);
-                            }
-                        }
-                    }
+    pub fn find_path_for(&self, entity: Entity, start: usize, end: usize, size: Option<&TileSize>, swims: bool) -> rltk::NavigationPath {
+        match size {
+            Some(size) => PathingMap::new(self, swims).sized(entity, size, end).find_path(start, end),
+            None => self.find_path(start, end, swims)
+        }
+    }
+
+    // every tile covered by an entity with its top left corner at idx
+    pub fn footprint(&self, idx: usize, size: Option<&TileSize>) -> Vec<usize> {
+        let (x, y) = self.idx_xy(idx);
+        let (width, height) = size.map_or((1, 1), |size| (size.x, size.y));
+        let mut tiles = Vec::new();
+        for ty in y .. y + height {
+            for tx in x .. x + width {
+                if tx >= 0 && tx < self.width && ty >= 0 && ty < self.height {
+                    tiles.push(self.xy_idx(tx, ty));
                 }
             }
         }
+        tiles
+    }
+
+    pub fn can_fit(&self, entity: Entity, idx: usize, size: Option<&TileSize>, swims: bool) -> bool {
+        match size {
+            Some(size) => {
+                let (x, y) = self.idx_xy(idx);
+                self.fits(entity, x, y, size.x, size.y, swims)
+            }
+            None => self.can_enter(idx, swims)
+        }
+    }
+
+    fn fits(&self, entity: Entity, x: i32, y: i32, width: i32, height: i32, swims: bool) -> bool {
+        for ty in y .. y + height {
+            for tx in x .. x + width {
+                if tx < 1 || tx > self.width - 2 || ty < 1 || ty > self.height - 2 { return false; }
+                let idx = self.xy_idx(tx, ty);
+                let walkable = tile_walkable(&self.tiles[idx]) || (swims && self.tiles[idx] == TileType::DeepWater);
+                if !walkable || self.blocks_pathing(idx) || spatial::is_blocked_by_other(idx, entity) { return false; }
+            }
+        }
+        true
+    }
+
+    pub fn populate_blocked(&mut self) {
+        spatial::populate_blocked_from_map(self);
     }
 
     pub fn clear_content_index(&mut self) {
@@ -140,6 +187,12 @@ impl BaseMap for Map {
     }
 
     fn get_available_exits(&self, idx: usize) -> rltk::SmallVec<[(usize, f32); 10]> {
+        self.exits(idx, |x, y| self.is_exit_valid(x, y))
+    }
+}
+
+impl Map {
+    fn exits<F>(&self, idx: usize, is_exit_valid: F) -> rltk::SmallVec<[(usize, f32); 10]> where F: Fn(i32, i32) -> bool {
         let mut exits = rltk::SmallVec::new();
         let x = idx as i32 % self.width;
         let y = idx as i32 / self.width;
@@ -148,16 +201,16 @@ impl BaseMap for Map {
         const DIAGONAL_COST: f32 = 1.45;
 
         // cardinal directions
-        if self.is_exit_valid(x-1, y) { exits.push((idx-1, tile_cost(tt))) };
-        if self.is_exit_valid(x+1, y) { exits.push((idx+1, tile_cost(tt))) };
-        if self.is_exit_valid(x, y-1) { exits.push((idx-w, tile_cost(tt))) };
-        if self.is_exit_valid(x, y+1) { exits.push((idx+w, tile_cost(tt))) };
+        if is_exit_valid(x-1, y) { exits.push((idx-1, tile_cost(tt) + self.hazard_cost(idx-1))) };
+        if is_exit_valid(x+1, y) { exits.push((idx+1, tile_cost(tt) + self.hazard_cost(idx+1))) };
+        if is_exit_valid(x, y-1) { exits.push((idx-w, tile_cost(tt) + self.hazard_cost(idx-w))) };
+        if is_exit_valid(x, y+1) { exits.push((idx+w, tile_cost(tt) + self.hazard_cost(idx+w))) };
 
         // diagonals
-        if self.is_exit_valid(x-1, y-1) { exits.push((idx-1, tile_cost(tt) * DIAGONAL_COST)) };
-        if self.is_exit_valid(x+1, y-1) { exits.push((idx-w, tile_cost(tt) * DIAGONAL_COST)) };
-        if self.is_exit_valid(x+1, y+1) { exits.push((idx+1, tile_cost(tt) * DIAGONAL_COST)) };
-        if self.is_exit_valid(x-1, y+1) { exits.push((idx+w, tile_cost(tt) * DIAGONAL_COST)) };
+        if is_exit_valid(x-1, y-1) { exits.push((idx-1, tile_cost(tt) * DIAGONAL_COST + self.hazard_cost(idx-1))) };
+        if is_exit_valid(x+1, y-1) { exits.push((idx-w, tile_cost(tt) * DIAGONAL_COST + self.hazard_cost(idx-w))) };
+        if is_exit_valid(x+1, y+1) { exits.push((idx+1, tile_cost(tt) * DIAGONAL_COST + self.hazard_cost(idx+1))) };
+        if is_exit_valid(x-1, y+1) { exits.push((idx+w, tile_cost(tt) * DIAGONAL_COST + self.hazard_cost(idx+w))) };
 
         exits
     }
@@ -168,3 +221,80 @@ impl Algorithm2D for Map {
         Point::new(self.width, self.height)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Map {
+        // an open floor surrounded by a wall, for tests
+        pub fn open(width: i32, height: i32) -> Map {
+            let mut map = Map::new(&MapData {
+                name: "Test".to_string(),
+                width,
+                height,
+                area_level: 1,
+                start: false,
+                town: false,
+                indoors: true,
+                prev_maps: None,
+                next_maps: None,
+                weather: None
+            });
+            for y in 1..height - 1 {
+                for x in 1..width - 1 {
+                    let idx = map.xy_idx(x, y);
+                    map.tiles[idx] = TileType::Floor;
+                }
+            }
+            map
+        }
+    }
+
+    #[test]
+    fn footprint_covers_the_whole_size() {
+        let map = Map::open(10, 10);
+        let footprint = map.footprint(map.xy_idx(2, 3), Some(&TileSize{ x: 2, y: 2 }));
+        assert_eq!(footprint, vec![map.xy_idx(2, 3), map.xy_idx(3, 3), map.xy_idx(2, 4), map.xy_idx(3, 4)]);
+    }
+
+    #[test]
+    fn footprint_of_a_normal_entity_is_its_tile() {
+        let map = Map::open(10, 10);
+        assert_eq!(map.footprint(map.xy_idx(4, 4), None), vec![map.xy_idx(4, 4)]);
+    }
+
+    #[test]
+    fn footprint_is_clipped_at_the_map_edge() {
+        let map = Map::open(10, 10);
+        let footprint = map.footprint(map.xy_idx(9, 9), Some(&TileSize{ x: 2, y: 2 }));
+        assert_eq!(footprint, vec![map.xy_idx(9, 9)]);
+    }
+
+    #[test]
+    fn large_entities_only_fit_inside_the_walls() {
+        let map = Map::open(10, 10);
+        let entity = World::new().create_entity().build();
+        let size = TileSize{ x: 2, y: 2 };
+
+        assert!(map.can_fit(entity, map.xy_idx(1, 1), Some(&size), false));
+        assert!(map.can_fit(entity, map.xy_idx(7, 7), Some(&size), false));
+        // the far corner would overlap the border wall
+        assert!(!map.can_fit(entity, map.xy_idx(8, 8), Some(&size), false));
+        assert!(!map.can_fit(entity, map.xy_idx(0, 4), Some(&size), false));
+        assert!(!map.can_fit(entity, map.xy_idx(4, 9), Some(&size), false));
+    }
+
+    #[test]
+    fn large_entities_dont_fit_through_walls() {
+        let mut map = Map::open(10, 10);
+        let entity = World::new().create_entity().build();
+        let size = TileSize{ x: 2, y: 2 };
+        let wall_idx = map.xy_idx(5, 5);
+        map.tiles[wall_idx] = TileType::Wall;
+
+        assert!(!map.can_fit(entity, map.xy_idx(4, 4), Some(&size), false));
+        assert!(!map.can_fit(entity, map.xy_idx(5, 5), Some(&size), false));
+        assert!(map.can_fit(entity, map.xy_idx(6, 6), Some(&size), false));
+    }
+}
@@ -0,0 +1,58 @@
+use rltk::{Algorithm2D, BaseMap, NavigationPath, Point};
+use specs::prelude::*;
+use super::Map;
+use crate::TileSize;
+
+// wraps the shared map with the rules for a single path search
+pub struct PathingMap<'a> {
+    map: &'a Map,
+    swims: bool,
+    footprint: Option<(Entity, i32, i32, usize)>
+}
+
+impl<'a> PathingMap<'a> {
+    pub fn new(map: &'a Map, swims: bool) -> PathingMap<'a> {
+        PathingMap{ map, swims, footprint: None }
+    }
+
+    // large entities need room for their whole footprint at every step
+    pub fn sized(mut self, entity: Entity, size: &TileSize, goal: usize) -> PathingMap<'a> {
+        self.footprint = Some((entity, size.x, size.y, goal));
+        self
+    }
+
+    pub fn find_path(&self, start: usize, end: usize) -> NavigationPath {
+        rltk::a_star_search(start, end, self)
+    }
+
+    fn is_exit_valid(&self, x: i32, y: i32) -> bool {
+        if x < 1 || x > self.map.width - 1 || y < 1 || y > self.map.height - 1 { return false; }
+        let idx = self.map.xy_idx(x, y);
+        if let Some((entity, width, height, goal)) = self.footprint {
+            // large entities only need to get their corner to the goal
+            if idx == goal && self.map.can_enter(idx, self.swims) { return true; }
+            return self.map.fits(entity, x, y, width, height, self.swims);
+        }
+        self.map.can_enter(idx, self.swims) && !self.map.blocks_pathing(idx)
+    }
+}
+
+impl<'a> BaseMap for PathingMap<'a> {
+    fn is_opaque(&self, idx: usize) -> bool {
+        self.map.is_opaque(idx)
+    }
+
+    fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
+        self.map.get_pathing_distance(idx1, idx2)
+    }
+
+    fn get_available_exits(&self, idx: usize) -> rltk::SmallVec<[(usize, f32); 10]> {
+        self.map.exits(idx, |x, y| self.is_exit_valid(x, y))
+    }
+}
+
+impl<'a> Algorithm2D for PathingMap<'a> {
+    fn dimensions(&self) -> Point {
+        self.map.dimensions()
+    }
+}
@@ -1,8 +1,8 @@
-use super::{Map, TileType};
+use super::{HazardType, Map, TileType};
 use rltk::{RGB, FontCharType};
 
 pub fn tile_glyph(idx: usize, map: &Map) -> (FontCharType, RGB, RGB) {
-    let (glyph, mut fg, mut bg) =
+    let (mut glyph, mut fg, mut bg) =
         match map.name.as_str() {
             "Forest" | "Dark Forest" | "Orc Camp" => get_forest_glyph(idx, map),
             "Caverns" => get_limestone_caverns_glyph(idx, map),
@@ -10,10 +10,33 @@ pub fn tile_glyph(idx: usize, map: &Map) -> (FontCharType, RGB, RGB) {
     };
 
     if map.bloodstains.contains(&idx) { bg = RGB::from_f32(0.75, 0., 0.); }
+    // hazards are only shown while they can be seen
+    if let Some(hazard) = map.hazards.get(&idx) {
+        if map.visible_tiles[idx] {
+            match hazard.hazard_type {
+                HazardType::Fire => {
+                    glyph = rltk::to_cp437('^');
+                    fg = RGB::named(rltk::ORANGE);
+                    bg = RGB::from_f32(0.5, 0.1, 0.);
+                }
+                HazardType::PoisonGas => {
+                    glyph = rltk::to_cp437('▒');
+                    fg = RGB::named(rltk::CHARTREUSE);
+                    bg = RGB::from_f32(0., 0.25, 0.);
+                }
+                HazardType::Lightning => {
+                    glyph = rltk::to_cp437('~');
+                    fg = RGB::named(rltk::YELLOW);
+                    bg = RGB::from_f32(0., 0., 0.4);
+                }
+                HazardType::Ice => {}
+            }
+        }
+    }
     if !map.visible_tiles[idx] {
         fg = fg.to_greyscale();
         bg = RGB::from_f32(0., 0., 0.); // hide out of sight bloodstains
-    } else if map.indoors {
+    } else {
         fg = fg * map.light[idx];
         bg = bg * map.light[idx];
     }
@@ -72,6 +95,10 @@ fn get_default_glyph(idx: usize, map: &Map) -> (FontCharType, RGB, RGB) {
             glyph = rltk::to_cp437('▓');
             fg = RGB::named(rltk::NAVY_BLUE);
         }
+        TileType::Ice => {
+            glyph = rltk::to_cp437('▒');
+            fg = RGB::named(rltk::LIGHT_CYAN);
+        }
         TileType::Gravel => {
             glyph = rltk::to_cp437(';');
             fg = RGB::named(rltk::GRAY);
@@ -165,6 +192,10 @@ fn get_forest_glyph(idx: usize, map: &Map) -> (FontCharType, RGB, RGB) {
             glyph = rltk::to_cp437('▓');
             fg = RGB::named(rltk::BLUE);
         }
+        TileType::Ice => {
+            glyph = rltk::to_cp437('▒');
+            fg = RGB::named(rltk::LIGHT_CYAN);
+        }
         TileType::Gravel => {
             glyph = rltk::to_cp437(';');
             fg = RGB::named(rltk::GRAY);
@@ -228,6 +259,10 @@ fn get_limestone_caverns_glyph(idx: usize, map: &Map) -> (FontCharType, RGB, RGB
             glyph = rltk::to_cp437('▓');
             fg = RGB::named(rltk::BLUE);
         }
+        TileType::Ice => {
+            glyph = rltk::to_cp437('▒');
+            fg = RGB::named(rltk::LIGHT_CYAN);
+        }
         TileType::Gravel => {
             glyph = rltk::to_cp437(';');
             fg = RGB::named(rltk::GRAY);
@@ -14,6 +14,7 @@ pub enum TileType {
     Bridge,
     Gravel,
     TownWall,
+    Ice,
     Placeholder
 }
 
@@ -27,7 +28,8 @@ pub fn tile_walkable(tt: &TileType) -> bool {
         TileType::ShallowWater |
         TileType::WoodFloor |
         TileType::Bridge |
-        TileType::Gravel
+        TileType::Gravel |
+        TileType::Ice
     )
 }
 
@@ -0,0 +1,71 @@
+use serde::{Serialize, Deserialize};
+use crate::{raws::MapData, RandomTable};
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum Weather { Clear, Rain, Fog, Storm }
+
+// how far anything can see through fog
+const FOG_SIGHT_RANGE: i32 = 4;
+
+impl Weather {
+    pub fn sight_range(&self) -> Option<i32> {
+        match self {
+            Weather::Fog => Some(FOG_SIGHT_RANGE),
+            _ => None
+        }
+    }
+
+    pub fn ranged_hit_penalty(&self) -> i32 {
+        match self {
+            Weather::Rain => 2,
+            Weather::Storm => 4,
+            _ => 0
+        }
+    }
+
+    pub fn is_wet(&self) -> bool {
+        matches!(self, Weather::Rain | Weather::Storm)
+    }
+
+    pub fn start_message(&self) -> &'static str {
+        match self {
+            Weather::Clear => "The skies clear.",
+            Weather::Rain => "It starts to rain.",
+            Weather::Fog => "A thick fog rolls in.",
+            Weather::Storm => "A storm breaks overhead!"
+        }
+    }
+}
+
+pub fn parse_weather(name: &str) -> Option<Weather> {
+    match name {
+        "clear" => Some(Weather::Clear),
+        "rain" => Some(Weather::Rain),
+        "fog" => Some(Weather::Fog),
+        "storm" => Some(Weather::Storm),
+        _ => None
+    }
+}
+
+// indoor maps and maps without weather odds are always clear
+pub fn roll_weather(map_data: &MapData) -> Weather {
+    if map_data.indoors { return Weather::Clear; }
+    let odds = match &map_data.weather {
+        Some(odds) => odds,
+        None => return Weather::Clear
+    };
+
+    let mut names: Vec<&String> = odds.keys().collect();
+    names.sort();
+    let mut table = RandomTable::new();
+    for name in names {
+        table = table.add(name.clone(), odds[name]);
+    }
+    match table.roll() {
+        Some(name) => parse_weather(&name).unwrap_or_else(|| {
+            rltk::console::log(format!("WARNING - Unknown weather [{}] for {}", name, map_data.name));
+            Weather::Clear
+        }),
+        None => Weather::Clear
+    }
+}
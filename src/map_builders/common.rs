@@ -1,4 +1,5 @@
 use super::{Map, TileType};
+use crate::tile_walkable;
 use std::cmp::{max, min};
 
 #[derive(PartialEq, Copy, Clone)]
@@ -115,3 +116,25 @@ fn apply_paint(map: &mut Map, brush_size: i32, x: i32, y: i32) {
         }
     }
 }
+
+// flood fills out from the start over walkable tiles that aren't blocked
+pub fn reachable_tiles<F>(map: &Map, start_idx: usize, blocked: F) -> Vec<bool>
+    where F: Fn(usize) -> bool
+{
+    let mut reached = vec![false; map.tiles.len()];
+    reached[start_idx] = true;
+    let mut open_list = vec![start_idx];
+    while let Some(idx) = open_list.pop() {
+        let (x, y) = map.idx_xy(idx);
+        for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < 0 || nx >= map.width || ny < 0 || ny >= map.height { continue; }
+            let next_idx = map.xy_idx(nx, ny);
+            if !reached[next_idx] && tile_walkable(&map.tiles[next_idx]) && !blocked(next_idx) {
+                reached[next_idx] = true;
+                open_list.push(next_idx);
+            }
+        }
+    }
+    reached
+}
@@ -20,7 +20,9 @@ impl DoorPlacement {
             for hall in halls.iter() {
                 if hall.len() > 2 { // ignore tiny corridors
                     if self.door_possible(build_data, hall[0]) {
-                        build_data.map.spawn_list.push((hall[0], "Door".to_string()));
+                        // some corridors are barricaded instead
+                        let door = if rng::roll_dice(1, 10) == 1 { "Barricade" } else { "Door" };
+                        build_data.map.spawn_list.push((hall[0], door.to_string()));
                     }
                 }
             }
@@ -8,11 +8,67 @@ pub fn caverns_builder(map_data: &MapData) -> BuilderChain {
     chain.with(CullUnreachable::new());
     chain.with(AreaStartingPosition::new(XStart::LEFT, YStart::CENTER, false));
     chain.with(VoronoiSpawning::new());
+    chain.with(DragonLair::new());
     // chain.with(DistantExit::new());
     chain.with(CavernDecorator::new());
     chain
 }
 
+// the deepest cavern is opened up into a lair big enough for its dragon
+pub struct DragonLair {}
+
+impl MetaMapBuilder for DragonLair {
+    fn build_map(&mut self, build_data: &mut BuilderMap) {
+        self.build(build_data);
+    }
+}
+
+impl DragonLair {
+    pub fn new() -> Box<DragonLair> {
+        Box::new(DragonLair{})
+    }
+
+    fn build(&mut self, build_data: &mut BuilderMap) {
+        let start = match &build_data.map.starting_position {
+            Some(start) => build_data.map.xy_idx(start.x, start.y),
+            None => return
+        };
+        build_data.map.populate_blocked();
+        let dijkstra_map = rltk::DijkstraMap::new(build_data.map.width as usize, build_data.map.height as usize, &[start], &build_data.map, 1000.0);
+
+        let mut lair: Option<(usize, f32)> = None;
+        for (idx, distance) in dijkstra_map.map.iter().enumerate() {
+            if *distance < f32::MAX && lair.is_none_or(|(_, furthest)| *distance > furthest) {
+                lair = Some((idx, *distance));
+            }
+        }
+        let (lair_x, lair_y) = match lair {
+            Some((idx, _)) => build_data.map.idx_xy(idx),
+            None => return
+        };
+
+        for y in lair_y - 3 ..= lair_y + 3 {
+            for x in lair_x - 3 ..= lair_x + 3 {
+                if x > 1 && x < build_data.map.width - 2 && y > 1 && y < build_data.map.height - 2 {
+                    let idx = build_data.map.xy_idx(x, y);
+                    build_data.map.tiles[idx] = TileType::Floor;
+                }
+            }
+        }
+
+        // keep the lair clear for the dragon
+        let lair_x = i32::max(2, i32::min(lair_x, build_data.map.width - 4));
+        let lair_y = i32::max(2, i32::min(lair_y, build_data.map.height - 4));
+        let width = build_data.map.width;
+        build_data.map.spawn_list.retain(|(idx, _)| {
+            let (x, y) = ((*idx as i32) % width, (*idx as i32) / width);
+            x < lair_x || x > lair_x + 1 || y < lair_y || y > lair_y + 1
+        });
+        let dragon_idx = build_data.map.xy_idx(lair_x, lair_y);
+        build_data.map.spawn_list.push((dragon_idx, "Cave Dragon".to_string()));
+    }
+}
+
 pub struct CavernDecorator {}
 
 impl MetaMapBuilder for CavernDecorator {
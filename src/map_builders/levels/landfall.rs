@@ -90,10 +90,17 @@ impl TownBuilder {
         // piers
         for _ in 0..rng::roll_dice(1, 4)+6 { // 8 to 11 piers
             let y = rng::roll_dice(1, build_data.map.height)-1;
-            for x in 2 + rng::roll_dice(1, 6)..water_width[y as usize] + 4 {
+            let pier_end = 2 + rng::roll_dice(1, 6);
+            for x in pier_end..water_width[y as usize] + 4 {
                 let idx = build_data.map.xy_idx(x, y);
                 build_data.map.tiles[idx] = TileType::Bridge;
             }
+
+            // some piers have a raft moored at the end
+            if y > 0 && y < build_data.map.height - 1 && rng::roll_dice(1, 2) == 1 {
+                let idx = build_data.map.xy_idx(pier_end - 1, y);
+                build_data.map.spawn_list.push((idx, "Raft".to_string()));
+            }
         }
     }
 
@@ -248,7 +255,7 @@ impl TownBuilder {
 
         // place other entities
         let mut to_place: Vec<&str> = vec!["Barkeep", "Shady Salesman", "Patron", "Patron",
-            "Keg", "Table", "Chair", "Table", "Chair"];
+            "Sellsword", "Sellsword", "Keg", "Table", "Chair", "Table", "Chair"];
         self.random_building_spawn(building, build_data, &mut to_place, player_idx);
     }
 
@@ -281,12 +288,12 @@ impl TownBuilder {
     }
 
     fn build_weaponsmith(&mut self, building: &(i32, i32, i32, i32), build_data: &mut BuilderMap) {
-        let mut to_place: Vec<&str> = vec!["Weaponsmith", "Table", "Chair"];
+        let mut to_place: Vec<&str> = vec!["Weaponsmith", "Anvil", "Table", "Chair"];
         self.random_building_spawn(building, build_data, &mut to_place, 0);
     }
 
     fn build_armoursmith(&mut self, building: &(i32, i32, i32, i32), build_data: &mut BuilderMap) {
-        let mut to_place: Vec<&str> = vec!["Armoursmith", "Table", "Chair"];
+        let mut to_place: Vec<&str> = vec!["Armoursmith", "Anvil", "Table", "Chair"];
         self.random_building_spawn(building, build_data, &mut to_place, 0);
     }
 
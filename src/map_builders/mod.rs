@@ -17,6 +17,7 @@ mod levels;
 mod maze;
 mod prefabs;
 mod rooms;
+mod secret_doors;
 mod simple_map;
 mod voronoi;
 mod voronoi_spawning;
@@ -37,6 +38,7 @@ use maze::MazeBuilder;
 use prefabs::PrefabBuilder;
 use rltk::Point;
 use rooms::*;
+use secret_doors::SecretDoors;
 use simple_map::SimpleMapBuilder;
 use voronoi::VoronoiCellBuilder;
 use voronoi_spawning::VoronoiSpawning;
@@ -151,6 +153,8 @@ pub fn random_builder(map_data: &MapData) -> BuilderChain {
 
     builder.with(DoorPlacement::new());
     builder.with(PrefabBuilder::vaults());
+    // secret doors go in after culling so the areas behind them are kept
+    builder.with(SecretDoors::new());
     builder
 }
 
@@ -1,5 +1,5 @@
-use super::{InitialMapBuilder, MetaMapBuilder, BuilderMap, TileType, Position};
-use crate::rng;
+use super::{InitialMapBuilder, MetaMapBuilder, BuilderMap, TileType, Position, reachable_tiles};
+use crate::{rng, BOSS_LOCK};
 pub mod prefab_levels;
 pub mod prefab_sections;
 pub mod prefab_rooms;
@@ -119,9 +119,25 @@ impl PrefabBuilder {
                 build_data.map.tiles[idx] = TileType::Floor;
                 build_data.map.spawn_list.push((idx, "Door".to_string()));
             }
+            'L' => {
+                // opened with the key dropped by the level's boss
+                build_data.map.tiles[idx] = TileType::Floor;
+                build_data.map.spawn_list.push((idx, "Door".to_string()));
+                build_data.map.locks.insert(idx, BOSS_LOCK.to_string());
+            }
+            'V' => {
+                build_data.map.tiles[idx] = TileType::Floor;
+                build_data.map.spawn_list.push((idx, "Door".to_string()));
+                build_data.map.locks.insert(idx, format!("vault:{}", idx));
+            }
             '◘' => {
                 build_data.map.tiles[idx] = TileType::Floor;
-                build_data.map.spawn_list.push((idx, random_chest()));
+                let chest = random_chest();
+                // the best chests have to be picked open
+                if chest == "Epic Chest" || chest == "Legendary Chest" {
+                    build_data.map.locks.insert(idx, format!("chest:{}", idx));
+                }
+                build_data.map.spawn_list.push((idx, chest));
             }
             _ => {
                 rltk::console::log(format!("Unknown glyph loading map: {}", ch));
@@ -186,6 +202,7 @@ impl PrefabBuilder {
                 i += 1;
             }
         }
+        PrefabBuilder::place_keys(build_data, &HashSet::new());
     }
 
     fn apply_previous_iteration<F>(&mut self, mut filter: F, build_data: &mut BuilderMap)
@@ -200,6 +217,48 @@ impl PrefabBuilder {
         });
     }
 
+    // every locked vault or chest gets a key somewhere the player can walk to from the start
+    fn place_keys(build_data: &mut BuilderMap, used_tiles: &HashSet<usize>) {
+        let key_tiles: HashSet<usize> = build_data.map.spawn_list.iter()
+            .filter(|spawn| spawn.1 == "Iron Key")
+            .map(|spawn| spawn.0)
+            .collect();
+        let keyed: HashSet<String> = key_tiles.iter().filter_map(|idx| build_data.map.locks.get(idx).cloned()).collect();
+        let mut missing: Vec<String> = build_data.map.locks.iter()
+            .filter(|(idx, lock_id)| !key_tiles.contains(idx) && lock_id.as_str() != BOSS_LOCK && !keyed.contains(*lock_id))
+            .map(|(_idx, lock_id)| lock_id.clone())
+            .collect();
+        if missing.is_empty() { return; }
+        missing.sort();
+        missing.dedup();
+
+        let start_idx = match &build_data.map.starting_position {
+            Some(start) => build_data.map.xy_idx(start.x, start.y),
+            None => {
+                rltk::console::log("WARNING - no starting position to place keys from");
+                return;
+            }
+        };
+        // nothing behind a lock counts as reachable
+        let reached = reachable_tiles(&build_data.map, start_idx, |idx| build_data.map.locks.contains_key(&idx));
+
+        for lock_id in missing {
+            let free_tiles: Vec<usize> = build_data.map.tiles.iter().enumerate()
+                .filter(|(idx, tile)| **tile == TileType::Floor && reached[*idx] && !used_tiles.contains(idx)
+                    && !build_data.map.locks.contains_key(idx)
+                    && !build_data.map.spawn_list.iter().any(|spawn| spawn.0 == *idx))
+                .map(|(idx, _tile)| idx)
+                .collect();
+            if free_tiles.is_empty() {
+                rltk::console::log(format!("WARNING - no room for the key to {}", lock_id));
+                return;
+            }
+            let key_idx = free_tiles[(rng::roll_dice(1, free_tiles.len() as i32) - 1) as usize];
+            build_data.map.locks.insert(key_idx, lock_id);
+            build_data.map.spawn_list.push((key_idx, "Iron Key".to_string()));
+        }
+    }
+
     fn apply_room_vaults(&mut self, build_data: &mut BuilderMap) {
         // apply the previous builder and keep all entities it spawns
         self.apply_previous_iteration(|_x,_y| true, build_data);
@@ -276,18 +335,23 @@ impl PrefabBuilder {
 
                 // load the ascii and add it to the map
                 let string_vec = PrefabBuilder::read_ascii_to_vec(vault.template);
+                let mut vault_tiles: Vec<usize> = Vec::new();
                 let mut i = 0;
                 for ty in 0..vault.height {
                     for tx in 0..vault.width {
                         let idx = build_data.map.xy_idx(tx as i32 + chunk_x, ty as i32 + chunk_y);
                         self.char_to_map(string_vec[i], idx, build_data);
                         used_tiles.insert(idx);
+                        vault_tiles.push(idx);
                         i += 1;
                     }
                 }
                 vault_list.remove(vault_index);
             }
         }
+
+        // sealed vaults have their key hidden elsewhere on the level
+        PrefabBuilder::place_keys(build_data, &used_tiles);
     }
 
 
@@ -334,3 +398,77 @@ fn random_chest() -> String {
         _ => "Legendary Chest".to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Map;
+    use std::collections::VecDeque;
+
+    fn build_data() -> BuilderMap {
+        let mut map = Map::open(12, 12);
+        map.starting_position = Some(Position{ x: 1, y: 1 });
+        BuilderMap {
+            map,
+            rooms: None,
+            corridors: None,
+            history: Vec::new(),
+            prev_maps: VecDeque::new(),
+            next_maps: VecDeque::new()
+        }
+    }
+
+    fn keys_for<'a>(build_data: &'a BuilderMap, lock_id: &str) -> Vec<&'a usize> {
+        build_data.map.spawn_list.iter()
+            .filter(|spawn| spawn.1 == "Iron Key" && build_data.map.locks.get(&spawn.0).is_some_and(|id| id == lock_id))
+            .map(|spawn| &spawn.0)
+            .collect()
+    }
+
+    #[test]
+    fn every_lock_gets_one_matching_key() {
+        let mut build_data = build_data();
+        let chest_idx = build_data.map.xy_idx(5, 5);
+        let boss_idx = build_data.map.xy_idx(6, 6);
+        build_data.map.locks.insert(chest_idx, format!("chest:{}", chest_idx));
+        build_data.map.locks.insert(boss_idx, BOSS_LOCK.to_string());
+
+        PrefabBuilder::place_keys(&mut build_data, &HashSet::new());
+        PrefabBuilder::place_keys(&mut build_data, &HashSet::new());
+
+        assert_eq!(keys_for(&build_data, &format!("chest:{}", chest_idx)).len(), 1);
+        // the boss carries that key
+        assert!(keys_for(&build_data, BOSS_LOCK).is_empty());
+    }
+
+    #[test]
+    fn keys_are_never_behind_a_lock_or_in_a_vault() {
+        let mut build_data = build_data();
+        // wall off the right side of the map behind a locked door
+        for y in 1..11 {
+            let idx = build_data.map.xy_idx(4, y);
+            build_data.map.tiles[idx] = TileType::Wall;
+        }
+        let door_idx = build_data.map.xy_idx(4, 5);
+        build_data.map.tiles[door_idx] = TileType::Floor;
+        build_data.map.locks.insert(door_idx, format!("vault:{}", door_idx));
+        let vault_tiles: HashSet<usize> = (1..11).map(|y| build_data.map.xy_idx(1, y)).collect();
+
+        for _ in 0..20 {
+            let mut attempt = BuilderMap {
+                map: build_data.map.clone(),
+                rooms: None,
+                corridors: None,
+                history: Vec::new(),
+                prev_maps: VecDeque::new(),
+                next_maps: VecDeque::new()
+            };
+            PrefabBuilder::place_keys(&mut attempt, &vault_tiles);
+
+            let keys = keys_for(&attempt, &format!("vault:{}", door_idx));
+            assert_eq!(keys.len(), 1);
+            let (x, _y) = attempt.map.idx_xy(*keys[0]);
+            assert!(x == 2 || x == 3);
+        }
+    }
+}
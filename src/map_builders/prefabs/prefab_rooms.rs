@@ -50,10 +50,10 @@ pub const OGRE_TRIO: PrefabRoom = PrefabRoom{
 const OGRE_TRIO_MAP: &str = "
           
  ######## 
- # %  % # 
- #  O   # 
-    O % # 
- #  O   # 
+ # %O % # 
+ #    O # 
+ V  O   # 
+ #     %# 
  # %  % # 
  ######## 
           
@@ -63,7 +63,7 @@ const WARBOSS_DEN_TEMPLATE: &str = "
 #  # ◘ #  # ◘ #     ##         ##     # ◘ #  # ◘ #  #
 #  ##+##  ##+##      #####+####       ##+##  ##+##  #
 #                                                   #
-##########################+##########################
+##########################L##########################
 #                                                   #
 #                      o  B  o                      #
 #                                                   #
@@ -0,0 +1,145 @@
+use super::{reachable_tiles, BuilderMap, MetaMapBuilder, TileType};
+use crate::rng;
+
+// how many places are tried when fitting a hidden room into solid rock
+const HIDDEN_ROOM_ATTEMPTS: i32 = 50;
+
+pub struct SecretDoors {}
+
+impl MetaMapBuilder for SecretDoors {
+    fn build_map(&mut self, build_data: &mut BuilderMap) {
+        self.build(build_data);
+    }
+}
+
+impl SecretDoors {
+    pub fn new() -> Box<SecretDoors> {
+        Box::new(SecretDoors{})
+    }
+
+    fn build(&mut self, build_data: &mut BuilderMap) {
+        self.hide_doors(build_data);
+        self.dead_ends(build_data);
+        self.hidden_room(build_data);
+    }
+
+    // secret doors are walls until they are found
+    fn make_secret(&self, build_data: &mut BuilderMap, idx: usize) -> bool {
+        let previous = build_data.map.tiles[idx].clone();
+        build_data.map.tiles[idx] = TileType::Wall;
+
+        // the exits must still be reachable without searching
+        if !self.exits_connected(build_data) {
+            build_data.map.tiles[idx] = previous;
+            return false;
+        }
+        build_data.map.secret_doors.insert(idx);
+        true
+    }
+
+    fn exits_connected(&self, build_data: &BuilderMap) -> bool {
+        let map = &build_data.map;
+        let mut exits: Vec<usize> = map.transitions.values().map(|point| map.xy_idx(point.x, point.y)).collect();
+        if let Some(start) = &map.starting_position {
+            exits.push(map.xy_idx(start.x, start.y));
+        }
+        let start_idx = match exits.first() {
+            Some(idx) => *idx,
+            None => return true
+        };
+
+        let reached = reachable_tiles(map, start_idx, |_| false);
+        exits.iter().all(|idx| reached[*idx])
+    }
+
+    fn hide_doors(&mut self, build_data: &mut BuilderMap) {
+        let doors: Vec<usize> = build_data.map.spawn_list.iter()
+            .filter(|spawn| spawn.1 == "Door" && !build_data.map.locks.contains_key(&spawn.0))
+            .map(|spawn| spawn.0)
+            .collect();
+
+        for idx in doors {
+            if rng::roll_dice(1, 8) == 1 && self.make_secret(build_data, idx) {
+                build_data.map.spawn_list.retain(|spawn| !(spawn.0 == idx && spawn.1 == "Door"));
+            }
+        }
+    }
+
+    fn dead_ends(&mut self, build_data: &mut BuilderMap) {
+        let width = build_data.map.width;
+        let height = build_data.map.height;
+        let mut passages: Vec<usize> = Vec::new();
+
+        for y in 2..height - 2 {
+            for x in 2..width - 2 {
+                if build_data.map.tiles[build_data.map.xy_idx(x, y)] != TileType::Floor { continue; }
+
+                let open: Vec<(i32, i32)> = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter()
+                    .filter(|(dx, dy)| build_data.map.tiles[build_data.map.xy_idx(x + dx, y + dy)] == TileType::Floor)
+                    .copied()
+                    .collect();
+                if open.len() != 1 { continue; }
+
+                // a dead end may carry on through the wall into the space beyond
+                let (dx, dy) = (-open[0].0, -open[0].1);
+                let wall_idx = build_data.map.xy_idx(x + dx, y + dy);
+                let beyond_idx = build_data.map.xy_idx(x + dx * 2, y + dy * 2);
+                if build_data.map.tiles[wall_idx] == TileType::Wall
+                    && build_data.map.tiles[beyond_idx] == TileType::Floor
+                    && rng::roll_dice(1, 3) == 1
+                {
+                    passages.push(wall_idx);
+                }
+            }
+        }
+
+        for idx in passages {
+            self.make_secret(build_data, idx);
+        }
+    }
+
+    fn hidden_room(&mut self, build_data: &mut BuilderMap) {
+        let width = build_data.map.width;
+        let height = build_data.map.height;
+
+        for _ in 0..HIDDEN_ROOM_ATTEMPTS {
+            let x = rng::roll_dice(1, width - 2);
+            let y = rng::roll_dice(1, height - 2);
+            if build_data.map.tiles[build_data.map.xy_idx(x, y)] != TileType::Floor { continue; }
+
+            // the room sits behind a single wall tile
+            let (dx, dy) = match rng::roll_dice(1, 4) {
+                1 => (-1, 0),
+                2 => (1, 0),
+                3 => (0, -1),
+                _ => (0, 1)
+            };
+            let (centre_x, centre_y) = (x + dx * 3, y + dy * 3);
+            if centre_x < 3 || centre_x > width - 4 || centre_y < 3 || centre_y > height - 4 { continue; }
+
+            // it has to be carved out of solid rock
+            let mut solid = true;
+            for ry in centre_y - 2..=centre_y + 2 {
+                for rx in centre_x - 2..=centre_x + 2 {
+                    if build_data.map.tiles[build_data.map.xy_idx(rx, ry)] != TileType::Wall { solid = false; }
+                }
+            }
+            if !solid { continue; }
+
+            for ry in centre_y - 1..=centre_y + 1 {
+                for rx in centre_x - 1..=centre_x + 1 {
+                    let idx = build_data.map.xy_idx(rx, ry);
+                    build_data.map.tiles[idx] = TileType::Floor;
+                }
+            }
+            let door_idx = build_data.map.xy_idx(x + dx, y + dy);
+            self.make_secret(build_data, door_idx);
+
+            // hidden rooms are worth finding
+            let chest = if rng::roll_dice(1, 4) == 1 { "Epic Chest" } else { "Rare Chest" };
+            let centre_idx = build_data.map.xy_idx(centre_x, centre_y);
+            build_data.map.spawn_list.push((centre_idx, chest.to_string()));
+            return;
+        }
+    }
+}
@@ -12,5 +12,9 @@ pub struct AbilityData {
 #[derive(Deserialize, Debug)]
 pub struct AbilityLevelData {
     pub mana_cost: Option<i32>,
+    pub rage_cost: Option<i32>,
+    pub focus_cost: Option<i32>,
+    pub cooldown: Option<i32>,
+    pub charges: Option<i32>,
     pub effects: HashMap<String, String>
 }
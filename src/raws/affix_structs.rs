@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use super::{AttributeBonusData, SkillBonusData, RegenBonusData};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AffixData {
+    pub name: String,
+    pub affix_type: String,
+    pub item_type: String,
+    pub min_level: i32,
+    pub max_level: i32,
+    pub weight: i32,
+    pub base_value: i32,
+    pub attribute_bonuses: Option<AttributeBonusData>,
+    pub skill_bonuses: Option<SkillBonusData>,
+    pub regen_bonuses: Option<RegenBonusData>,
+    pub proc_chance: Option<f32>,
+    pub proc_target: Option<String>,
+    pub proc_effects: Option<HashMap<String, String>>,
+    pub cursed: Option<bool>
+}
@@ -1,11 +1,17 @@
 use serde::Deserialize;
 use std::collections::HashMap;
-use super::{AttributeBonusData, SkillBonusData};
+use super::{AttributeBonusData, SkillBonusData, MobAttributesData};
 
 #[derive(Deserialize, Debug)]
 pub struct CharacterClassData {
     pub name: String,
     pub description: String,
+    pub colour: Option<String>,
+    pub starting_attributes: Option<MobAttributesData>,
+    pub starting_skills: Option<HashMap<String, i32>>,
+    pub starting_gold: Option<String>,
+    pub faction: Option<String>,
+    pub resources: Option<HashMap<String, i32>>,
     pub passives: Vec<CharacterClassPassiveData>,
     pub starting_equipment: Vec<String>,
     pub starting_items: Vec<String>,
@@ -17,13 +17,19 @@ pub struct ItemData {
     pub attribute_bonuses: Option<AttributeBonusData>,
     pub skill_bonuses: Option<SkillBonusData>,
     pub set_name: Option<String>,
-    pub regen_bonuses: Option<RegenBonusData>
+    pub regen_bonuses: Option<RegenBonusData>,
+    pub durability: Option<i32>,
+    pub digging: Option<i32>,
+    pub key: Option<bool>,
+    pub lockpick: Option<bool>,
+    pub water_damage: Option<bool>
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct ConsumableData {
     pub effects: HashMap<String, String>,
-    pub charges: Option<i32>
+    pub charges: Option<i32>,
+    pub naming: Option<String>
 }
 
 #[derive(Deserialize, Debug, Clone)]
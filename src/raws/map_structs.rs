@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 use serde::Deserialize;
 
@@ -15,5 +15,6 @@ pub struct MapData {
     #[serde(default)]
     pub indoors: bool,
     pub prev_maps: Option<VecDeque<String>>,
-    pub next_maps: Option<VecDeque<String>>
+    pub next_maps: Option<VecDeque<String>>,
+    pub weather: Option<HashMap<String, i32>>
 }
@@ -22,9 +22,13 @@ pub struct MobData {
     pub movement: String,
     pub gold: Option<String>,
     pub vendor: Option<String>,
+    pub vendor_hours: Option<(i32, i32)>,
+    pub nocturnal: Option<bool>,
+    pub hire_cost: Option<i32>,
     pub quest_giver: Option<bool>,
     pub abilities: Option<Vec<MobAbilityData>>,
     pub boss: Option<bool>,
+    pub smashes: Option<bool>,
     pub map_marker: Option<MapMarkerData>,
     pub species: String
 }
@@ -1,5 +1,6 @@
 mod item_structs;
 mod item_set_structs;
+mod affix_structs;
 mod mob_structs;
 mod prop_structs;
 mod spawn_table_structs;
@@ -11,10 +12,12 @@ mod character_class_structs;
 mod quest_structs;
 mod species_structs;
 mod map_structs;
+mod recipe_structs;
 mod rawmaster;
 
 pub use item_structs::*;
 use item_set_structs::*;
+pub use affix_structs::*;
 use mob_structs::*;
 use prop_structs::*;
 use spawn_table_structs::*;
@@ -26,6 +29,7 @@ pub use character_class_structs::*;
 pub use quest_structs::*;
 pub use species_structs::*;
 pub use map_structs::*;
+pub use recipe_structs::*;
 pub use rawmaster::*;
 
 use serde::Deserialize;
@@ -81,6 +85,7 @@ pub struct RegenBonusData {
 pub struct Raws {
     pub items: Vec<ItemData>,
     pub item_sets: Vec<ItemSetData>,
+    pub affixes: Vec<AffixData>,
     pub item_class_colours: HashMap<String, String>,
     pub mobs: Vec<MobData>,
     pub props: Vec<PropData>,
@@ -92,7 +97,8 @@ pub struct Raws {
     pub character_classes: Vec<CharacterClassData>,
     pub quests: Vec<QuestData>,
     pub species: Vec<SpeciesData>,
-    pub maps: Vec<MapData>
+    pub maps: Vec<MapData>,
+    pub recipes: Vec<RecipeData>
 }
 
 pub fn load_raws() {
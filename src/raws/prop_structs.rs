@@ -11,7 +11,18 @@ pub struct PropData {
     pub door_open: Option<bool>,
     pub entry_trigger: Option<EntryTriggerData>,
     pub light: Option<PropLightData>,
-    pub map_marker: Option<MapMarkerData>
+    pub map_marker: Option<MapMarkerData>,
+    pub gather: Option<String>,
+    pub crafting_station: Option<bool>,
+    pub trap: Option<TrapData>,
+    pub boat: Option<bool>,
+    pub breakable: Option<i32>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TrapData {
+    pub difficulty: i32,
+    pub min_level: i32
 }
 
 #[derive(Deserialize, Debug)]
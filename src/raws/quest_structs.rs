@@ -13,7 +13,8 @@ pub struct QuestData {
 #[derive(Deserialize, Debug)]
 pub struct QuestRewardData {
     pub gold: Option<String>,
-    pub xp: Option<i32>
+    pub xp: Option<i32>,
+    pub remove_curses: Option<bool>
 }
 
 #[derive(Deserialize, Debug)]
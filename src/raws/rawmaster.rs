@@ -6,15 +6,17 @@ use crate::raws::item_set_structs::ItemSetData;
 use crate::raws::AbilityData;
 use crate::raws::MapData;
 use crate::raws::QuestData;
-use crate::Map;
+use crate::{HazardType, Map, Weather, parse_hazard_type, roll_weather};
 use crate::MasterDungeonMap;
-use super::{Raws, Reaction, RenderableData, SpawnTableEntry, MapMarkerData, ItemData};
+use super::{Raws, Reaction, RenderableData, SpawnTableEntry, MapMarkerData, ItemData, AffixData, RecipeData, CharacterClassData};
 use crate::{attr_bonus, hp_at_level, mana_at_level, parse_dice_string, determine_roll};
 use specs::saveload::{MarkedBuilder, SimpleMarker};
 use crate::rng;
 use crate::helpers::*;
 use crate::map_builders::level_builder;
 
+const DEFAULT_DURABILITY: i32 = 100;
+
 pub enum SpawnType {
     AtPosition { x: i32, y: i32 },
     Equipped { by: Entity },
@@ -25,6 +27,7 @@ pub struct RawMaster {
     raws: Raws,
     item_index: HashMap<String, usize>,
     item_set_index: HashMap<String, usize>,
+    affix_index: HashMap<String, usize>,
     mob_index: HashMap<String, usize>,
     prop_index: HashMap<String, usize>,
     ability_index: HashMap<String, usize>,
@@ -33,7 +36,8 @@ pub struct RawMaster {
     chest_index: HashMap<String, usize>,
     character_class_index: HashMap<String, usize>,
     quest_index: HashMap<String, usize>,
-    species_index: HashMap<String, usize>
+    species_index: HashMap<String, usize>,
+    recipe_index: HashMap<String, usize>
 }
 
 impl RawMaster {
@@ -42,6 +46,7 @@ impl RawMaster {
             raws: Raws {
                 items: Vec::new(),
                 item_sets: Vec::new(),
+                affixes: Vec::new(),
                 item_class_colours: HashMap::new(),
                 mobs: Vec::new(),
                 props: Vec::new(),
@@ -53,10 +58,12 @@ impl RawMaster {
                 character_classes: Vec::new(),
                 quests: Vec::new(),
                 species: Vec::new(),
-                maps: Vec::new()
+                maps: Vec::new(),
+                recipes: Vec::new()
             },
             item_index: HashMap::new(),
             item_set_index: HashMap::new(),
+            affix_index: HashMap::new(),
             mob_index: HashMap::new(),
             prop_index: HashMap::new(),
             ability_index: HashMap::new(),
@@ -65,7 +72,8 @@ impl RawMaster {
             chest_index: HashMap::new(),
             character_class_index: HashMap::new(),
             quest_index: HashMap::new(),
-            species_index: HashMap::new()
+            species_index: HashMap::new(),
+            recipe_index: HashMap::new()
         }
     }
 
@@ -93,6 +101,16 @@ impl RawMaster {
             self.item_set_index.insert(item_set.name.clone(), i);
             used_names.insert(item_set.name.clone());
         }
+        // affixes
+        for (i, affix) in self.raws.affixes.iter().enumerate() {
+            if self.affix_index.contains_key(&affix.name) {
+                panic!("ERROR - duplicate affix name in raws [{}]", &affix.name);
+            }
+            if affix.affix_type != "prefix" && affix.affix_type != "suffix" {
+                rltk::console::log(format!("ERROR - unknown affix type in raws [{}]", &affix.affix_type));
+            }
+            self.affix_index.insert(affix.name.clone(), i);
+        }
         // mobs
         for (i, mob) in self.raws.mobs.iter().enumerate() {
             if used_names.contains(&mob.name) {
@@ -197,6 +215,24 @@ impl RawMaster {
             }
             self.quest_index.insert(quest.name.clone(), i);
         }
+        // recipes
+        for (i, recipe) in self.raws.recipes.iter().enumerate() {
+            if self.recipe_index.contains_key(&recipe.name) {
+                panic!("ERROR - duplicate recipe name in raws [{}]", recipe.name);
+            }
+            if !self.item_index.contains_key(&recipe.output) {
+                panic!("ERROR - recipe ({}) output references unspecified item {}", recipe.name, recipe.output);
+            }
+            if !self.prop_index.contains_key(&recipe.station) {
+                panic!("ERROR - recipe ({}) station references unspecified prop {}", recipe.name, recipe.station);
+            }
+            for ingredient in recipe.ingredients.iter() {
+                if !self.item_index.contains_key(&ingredient.name) {
+                    panic!("ERROR - recipe ({}) ingredient references unspecified item {}", recipe.name, ingredient.name);
+                }
+            }
+            self.recipe_index.insert(recipe.name.clone(), i);
+        }
 
     }
 }
@@ -339,7 +375,12 @@ macro_rules! apply_effects {
                 }
                 "damage" => $eb = $eb.with(Damage{ damage: effect.1.to_string() }),
                 "self_damage" => $eb = $eb.with(SelfDamage{ damage: effect.1.to_string() }),
-                "area_of_effect" => $eb = $eb.with(AreaOfEffect{ radius: effect.1.parse::<i32>().unwrap() }),
+                "area_of_effect" => $eb = $eb.with(parse_area_of_effect(&effect.1)),
+                "hazard" => $eb = $eb.with(parse_hazard(&effect.1)),
+                "pit" => $eb = $eb.with(Pit{}),
+                "alarm" => $eb = $eb.with(Alarm{}),
+                "random_teleport" => $eb = $eb.with(RandomTeleport{}),
+                "tunnel" => $eb = $eb.with(Tunnels{}),
                 "confusion" => {
                     $eb = $eb.with(Confusion{});
                     $eb = $eb.with(Duration{ turns: effect.1.parse::<i32>().unwrap() });
@@ -350,6 +391,8 @@ macro_rules! apply_effects {
                 }
                 "magic_mapping" => $eb = $eb.with(MagicMapping{}),
                 "town_portal" => $eb = $eb.with(TownPortal{}),
+                "identify" => $eb = $eb.with(Identify{}),
+                "remove_curse" => $eb = $eb.with(RemoveCurse{}),
                 "food" => $eb = $eb.with(Food{}),
                 "single_activation" => $eb = $eb.with(SingleActivation{}),
                 "particle_line" => $eb = $eb.with(parse_particle_line(&effect.1)),
@@ -371,8 +414,15 @@ macro_rules! apply_effects {
                     $eb = $eb.with(FrostShield{});
                     $eb = $eb.with(Duration{ turns: effect.1.parse::<i32>().unwrap() });
                 }
+                "water_breathing" => {
+                    $eb = $eb.with(WaterBreathing{});
+                    $eb = $eb.with(Duration{ turns: effect.1.parse::<i32>().unwrap() });
+                }
                 "dodge" => $eb = $eb.with(Dodge{ chance: effect.1.parse::<f32>().unwrap() }),
                 "repeat" => $eb = $eb.with(Repeat{ count: effect.1.parse::<i32>().unwrap() }),
+                "summon" => $eb = $eb.with(Summon{ mob: effect.1.to_string() }),
+                // applied to the user by the passive ability system
+                "thorns" | "mana_regen" | "requires" => {}
                 _ => rltk::console::log(format!("WARNING - Effect not implemented: {}", effect_name))
             }
         }
@@ -380,8 +430,43 @@ macro_rules! apply_effects {
 }
 
 pub fn spawn_named_item(raws: &RawMaster, ecs: &mut World, key: &str, pos: SpawnType, quality: ItemQuality) -> Option<Entity> {
+    // only randomly generated items roll affixes
+    let (prefix, suffix) = if quality == ItemQuality::Random {
+        let area_level = ecs.fetch::<Map>().area_level;
+        roll_item_affixes(raws, &raws.raws.items[raws.item_index[key]], area_level)
+    } else {
+        (None, None)
+    };
+    spawn_named_item_with_affixes(raws, ecs, key, pos, quality, prefix, suffix)
+}
+
+pub fn spawn_named_item_with_affixes(raws: &RawMaster, ecs: &mut World, key: &str, pos: SpawnType, quality: ItemQuality,
+    prefix: Option<String>, suffix: Option<String>) -> Option<Entity>
+{
     let item_template = &raws.raws.items[raws.item_index[key]];
     let item_class_colours = &raws.raws.item_class_colours;
+    let affixes: Vec<&AffixData> = [&prefix, &suffix].iter()
+        .filter_map(|affix| affix.as_ref())
+        .filter_map(|affix_name| raws.affix_index.get(affix_name))
+        .map(|affix_index| &raws.raws.affixes[*affix_index])
+        .collect();
+
+    // magic consumables stay unidentified until their type is known, magic gear until that piece is
+    let mut is_magic_item = false;
+    let mut unidentified_name: Option<String> = None;
+    if let Some(naming) = item_template.consumable.as_ref().and_then(|consumable| consumable.naming.as_ref()) {
+        is_magic_item = true;
+        let mut dungeon_master = ecs.fetch_mut::<MasterDungeonMap>();
+        if !dungeon_master.is_identified(key) {
+            unidentified_name = Some(dungeon_master.get_item_appearance(key, naming));
+        }
+    } else if (item_template.weapon.is_some() || item_template.wearable.is_some())
+        && (item_template.class != "common" || !affixes.is_empty())
+    {
+        is_magic_item = true;
+        unidentified_name = Some(format!("Unidentified {}", key));
+    }
+
     let mut eb = ecs.create_entity().marked::<SimpleMarker<SerializeMe>>();
 
     // spawn in the specified location
@@ -407,11 +492,17 @@ pub fn spawn_named_item(raws: &RawMaster, ecs: &mut World, key: &str, pos: Spawn
         }
     };
 
+    let base_value = get_item_value(&item_quality, item_template.base_value);
+    let affix_value = get_item_value(
+        &item_quality,
+        item_template.base_value + affixes.iter().map(|affix| affix.base_value).sum::<i32>()
+    ) - base_value;
     eb = eb.with(Item{
         name: item_template.name.clone(),
         initiative_penalty: item_template.initiative_penalty.unwrap_or(0.0),
         weight_lbs: item_template.weight_lbs.unwrap_or(0.0),
-        base_value: get_item_value(&item_quality, item_template.base_value),
+        base_value,
+        affix_value,
         class: {
             let class_name = item_template.class.as_str();
             match class_name {
@@ -427,14 +518,23 @@ pub fn spawn_named_item(raws: &RawMaster, ecs: &mut World, key: &str, pos: Spawn
             }
         },
         quality: item_quality.clone(),
-        vendor_category: item_template.vendor_category.clone()
+        vendor_category: item_template.vendor_category.clone(),
+        prefix: prefix.clone(),
+        suffix: suffix.clone(),
+        unidentified_name
     });
+    if is_magic_item {
+        eb = eb.with(MagicItem{});
+    }
+    if affixes.iter().any(|affix| affix.cursed.unwrap_or(false)) {
+        eb = eb.with(Cursed{ known: false });
+    }
 
     // equipment
     if let Some(weapon) = &item_template.weapon {
         eb = eb.with(Equippable{ slot: string_to_weapon_slot(&weapon.slot) });
         let (n_dice, die_type, bonus, hit_bonus) = quality_weapon_stats(&item_quality, &weapon.base_damage, weapon.hit_bonus);
-        let wpn = Weapon {
+        let mut wpn = Weapon {
             range: if weapon.range == "melee" { None } else { Some(weapon.range.parse::<i32>().expect("Not a number")) },
             attribute: match weapon.attribute.to_lowercase().as_str() {
                 "strength" => WeaponAttribute::Strength,
@@ -452,16 +552,41 @@ pub fn spawn_named_item(raws: &RawMaster, ecs: &mut World, key: &str, pos: Spawn
             proc_chance: weapon.proc_chance,
             proc_target: weapon.proc_target.clone(),
         };
-        eb = eb.with(wpn);
         if let Some(proc_effects) = &weapon.proc_effects {
             apply_effects!(self, proc_effects, eb);
         }
+        // affix procs only roll for weapons without a proc of their own
+        for affix in affixes.iter() {
+            if let Some(proc_effects) = &affix.proc_effects {
+                wpn.proc_chance = affix.proc_chance;
+                wpn.proc_target = affix.proc_target.clone();
+                apply_effects!(self, proc_effects, eb);
+            }
+        }
+        eb = eb.with(wpn);
     }
     if let Some(wearable) = &item_template.wearable {
         let slot = string_to_wearable_slot(&wearable.slot);
         eb = eb.with(Equippable{ slot });
         eb = eb.with(Wearable{ armour_class: quality_armour_class(&item_quality, wearable.armour_class) });
     }
+    if item_template.weapon.is_some() || item_template.wearable.is_some() {
+        let max_durability = item_template.durability.unwrap_or(DEFAULT_DURABILITY);
+        eb = eb.with(Durability{ current: max_durability, max: max_durability });
+    }
+    if let Some(power) = item_template.digging {
+        eb = eb.with(Digger{ power });
+    }
+    // keys are matched to their lock when placed
+    if item_template.key.unwrap_or(false) {
+        eb = eb.with(Key{ lock_id: String::new() });
+    }
+    if item_template.lockpick.unwrap_or(false) {
+        eb = eb.with(Lockpick{});
+    }
+    if item_template.water_damage.unwrap_or(false) {
+        eb = eb.with(WaterDamageable{});
+    }
 
     // consumables
     if let Some(consumable) = &item_template.consumable {
@@ -471,26 +596,64 @@ pub fn spawn_named_item(raws: &RawMaster, ecs: &mut World, key: &str, pos: Spawn
     }
 
     // skill bonuses
-    if let Some(skill_bonus) = item_skill_bonus(&item_quality, &item_template) {
+    let mut skill_bonus = item_skill_bonus(&item_quality, &item_template);
+    for affix in affixes.iter() {
+        if let Some(bonus) = &affix.skill_bonuses {
+            let affix_bonus = SkillBonus{ melee: bonus.melee, defence: bonus.defence, ranged: bonus.ranged, magic: bonus.magic };
+            if let Some(skill_bonus) = &mut skill_bonus {
+                skill_bonus.combine(Some(&affix_bonus));
+            } else {
+                skill_bonus = Some(affix_bonus);
+            }
+        }
+    }
+    if let Some(skill_bonus) = skill_bonus {
         eb = eb.with(skill_bonus);
     }
 
     // attribute bonuses
-    if let Some(bonus) = &item_template.attribute_bonuses {
-        eb = eb.with(AttributeBonus{
-            strength: bonus.strength,
-            dexterity: bonus.dexterity,
-            constitution: bonus.constitution,
-            intelligence: bonus.intelligence
-        });
+    let mut attribute_bonus = item_template.attribute_bonuses.as_ref().map(|bonus| AttributeBonus{
+        strength: bonus.strength,
+        dexterity: bonus.dexterity,
+        constitution: bonus.constitution,
+        intelligence: bonus.intelligence
+    });
+    for affix in affixes.iter() {
+        if let Some(bonus) = &affix.attribute_bonuses {
+            let affix_bonus = AttributeBonus{
+                strength: bonus.strength,
+                dexterity: bonus.dexterity,
+                constitution: bonus.constitution,
+                intelligence: bonus.intelligence
+            };
+            if let Some(attribute_bonus) = &mut attribute_bonus {
+                attribute_bonus.combine(Some(&affix_bonus));
+            } else {
+                attribute_bonus = Some(affix_bonus);
+            }
+        }
+    }
+    if let Some(attribute_bonus) = attribute_bonus {
+        eb = eb.with(attribute_bonus);
     }
 
     // regen bonuses
-    if let Some(regen_bonus) = &item_template.regen_bonuses {
-        eb = eb.with(RegenBonus{
-            health: regen_bonus.health,
-            mana: regen_bonus.mana
-        });
+    let mut regen_bonus = item_template.regen_bonuses.as_ref().map(|bonus| RegenBonus{
+        health: bonus.health,
+        mana: bonus.mana
+    });
+    for affix in affixes.iter() {
+        if let Some(bonus) = &affix.regen_bonuses {
+            if let Some(regen_bonus) = &mut regen_bonus {
+                regen_bonus.health = Some(regen_bonus.health.unwrap_or(0) + bonus.health.unwrap_or(0));
+                regen_bonus.mana = Some(regen_bonus.mana.unwrap_or(0) + bonus.mana.unwrap_or(0));
+            } else {
+                regen_bonus = Some(RegenBonus{ health: bonus.health, mana: bonus.mana });
+            }
+        }
+    }
+    if let Some(regen_bonus) = regen_bonus {
+        eb = eb.with(regen_bonus);
     }
 
     // item sets
@@ -575,6 +738,8 @@ pub fn spawn_named_mob(raws: &RawMaster, ecs: &mut World, key: &str, pos: SpawnT
         xp: 0,
         hit_points: Pool{ current: mob_hp, max: mob_hp },
         mana: Pool{ current: mob_mana, max: mob_mana },
+        rage: Pool::empty(),
+        focus: Pool::empty(),
         total_weight: 0.0,
         initiative_penalty: InitiativePenalty::initial(),
         gold:
@@ -641,7 +806,15 @@ pub fn spawn_named_mob(raws: &RawMaster, ecs: &mut World, key: &str, pos: SpawnT
     }
 
     if let Some(vendor) = &mob_template.vendor {
-        eb = eb.with(Vendor{ category: vendor.clone() });
+        eb = eb.with(Vendor{ category: vendor.clone(), hours: mob_template.vendor_hours });
+    }
+
+    if mob_template.nocturnal.unwrap_or(false) {
+        eb = eb.with(Nocturnal{});
+    }
+
+    if let Some(cost) = mob_template.hire_cost {
+        eb = eb.with(Hireable{ cost });
     }
 
     if let Some(quest_giver) = &mob_template.quest_giver {
@@ -667,6 +840,9 @@ pub fn spawn_named_mob(raws: &RawMaster, ecs: &mut World, key: &str, pos: SpawnT
     if !raws.species_index.contains_key(&species_name) {
         rltk::console::log(format!("WARNING - Unkown species: [{}]", species_name));
     }
+    if raws.species_index.get(&species_name).is_some_and(|i| raws.raws.species[*i].swims.unwrap_or(false)) {
+        eb = eb.with(Swimmer{});
+    }
     eb = eb.with(Species{ name: species_name });
 
     // bosses
@@ -674,6 +850,11 @@ pub fn spawn_named_mob(raws: &RawMaster, ecs: &mut World, key: &str, pos: SpawnT
         eb = eb.with(Boss{})
     }
 
+    // mobs that can break through doors and barricades
+    if mob_template.smashes.unwrap_or(false) {
+        eb = eb.with(Smasher{});
+    }
+
     eb = eb.with(KnownAbilities{ abilities: EntityVec::new() });
 
     let new_mob = eb.build();
@@ -689,10 +870,7 @@ pub fn spawn_named_mob(raws: &RawMaster, ecs: &mut World, key: &str, pos: SpawnT
     let mut wants_learn = ecs.write_storage::<WantsToLearnAbility>();
     if let Some(ability_list) = &mob_template.abilities {
         for ability in ability_list.iter() {
-            wants_learn.insert(
-                new_mob,
-                WantsToLearnAbility{ ability_name: ability.name.clone(), level: ability.level.unwrap_or(1) }
-            ).expect("Unable to insert");
+            WantsToLearnAbility::learn(&mut wants_learn, new_mob, &ability.name, ability.level.unwrap_or(1), false);
         }
     }
 
@@ -736,6 +914,23 @@ pub fn spawn_named_prop(raws: &RawMaster, ecs: &mut World, key: &str, pos: Spawn
         eb = eb.with(LightSource{ range: light.range, colour: RGB::from_hex(&light.colour).expect("Bad colour") });
         eb = eb.with(Viewshed{ range: light.range, dirty: true, visible_tiles: Vec::new() });
     }
+    if let Some(item) = &prop_template.gather {
+        eb = eb.with(Gatherable{ item: item.clone() });
+        eb = eb.with(SingleActivation{});
+    }
+    if let Some(crafting_station) = prop_template.crafting_station {
+        if crafting_station { eb = eb.with(CraftingStation{}) };
+    }
+    if let Some(trap) = &prop_template.trap {
+        eb = eb.with(Trap{ difficulty: trap.difficulty });
+        eb = eb.with(Hidden{});
+    }
+    if let Some(boat) = prop_template.boat {
+        if boat { eb = eb.with(Boat{}) };
+    }
+    if let Some(hit_points) = prop_template.breakable {
+        eb = eb.with(Breakable{ hit_points });
+    }
 
     Some(eb.build())
 }
@@ -766,6 +961,19 @@ pub fn spawn_named_chest(raws: &RawMaster, ecs: &mut World, key: &str, pos: Spaw
     Some(eb.build())
 }
 
+pub fn get_character_class_names(raws: &RawMaster) -> Vec<String> {
+    raws.raws.character_classes.iter().map(|character_class| character_class.name.clone()).collect()
+}
+
+pub fn get_character_class_colour(raws: &RawMaster, key: &str) -> RGB {
+    if raws.character_class_index.contains_key(key) {
+        if let Some(colour) = &raws.raws.character_classes[raws.character_class_index[key]].colour {
+            return RGB::from_hex(colour).expect("Bad colour");
+        }
+    }
+    RGB::named(rltk::YELLOW)
+}
+
 pub fn get_character_class_description(raws: &RawMaster, key: &str) -> Option<String> {
     if raws.character_class_index.contains_key(key) {
         let character_class_template = &raws.raws.character_classes[raws.character_class_index[key]];
@@ -822,6 +1030,7 @@ pub fn spawn_named_character_class(raws: &RawMaster, ecs: &mut World, key: &str)
                 passives
             }).expect("Unable to insert");
         }
+        apply_character_class_start(ecs, character_class_template);
 
         initialise_character_class(
             ecs,
@@ -835,6 +1044,156 @@ pub fn spawn_named_character_class(raws: &RawMaster, ecs: &mut World, key: &str)
     }
 }
 
+pub fn get_playable_species_names(raws: &RawMaster) -> Vec<String> {
+    raws.raws.species.iter()
+        .filter(|species| species.playable.unwrap_or(false))
+        .map(|species| species.name.clone())
+        .collect()
+}
+
+pub fn get_species_description(raws: &RawMaster, key: &str) -> Option<String> {
+    if raws.species_index.contains_key(key) {
+        let species_template = &raws.raws.species[raws.species_index[key]];
+        let mut description = species_template.description.clone().unwrap_or_default();
+        if let Some(passive) = &species_template.passive {
+            description = format!("{} - {}: {}", description, passive.name, passive.description);
+        }
+        return Some(description);
+    }
+    None
+}
+
+pub fn spawn_named_species(raws: &RawMaster, ecs: &mut World, key: &str) {
+    if !raws.species_index.contains_key(key) {
+        rltk::console::log(format!("WARNING - Unkown species: [{}]", key));
+        return;
+    }
+    let species_template = &raws.raws.species[raws.species_index[key]];
+    let player = ecs.read_resource::<Entity>();
+
+    ecs.write_storage::<Species>().insert(*player, Species{ name: species_template.name.clone() }).expect("Unable to insert");
+
+    if let Some(modifiers) = &species_template.attribute_modifiers {
+        let mut attributes = ecs.write_storage::<Attributes>();
+        let mut pools = ecs.write_storage::<Pools>();
+        if let Some(attr) = attributes.get_mut(*player) {
+            attr.strength.base += modifiers.strength.unwrap_or(0);
+            attr.dexterity.base += modifiers.dexterity.unwrap_or(0);
+            attr.constitution.base += modifiers.constitution.unwrap_or(0);
+            attr.intelligence.base += modifiers.intelligence.unwrap_or(0);
+            attr.strength.bonus = attr_bonus(attr.strength.base);
+            attr.dexterity.bonus = attr_bonus(attr.dexterity.base);
+            attr.constitution.bonus = attr_bonus(attr.constitution.base);
+            attr.intelligence.bonus = attr_bonus(attr.intelligence.base);
+
+            if let Some(player_pools) = pools.get_mut(*player) {
+                player_pools.hit_points.max = hp_at_level(attr.constitution.base, player_pools.level);
+                player_pools.hit_points.current = player_pools.hit_points.max;
+                player_pools.mana.max = mana_at_level(attr.intelligence.base, player_pools.level);
+                player_pools.mana.current = player_pools.mana.max;
+            }
+        }
+    }
+
+    if species_template.swims.unwrap_or(false) {
+        ecs.write_storage::<Swimmer>().insert(*player, Swimmer{}).expect("Unable to insert");
+    }
+
+    if let Some(passive) = &species_template.passive {
+        ecs.write_storage::<RacialPassive>().insert(*player, RacialPassive{
+            name: passive.name.clone(),
+            description: passive.description.clone(),
+            darkvision: passive.darkvision.unwrap_or(0),
+            damage_over_time_resistance: passive.damage_over_time_resistance.unwrap_or(0.0)
+        }).expect("Unable to insert");
+    }
+
+    if let Some(faction_attitudes) = &species_template.faction_attitudes {
+        let mut reactions: HashMap<String, Reaction> = HashMap::new();
+        for attitude in faction_attitudes.iter() {
+            reactions.insert(
+                attitude.0.clone(),
+                match attitude.1.as_str() {
+                    "ignore" => Reaction::Ignore,
+                    _ => Reaction::Attack
+                }
+            );
+        }
+        ecs.write_storage::<FactionAttitudes>().insert(*player, FactionAttitudes{ reactions }).expect("Unable to insert");
+    }
+    ecs.write_storage::<EquipmentChanged>().insert(*player, EquipmentChanged{}).expect("Unable to insert");
+}
+
+fn apply_character_class_start(ecs: &mut World, character_class_template: &CharacterClassData) {
+    let player = ecs.read_resource::<Entity>();
+    let mut attributes = ecs.write_storage::<Attributes>();
+    let mut skills = ecs.write_storage::<Skills>();
+    let mut pools = ecs.write_storage::<Pools>();
+    let mut factions = ecs.write_storage::<Faction>();
+
+    if let Some(starting_attributes) = &character_class_template.starting_attributes {
+        if let Some(attr) = attributes.get_mut(*player) {
+            if let Some(strength) = starting_attributes.strength {
+                attr.strength = Attribute{ base: strength, item_modifiers: 0, status_effect_modifiers: 0, bonus: attr_bonus(strength) };
+            }
+            if let Some(dexterity) = starting_attributes.dexterity {
+                attr.dexterity = Attribute{ base: dexterity, item_modifiers: 0, status_effect_modifiers: 0, bonus: attr_bonus(dexterity) };
+            }
+            if let Some(constitution) = starting_attributes.constitution {
+                attr.constitution = Attribute{ base: constitution, item_modifiers: 0, status_effect_modifiers: 0, bonus: attr_bonus(constitution) };
+            }
+            if let Some(intelligence) = starting_attributes.intelligence {
+                attr.intelligence = Attribute{ base: intelligence, item_modifiers: 0, status_effect_modifiers: 0, bonus: attr_bonus(intelligence) };
+            }
+
+            // starting pools follow the new attributes
+            if let Some(player_pools) = pools.get_mut(*player) {
+                player_pools.hit_points.max = hp_at_level(attr.constitution.base, player_pools.level);
+                player_pools.hit_points.current = player_pools.hit_points.max;
+                player_pools.mana.max = mana_at_level(attr.intelligence.base, player_pools.level);
+                player_pools.mana.current = player_pools.mana.max;
+            }
+        }
+    }
+
+    if let Some(starting_skills) = &character_class_template.starting_skills {
+        if let Some(player_skills) = skills.get_mut(*player) {
+            for sk in starting_skills.iter() {
+                match sk.0.as_str() {
+                    "melee" => { player_skills.melee.base = *sk.1; }
+                    "defence" => { player_skills.defence.base = *sk.1; }
+                    "magic" => { player_skills.magic.base = *sk.1; }
+                    "ranged" => { player_skills.ranged.base = *sk.1; }
+                    _ => { rltk::console::log(format!("Unknown skill referenced: [{}]", sk.0)); }
+                }
+            }
+        }
+    }
+
+    if let Some(gold) = &character_class_template.starting_gold {
+        if let Some(player_pools) = pools.get_mut(*player) {
+            player_pools.gold += determine_roll(gold);
+        }
+    }
+
+    if let Some(resources) = &character_class_template.resources {
+        if let Some(player_pools) = pools.get_mut(*player) {
+            for resource in resources.iter() {
+                match resource.0.as_str() {
+                    // rage is built up in combat
+                    "rage" => { player_pools.rage = Pool{ max: *resource.1, current: 0 }; }
+                    "focus" => { player_pools.focus = Pool{ max: *resource.1, current: *resource.1 }; }
+                    _ => { rltk::console::log(format!("Unknown resource referenced: [{}]", resource.0)); }
+                }
+            }
+        }
+    }
+
+    if let Some(faction) = &character_class_template.faction {
+        factions.insert(*player, Faction{ name: faction.clone() }).expect("Unable to insert");
+    }
+}
+
 fn initialise_character_class(ecs: &mut World, raws: &RawMaster, equipment: &Vec<String>, items: &Vec<String>, abilities: &Vec<String>) {
     spawner::spawn_starting_gear(ecs, raws, equipment, items);
 
@@ -842,10 +1201,7 @@ fn initialise_character_class(ecs: &mut World, raws: &RawMaster, equipment: &Vec
     let player = ecs.read_resource::<Entity>();
     let mut wants_learn = ecs.write_storage::<WantsToLearnAbility>();
     for ability_name in abilities.iter() {
-        wants_learn.insert(
-            *player,
-            WantsToLearnAbility{ ability_name: ability_name.clone(), level: 1 }
-        ).expect("Unable to insert");
+        WantsToLearnAbility::learn(&mut wants_learn, *player, ability_name, 1, false);
     }
 }
 
@@ -884,7 +1240,8 @@ pub fn store_named_quest(ecs: &mut World, quest_template: &QuestData) {
     for reward in quest_template.rewards.iter() {
         rewards.push(QuestReward {
             gold: reward.gold.clone(),
-            xp: reward.xp
+            xp: reward.xp,
+            remove_curses: reward.remove_curses.unwrap_or(false)
         });
     }
     quests.quests.push(Quest {
@@ -948,6 +1305,10 @@ pub fn store_named_ability(ecs: &mut World, ability_template: &AbilityData) -> O
     for level in &ability_template.levels {
         levels.insert(level.0.parse::<i32>().unwrap(), AbilityLevel{
             mana_cost: level.1.mana_cost,
+            rage_cost: level.1.rage_cost,
+            focus_cost: level.1.focus_cost,
+            cooldown: level.1.cooldown,
+            charges: level.1.charges,
             effects: level.1.effects.clone()
         });
     }
@@ -992,6 +1353,36 @@ pub fn get_spawn_table_for_map(raws: &RawMaster, map_name: &str) -> RandomTable
     rt
 }
 
+pub fn roll_weather_for_map(raws: &RawMaster, map_name: &str) -> Weather {
+    match raws.raws.maps.iter().find(|map| map.name == map_name) {
+        Some(map_data) => roll_weather(map_data),
+        None => Weather::Clear
+    }
+}
+
+// entries with a time only spawn during the day or at night
+pub fn spawns_at_time(raws: &RawMaster, name: &str, time_of_day: TimeOfDay) -> bool {
+    let night = matches!(time_of_day, TimeOfDay::Dusk | TimeOfDay::Night);
+    match raws.raws.spawn_table.iter().find(|entry| entry.name == name).and_then(|entry| entry.time.as_deref()) {
+        Some("day") => !night,
+        Some("night") => night,
+        _ => true
+    }
+}
+
+// traps that can appear at the given area level
+pub fn get_trap_table(raws: &RawMaster, area_level: i32) -> RandomTable {
+    let mut rt = RandomTable::new();
+    for prop in raws.raws.props.iter() {
+        if let Some(trap) = &prop.trap {
+            if trap.min_level <= area_level {
+                rt = rt.add(prop.name.clone(), 1);
+            }
+        }
+    }
+    rt
+}
+
 pub fn get_item_drop(raws: &RawMaster, table_name: &str) -> Option<String> {
     if raws.loot_index.contains_key(table_name) {
         let mut rt = RandomTable::new();
@@ -1016,6 +1407,31 @@ pub fn faction_reaction(my_faction: &str, their_faction: &str, raws: &RawMaster)
     Reaction::Ignore
 }
 
+// reaction of my_faction towards an entity, respecting that entity's own faction attitudes
+pub fn attitude_reaction(my_faction: &str, their_faction: &str, their_attitudes: Option<&FactionAttitudes>, raws: &RawMaster) -> Reaction {
+    if let Some(attitudes) = their_attitudes {
+        if let Some(reaction) = attitudes.reactions.get(my_faction) {
+            return *reaction;
+        }
+    }
+    faction_reaction(my_faction, their_faction, raws)
+}
+
+pub fn get_item_template(key: &str, raws: &RawMaster) -> Option<ItemData> {
+    raws.item_index.get(key).map(|index| raws.raws.items[*index].clone())
+}
+
+pub fn get_recipe(key: &str, raws: &RawMaster) -> Option<RecipeData> {
+    raws.recipe_index.get(key).map(|index| raws.raws.recipes[*index].clone())
+}
+
+pub fn get_station_recipes(station: &str, raws: &RawMaster) -> Vec<RecipeData> {
+    raws.raws.recipes.iter()
+        .filter(|recipe| recipe.station == station)
+        .cloned()
+        .collect()
+}
+
 pub fn get_vendor_items(category: &String, raws: &RawMaster) -> Vec<ItemData> {
     let mut result: Vec<ItemData> = Vec::new();
     for item in raws.raws.items.iter() {
@@ -1054,6 +1470,39 @@ pub fn parse_particle_line(token_string: &str) -> SpawnParticleLine {
     }
 }
 
+// either a plain radius for a circle or shape:radius, e.g. cone:6
+pub fn parse_area_of_effect(token_string: &str) -> AreaOfEffect {
+    let (shape, radius) = match token_string.split_once(':') {
+        Some((shape, radius)) => (shape, radius),
+        None => ("circle", token_string)
+    };
+    let shape = match shape {
+        "cone" => AoeShape::Cone,
+        "line" => AoeShape::Line,
+        "ring" => AoeShape::Ring,
+        "cross" => AoeShape::Cross,
+        "circle" => AoeShape::Circle,
+        _ => {
+            rltk::console::log(format!("WARNING - Unknown area of effect shape: {}", shape));
+            AoeShape::Circle
+        }
+    };
+    AreaOfEffect{ radius: radius.parse::<i32>().unwrap(), shape }
+}
+
+// hazard type and how many turns it lasts, e.g. fire:5
+pub fn parse_hazard(token_string: &str) -> CreatesHazard {
+    let (hazard, turns) = token_string.split_once(':').unwrap_or((token_string, "5"));
+    let hazard_type = match parse_hazard_type(hazard) {
+        Some(hazard_type) => hazard_type,
+        None => {
+            rltk::console::log(format!("WARNING - Unknown hazard type: {}", hazard));
+            HazardType::Fire
+        }
+    };
+    CreatesHazard{ hazard_type, turns: turns.parse::<i32>().unwrap() }
+}
+
 pub fn parse_particle(token_string: &str) -> SpawnParticleBurst {
     let tokens: Vec<_> = token_string.split(';').collect();
     SpawnParticleBurst {
@@ -1075,6 +1524,59 @@ fn roll_item_quality(item_class: &str, is_consumable: bool) -> ItemQuality {
     }
 }
 
+fn roll_item_affixes(raws: &RawMaster, item_template: &ItemData, area_level: i32) -> (Option<String>, Option<String>) {
+    if item_template.consumable.is_some() { return (None, None); }
+    if item_template.weapon.is_none() && item_template.wearable.is_none() { return (None, None); }
+
+    // common items only roll an affix when they are cursed
+    let cursed_only = item_template.class == "common";
+    let (mut roll_prefix, mut roll_suffix) = match item_template.class.as_str() {
+        "rare" => if rng::roll_dice(1, 2) == 1 { (true, false) } else { (false, true) },
+        "legendary" => (true, true),
+        "common" if rng::roll_dice(1, 10) == 1 => if rng::roll_dice(1, 2) == 1 { (true, false) } else { (false, true) },
+        _ => (false, false)
+    };
+
+    let mut prefix: Option<String> = None;
+    let mut suffix: Option<String> = None;
+    // weapons can only have a single proc effect
+    let mut has_proc = item_template.weapon.as_ref().is_some_and(|weapon| weapon.proc_effects.is_some());
+    while roll_prefix || roll_suffix {
+        let affix_type = if roll_prefix { "prefix" } else { "suffix" };
+        let mut rt = RandomTable::new();
+        for affix in raws.raws.affixes.iter() {
+            if affix.affix_type != affix_type { continue; }
+            if cursed_only && !affix.cursed.unwrap_or(false) { continue; }
+            if area_level < affix.min_level || area_level > affix.max_level { continue; }
+            if affix.proc_effects.is_some() && (has_proc || item_template.weapon.is_none()) { continue; }
+            let valid_item_type = match affix.item_type.as_str() {
+                "weapon" => item_template.weapon.is_some(),
+                "melee" => item_template.weapon.as_ref().is_some_and(|weapon| weapon.range == "melee"),
+                "ranged" => item_template.weapon.as_ref().is_some_and(|weapon| weapon.range != "melee"),
+                "wearable" => item_template.wearable.is_some(),
+                _ => true
+            };
+            if valid_item_type {
+                rt = rt.add(affix.name.clone(), affix.weight);
+            }
+        }
+
+        let roll = rt.roll();
+        if let Some(affix_name) = &roll {
+            has_proc = has_proc || raws.raws.affixes[raws.affix_index[affix_name]].proc_effects.is_some();
+        }
+        if roll_prefix {
+            prefix = roll;
+            roll_prefix = false;
+        } else {
+            suffix = roll;
+            roll_suffix = false;
+        }
+    }
+
+    (prefix, suffix)
+}
+
 fn quality_weapon_stats(quality: &ItemQuality, base_damage: &str, base_hit_bonus: i32) -> (i32, i32, i32, i32) {
     let (n_dice, mut die_type, mut die_bonus) = parse_dice_string(base_damage);
     let mut hit_bonus = base_hit_bonus;
@@ -1112,6 +1614,50 @@ fn quality_armour_class(quality: &ItemQuality, base_armour_class: f32) -> f32 {
     }
 }
 
+pub fn effective_item_quality(quality: &ItemQuality, durability: &Durability) -> ItemQuality {
+    // low durability drops the item down quality tiers
+    let tiers_lost = if durability.current * 4 <= durability.max {
+        2
+    } else if durability.current * 2 <= durability.max {
+        1
+    } else {
+        0
+    };
+
+    let mut effective_quality = quality.clone();
+    for _ in 0..tiers_lost {
+        effective_quality = match effective_quality {
+            ItemQuality::Exceptional => ItemQuality::Improved,
+            ItemQuality::Improved => ItemQuality::Standard,
+            ItemQuality::Standard => ItemQuality::Worn,
+            _ => ItemQuality::Damaged
+        };
+    }
+    effective_quality
+}
+
+pub fn refresh_item_condition(raws: &RawMaster, ecs: &World, item_entity: Entity) {
+    let items = ecs.read_storage::<Item>();
+    let durabilities = ecs.read_storage::<Durability>();
+    let (item, durability) = match (items.get(item_entity), durabilities.get(item_entity)) {
+        (Some(item), Some(durability)) => (item, durability),
+        _ => return
+    };
+    let item_template = &raws.raws.items[raws.item_index[&item.name]];
+    let quality = effective_item_quality(&item.quality, durability);
+
+    if let (Some(weapon_template), Some(weapon)) = (&item_template.weapon, ecs.write_storage::<Weapon>().get_mut(item_entity)) {
+        let (n_dice, die_type, bonus, hit_bonus) = quality_weapon_stats(&quality, &weapon_template.base_damage, weapon_template.hit_bonus);
+        weapon.damage_n_dice = n_dice;
+        weapon.damage_die_type = die_type;
+        weapon.damage_bonus = bonus;
+        weapon.hit_bonus = hit_bonus;
+    }
+    if let (Some(wearable_template), Some(wearable)) = (&item_template.wearable, ecs.write_storage::<Wearable>().get_mut(item_entity)) {
+        wearable.armour_class = quality_armour_class(&quality, wearable_template.armour_class);
+    }
+}
+
 pub fn get_item_value(quality: &ItemQuality, base_value: i32) -> i32 {
     let mut value = base_value as f32;
     match quality {
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RecipeData {
+    pub name: String,
+    pub station: String,
+    pub ingredients: Vec<RecipeIngredientData>,
+    pub output: String,
+    pub skill: Option<String>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RecipeIngredientData {
+    pub name: String,
+    pub count: i32
+}
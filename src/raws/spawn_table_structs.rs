@@ -5,5 +5,6 @@ use serde::Deserialize;
 #[derive(Deserialize, Debug)]
 pub struct SpawnTableEntry {
     pub name: String,
-    pub weights: HashMap<String, i32>
+    pub weights: HashMap<String, i32>,
+    pub time: Option<String>
 }
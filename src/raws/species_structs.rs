@@ -1,6 +1,22 @@
 use serde::Deserialize;
+use std::collections::HashMap;
+use super::AttributeBonusData;
 
 #[derive(Deserialize, Debug)]
 pub struct SpeciesData {
-  pub name: String
+  pub name: String,
+  pub playable: Option<bool>,
+  pub description: Option<String>,
+  pub attribute_modifiers: Option<AttributeBonusData>,
+  pub passive: Option<SpeciesPassiveData>,
+  pub swims: Option<bool>,
+  pub faction_attitudes: Option<HashMap<String, String>>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SpeciesPassiveData {
+  pub name: String,
+  pub description: String,
+  pub darkvision: Option<i32>,
+  pub damage_over_time_resistance: Option<f32>
 }
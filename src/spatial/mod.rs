@@ -57,6 +57,17 @@ pub fn is_blocked(idx: usize) -> bool {
     lock.blocked[idx].0 || lock.blocked[idx].1
 }
 
+pub fn is_blocked_by_entity(idx: usize) -> bool {
+    let lock = SPATIAL_MAP.lock().unwrap();
+    lock.blocked[idx].1
+}
+
+// whether a tile is blocked by anything other than the given entity
+pub fn is_blocked_by_other(idx: usize, entity: Entity) -> bool {
+    let lock = SPATIAL_MAP.lock().unwrap();
+    lock.blocked[idx].1 && lock.tile_content[idx].iter().any(|(e, blocks)| *blocks && *e != entity)
+}
+
 pub fn set_blocked(idx: usize, blocked: bool) {
     let mut lock = SPATIAL_MAP.lock().unwrap();
     lock.blocked[idx] = (lock.blocked[idx].0, blocked);
@@ -85,25 +96,33 @@ pub fn get_tile_content_clone(idx: usize) -> Vec<Entity> {
 }
 
 pub fn move_entity(entity: Entity, moving_from: usize, moving_to: usize) {
+    move_footprint(entity, &[moving_from], &[moving_to]);
+}
+
+// large entities are indexed on every tile they cover
+pub fn move_footprint(entity: Entity, moving_from: &[usize], moving_to: &[usize]) {
     let mut lock = SPATIAL_MAP.lock().unwrap();
     let mut entity_blocks = false;
-    lock.tile_content[moving_from].retain(|(e, blocks)| {
-        if *e == entity {
-            entity_blocks = *blocks;
-            false
-        } else {
-            true
-        }
-    });
-    lock.tile_content[moving_to].push((entity, entity_blocks));
+    for idx in moving_from.iter() {
+        lock.tile_content[*idx].retain(|(e, blocks)| {
+            if *e == entity {
+                entity_blocks = *blocks;
+                false
+            } else {
+                true
+            }
+        });
+    }
+    for idx in moving_to.iter() {
+        lock.tile_content[*idx].push((entity, entity_blocks));
+    }
 
-    // recalculate blocks for both tiles
-    let mut from_blocked = false;
-    let mut to_blocked = false;
-    lock.tile_content[moving_from].iter().for_each(|(_, blocks)| if *blocks { from_blocked = true; } );
-    lock.tile_content[moving_to].iter().for_each(|(_, blocks)| if *blocks { to_blocked = true; } );
-    lock.blocked[moving_from].1 = from_blocked;
-    lock.blocked[moving_to].1 = to_blocked;
+    // recalculate blocks for all affected tiles
+    for idx in moving_from.iter().chain(moving_to.iter()) {
+        let mut blocked = false;
+        lock.tile_content[*idx].iter().for_each(|(_, blocks)| if *blocks { blocked = true; } );
+        lock.blocked[*idx].1 = blocked;
+    }
 }
 
 pub fn remove_entity(entity: Entity, idx: usize) {
@@ -1,10 +1,11 @@
 use specs::prelude::*;
 use crate::raws;
-use crate::raws::{find_ability_by_name, parse_particle, parse_particle_line, parse_ranged_string};
+use crate::raws::{find_ability_by_name, parse_area_of_effect, parse_hazard, parse_particle, parse_particle_line, parse_ranged_string};
 use crate::{apply_effects, Ability, AbilityType, AreaOfEffect, Block, Confusion, Damage, DamageOverTime, Dodge, Duration, Food, Fortress, 
     FrostShield, Healing, KnownAbilities, KnownAbility, MagicMapping, Rage, Ranged, RestoresMana, RunState, SelfDamage, SingleActivation, 
     Slow, SpawnParticleBurst, SpawnParticleLine, Stun, TeachesAbility, TownPortal, WantsToLearnAbility, WantsToLevelAbility, Repeat,
-    WantsToRepeatAbility, MyTurn};
+    WantsToRepeatAbility, MyTurn, Identify, RemoveCurse, PassiveBonus, Equipped, EquipmentSlot, Wearable, Weapon,
+    Summon, CreatesHazard, Pit, Alarm, RandomTeleport, WaterBreathing, Tunnels};
 use crate::effects::add_effect;
 
 pub struct LearnAbilitySystem {}
@@ -16,14 +17,12 @@ impl<'a> System<'a> for LearnAbilitySystem {
         ReadStorage<'a, Ability>,
         WriteStorage<'a, KnownAbilities>,
         WriteStorage<'a, WantsToLearnAbility>,
-        ReadExpect<'a, RunState>,
-        WriteStorage<'a, Dodge>,
-        WriteStorage<'a, Block>
+        ReadExpect<'a, RunState>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (entities, lazy, abilities, mut known_ability_lists, 
-            mut wants_learn, runstate, mut dodges, mut blocks) = data;
+            mut wants_learn, runstate) = data;
 
         if wants_learn.count() < 1 { return; }
         if *runstate != RunState::Ticking {
@@ -33,30 +32,23 @@ impl<'a> System<'a> for LearnAbilitySystem {
             }
         }
 
-        for (entity, learn) in (&entities, &wants_learn).join() {
-            let ability = find_ability_by_name(&learn.ability_name, &abilities, &entities).unwrap();
-            let effects = &ability.levels[&learn.level].effects;
-
-            let mut lb = lazy.create_entity(&entities);
-            apply_effects!(raws, effects, lb);
-
-            let known_ability_list = &mut known_ability_lists.get_mut(entity).unwrap().abilities;
-            let known_ability_entity = lb.with(KnownAbility{
-                name: ability.name.clone(),
-                level: learn.level,
-                mana_cost: ability.levels[&learn.level].mana_cost.unwrap_or(0),
-                ability_type: ability.ability_type.clone()
-            }).build();
-            known_ability_list.push(known_ability_entity);
-
-            if ability.ability_type == AbilityType::Passive {
-                // apply passive effects of abilities to the user
-                if let Some(dodge_chance) = effects.get("dodge") {
-                    dodges.insert(entity, Dodge{ chance: dodge_chance.parse::<f32>().unwrap() }).expect("Unable to insert");
-                }
-                if let Some(block_chance) = effects.get("block") {
-                    blocks.insert(entity, Block{ chance: block_chance.parse::<f32>().unwrap() }).expect("Unable to insert");
-                }
+        for (entity, wants) in (&entities, &wants_learn).join() {
+            for learn in wants.abilities.iter() {
+                let ability = find_ability_by_name(&learn.ability_name, &abilities, &entities).unwrap();
+                let effects = &ability.levels[&learn.level].effects;
+
+                let mut lb = lazy.create_entity(&entities);
+                apply_effects!(raws, effects, lb);
+
+                let known_ability_list = &mut known_ability_lists.get_mut(entity).unwrap().abilities;
+                let known_ability_entity = lb.with(KnownAbility::new(
+                    &ability.name,
+                    ability.ability_type.clone(),
+                    learn.level,
+                    &ability.levels[&learn.level],
+                    learn.from_passive
+                )).build();
+                known_ability_list.push(known_ability_entity);
             }
         }
 
@@ -92,240 +84,236 @@ impl<'a> System<'a> for LevelAbilitySystem {
         WriteStorage<'a, Repeat>,
         WriteStorage<'a, SpawnParticleLine>,
         WriteStorage<'a, SpawnParticleBurst>,
-        ReadExpect<'a, RunState>
+        ReadExpect<'a, RunState>,
+        WriteStorage<'a, Summon>,
+        WriteStorage<'a, CreatesHazard>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (entities, abilities, mut known_ability_lists, mut known_abilities, mut wants_level,
             mut ranged, mut damage, mut self_damage, mut aoe, mut confusion, mut duration,
             mut stun, mut dot, mut rage, mut blocks, mut fortress, mut frost_shield, mut dodges,
-            mut healing, mut slow, mut repeat, mut particle_line, mut particle_burst, runstate) = data;
+            mut healing, mut slow, mut repeat, mut particle_line, mut particle_burst, runstate,
+            mut summons, mut hazards) = data;
 
         if wants_level.count() < 1 { return; }
         if *runstate != RunState::Ticking { return; }
 
         for (entity, want_level) in (&entities, &wants_level).join() {
-            let ability = find_ability_by_name(&want_level.ability_name, &abilities, &entities).unwrap();
-
-            let entity_known_ability_list = &known_ability_lists.get_mut(entity).unwrap().abilities;
-            for ability_entity in entity_known_ability_list.iter() {
-                let known_ability = known_abilities.get_mut(*ability_entity).unwrap();
-                if known_ability.name != ability.name { continue; }
-
-                known_ability.level += 1;
-                known_ability.mana_cost = ability.levels[&known_ability.level].mana_cost.unwrap_or(0);
-
-                // update current known ability with effects from the next ability level
-                let new_effects = &ability.levels.get(&known_ability.level).unwrap().effects;
-
-                // Ranged
-                if let Some(new_range_string) = new_effects.get("ranged") {
-                    let (new_min, new_max) = parse_ranged_string(new_range_string.clone());
-                    if let Some(current_ranged) = ranged.get_mut(*ability_entity) {
-                        current_ranged.min_range = new_min;
-                        current_ranged.max_range = new_max;
-                    } else {
-                        ranged.insert(*ability_entity, Ranged{ min_range: new_min, max_range: new_max }).expect("Unable to insert");
+            for ability_name in want_level.ability_names.iter() {
+                let ability = find_ability_by_name(ability_name, &abilities, &entities).unwrap();
+
+                let entity_known_ability_list = &known_ability_lists.get_mut(entity).unwrap().abilities;
+                for ability_entity in entity_known_ability_list.iter() {
+                    let known_ability = known_abilities.get_mut(*ability_entity).unwrap();
+                    if known_ability.name != ability.name { continue; }
+
+                    let next_level = known_ability.level + 1;
+                    known_ability.set_level(next_level, &ability.levels[&next_level]);
+
+                    // update current known ability with effects from the next ability level
+                    let new_effects = &ability.levels.get(&known_ability.level).unwrap().effects;
+
+                    // Ranged
+                    if let Some(new_range_string) = new_effects.get("ranged") {
+                        let (new_min, new_max) = parse_ranged_string(new_range_string.clone());
+                        if let Some(current_ranged) = ranged.get_mut(*ability_entity) {
+                            current_ranged.min_range = new_min;
+                            current_ranged.max_range = new_max;
+                        } else {
+                            ranged.insert(*ability_entity, Ranged{ min_range: new_min, max_range: new_max }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Damage
-                if let Some(new_damage_string) = new_effects.get("damage") {
-                    if let Some(current_damage) = damage.get_mut(*ability_entity) {
-                        current_damage.damage = new_damage_string.clone();
-                    } else {
-                        damage.insert(*ability_entity, Damage{ damage: new_damage_string.clone() }).expect("Unable to insert");
+                    // Damage
+                    if let Some(new_damage_string) = new_effects.get("damage") {
+                        if let Some(current_damage) = damage.get_mut(*ability_entity) {
+                            current_damage.damage = new_damage_string.clone();
+                        } else {
+                            damage.insert(*ability_entity, Damage{ damage: new_damage_string.clone() }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Self Damage
-                if let Some(new_self_damage) = new_effects.get("self_damage") {
-                    if let Some(current_self_damage) = self_damage.get_mut(*ability_entity) {
-                        current_self_damage.damage = new_self_damage.clone();
-                    } else {
-                        self_damage.insert(*ability_entity, SelfDamage{ damage: new_self_damage.clone() }).expect("Unable to insert");
+                    // Self Damage
+                    if let Some(new_self_damage) = new_effects.get("self_damage") {
+                        if let Some(current_self_damage) = self_damage.get_mut(*ability_entity) {
+                            current_self_damage.damage = new_self_damage.clone();
+                        } else {
+                            self_damage.insert(*ability_entity, SelfDamage{ damage: new_self_damage.clone() }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Area of Effect
-                if let Some(new_aoe_string) = new_effects.get("area_of_effect") {
-                    let new_radius = new_aoe_string.parse::<i32>().unwrap();
-                    if let Some(current_aoe) = aoe.get_mut(*ability_entity) {
-                        current_aoe.radius = new_radius;
-                    } else {
-                        aoe.insert(*ability_entity, AreaOfEffect{ radius: new_radius }).expect("Unable to insert");
+                    // Area of Effect
+                    if let Some(new_aoe_string) = new_effects.get("area_of_effect") {
+                        aoe.insert(*ability_entity, parse_area_of_effect(new_aoe_string)).expect("Unable to insert");
                     }
-                }
 
-                // Confusion
-                if let Some(new_confusion_string) = new_effects.get("confusion") {
-                    let new_duration = new_confusion_string.parse::<i32>().unwrap();
-                    if confusion.get(*ability_entity).is_some() {
-                        if let Some(current_duration) = duration.get_mut(*ability_entity) {
-                            current_duration.turns = new_duration;
+                    // Confusion
+                    if let Some(new_confusion_string) = new_effects.get("confusion") {
+                        let new_duration = new_confusion_string.parse::<i32>().unwrap();
+                        if confusion.get(*ability_entity).is_some() {
+                            if let Some(current_duration) = duration.get_mut(*ability_entity) {
+                                current_duration.turns = new_duration;
+                            } else {
+                                duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
+                            }
                         } else {
+                            confusion.insert(*ability_entity, Confusion{}).expect("Unable to insert");
                             duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                         }
-                    } else {
-                        confusion.insert(*ability_entity, Confusion{}).expect("Unable to insert");
-                        duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                     }
-                }
 
-                // Stun
-                if let Some(new_stun_string) = new_effects.get("stun") {
-                    let new_duration = new_stun_string.parse::<i32>().unwrap();
-                    if stun.get(*ability_entity).is_some() {
-                        if let Some(current_duration) = duration.get_mut(*ability_entity) {
-                            current_duration.turns = new_duration;
+                    // Stun
+                    if let Some(new_stun_string) = new_effects.get("stun") {
+                        let new_duration = new_stun_string.parse::<i32>().unwrap();
+                        if stun.get(*ability_entity).is_some() {
+                            if let Some(current_duration) = duration.get_mut(*ability_entity) {
+                                current_duration.turns = new_duration;
+                            } else {
+                                duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
+                            }
                         } else {
+                            stun.insert(*ability_entity, Stun{}).expect("Unable to insert");
                             duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                         }
-                    } else {
-                        stun.insert(*ability_entity, Stun{}).expect("Unable to insert");
-                        duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                     }
-                }
 
-                // Duration
-                if let Some(new_duration_string) = new_effects.get("duration") {
-                    let new_duration = new_duration_string.parse::<i32>().unwrap();
-                    if let Some(current_duration) = duration.get_mut(*ability_entity) {
-                        current_duration.turns = new_duration;
-                    } else {
-                        duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
+                    // Duration
+                    if let Some(new_duration_string) = new_effects.get("duration") {
+                        let new_duration = new_duration_string.parse::<i32>().unwrap();
+                        if let Some(current_duration) = duration.get_mut(*ability_entity) {
+                            current_duration.turns = new_duration;
+                        } else {
+                            duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Damage Over Time
-                if let Some(new_dot_string) = new_effects.get("damage_over_time") {
-                    let new_dot = new_dot_string.parse::<i32>().unwrap();
-                    if let Some(current_dot) = dot.get_mut(*ability_entity) {
-                        current_dot.damage = new_dot;
-                    } else {
-                        dot.insert(*ability_entity, DamageOverTime{ damage: new_dot }).expect("Unable to insert");
+                    // Damage Over Time
+                    if let Some(new_dot_string) = new_effects.get("damage_over_time") {
+                        let new_dot = new_dot_string.parse::<i32>().unwrap();
+                        if let Some(current_dot) = dot.get_mut(*ability_entity) {
+                            current_dot.damage = new_dot;
+                        } else {
+                            dot.insert(*ability_entity, DamageOverTime{ damage: new_dot }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Rage
-                if let Some(new_rage_string) = new_effects.get("rage") {
-                    let new_duration = new_rage_string.parse::<i32>().unwrap();
-                    if rage.get(*ability_entity).is_some() {
-                        if let Some(current_duration) = duration.get_mut(*ability_entity) {
-                            current_duration.turns = new_duration;
+                    // Rage
+                    if let Some(new_rage_string) = new_effects.get("rage") {
+                        let new_duration = new_rage_string.parse::<i32>().unwrap();
+                        if rage.get(*ability_entity).is_some() {
+                            if let Some(current_duration) = duration.get_mut(*ability_entity) {
+                                current_duration.turns = new_duration;
+                            } else {
+                                duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
+                            }
                         } else {
+                            rage.insert(*ability_entity, Rage{}).expect("Unable to insert");
                             duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                         }
-                    } else {
-                        rage.insert(*ability_entity, Rage{}).expect("Unable to insert");
-                        duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                     }
-                }
 
-                // Block
-                if let Some(new_block_string) = new_effects.get("block") {
-                    let new_chance = new_block_string.parse::<f32>().unwrap();
-                    // ability
-                    if let Some(ability_block) = blocks.get_mut(*ability_entity) {
-                        ability_block.chance = new_chance;
-                    } else {
-                        blocks.insert(*ability_entity, Block{ chance: new_chance }).expect("Unable to insert");
-                    }
-                    // user
-                    if let Some(user_block) = blocks.get_mut(entity) {
-                        user_block.chance = new_chance
-                    } else {
-                        blocks.insert(entity, Block{ chance: new_chance }).expect("Unable to insert");
+                    // Block
+                    if let Some(new_block_string) = new_effects.get("block") {
+                        let new_chance = new_block_string.parse::<f32>().unwrap();
+                        if let Some(ability_block) = blocks.get_mut(*ability_entity) {
+                            ability_block.chance = new_chance;
+                        } else {
+                            blocks.insert(*ability_entity, Block{ chance: new_chance }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Fortress
-                if let Some(new_fortress_string) = new_effects.get("fortress") {
-                    let new_duration = new_fortress_string.parse::<i32>().unwrap();
-                    if fortress.get(*ability_entity).is_some() {
-                        if let Some(current_duration) = duration.get_mut(*ability_entity) {
-                            current_duration.turns = new_duration;
+                    // Fortress
+                    if let Some(new_fortress_string) = new_effects.get("fortress") {
+                        let new_duration = new_fortress_string.parse::<i32>().unwrap();
+                        if fortress.get(*ability_entity).is_some() {
+                            if let Some(current_duration) = duration.get_mut(*ability_entity) {
+                                current_duration.turns = new_duration;
+                            } else {
+                                duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
+                            }
                         } else {
+                            fortress.insert(*ability_entity, Fortress{}).expect("Unable to insert");
                             duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                         }
-                    } else {
-                        fortress.insert(*ability_entity, Fortress{}).expect("Unable to insert");
-                        duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                     }
-                }
 
-                // Frost Shield
-                if let Some(new_frost_shield_string) = new_effects.get("frost_shield") {
-                    let new_duration = new_frost_shield_string.parse::<i32>().unwrap();
-                    if frost_shield.get(*ability_entity).is_some() {
-                        if let Some(current_duration) = duration.get_mut(*ability_entity) {
-                            current_duration.turns = new_duration;
+                    // Frost Shield
+                    if let Some(new_frost_shield_string) = new_effects.get("frost_shield") {
+                        let new_duration = new_frost_shield_string.parse::<i32>().unwrap();
+                        if frost_shield.get(*ability_entity).is_some() {
+                            if let Some(current_duration) = duration.get_mut(*ability_entity) {
+                                current_duration.turns = new_duration;
+                            } else {
+                                duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
+                            }
                         } else {
+                            frost_shield.insert(*ability_entity, FrostShield{}).expect("Unable to insert");
                             duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                         }
-                    } else {
-                        frost_shield.insert(*ability_entity, FrostShield{}).expect("Unable to insert");
-                        duration.insert(*ability_entity, Duration{ turns: new_duration }).expect("Unable to insert");
                     }
-                }
 
-                // Dodge
-                if let Some(new_dodge_string) = new_effects.get("dodge") {
-                    let new_chance = new_dodge_string.parse::<f32>().unwrap();
-                    // ability
-                    if let Some(ability_dodge) = dodges.get_mut(*ability_entity) {
-                        ability_dodge.chance = new_chance;
-                    } else {
-                        dodges.insert(*ability_entity, Dodge{ chance: new_chance }).expect("Unable to insert");
+                    // Dodge
+                    if let Some(new_dodge_string) = new_effects.get("dodge") {
+                        let new_chance = new_dodge_string.parse::<f32>().unwrap();
+                        if let Some(ability_dodge) = dodges.get_mut(*ability_entity) {
+                            ability_dodge.chance = new_chance;
+                        } else {
+                            dodges.insert(*ability_entity, Dodge{ chance: new_chance }).expect("Unable to insert");
+                        }
+                    }
+
+                    // Healing
+                    if let Some(new_healing_string) = new_effects.get("healing") {
+                        let new_healing = new_healing_string.parse::<i32>().unwrap();
+                        if let Some(current_healing) = healing.get_mut(*ability_entity) {
+                            current_healing.heal_amount = new_healing;
+                        } else {
+                            healing.insert(*ability_entity, Healing{ heal_amount: new_healing }).expect("Unable to insert");
+                        }
                     }
-                    // user
-                    if let Some(user_dodge) = dodges.get_mut(entity) {
-                        user_dodge.chance = new_chance
-                    } else {
-                        dodges.insert(entity, Dodge{ chance: new_chance }).expect("Unable to insert");
+
+                    // Slow
+                    if let Some(new_slow_string) = new_effects.get("slow") {
+                        let new_initiative_penalty = new_slow_string.parse::<f32>().unwrap();
+                        if let Some(current_slow) = slow.get_mut(*ability_entity) {
+                            current_slow.initiative_penalty = new_initiative_penalty;
+                        } else {
+                            slow.insert(*ability_entity, Slow{ initiative_penalty: new_initiative_penalty }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Healing
-                if let Some(new_healing_string) = new_effects.get("healing") {
-                    let new_healing = new_healing_string.parse::<i32>().unwrap();
-                    if let Some(current_healing) = healing.get_mut(*ability_entity) {
-                        current_healing.heal_amount = new_healing;
-                    } else {
-                        healing.insert(*ability_entity, Healing{ heal_amount: new_healing }).expect("Unable to insert");
+                    // Repeat
+                    if let Some(new_repeat_string) = new_effects.get("repeat") {
+                        let new_repeat_count = new_repeat_string.parse::<i32>().unwrap();
+                        if let Some(current_repeat) = repeat.get_mut(*ability_entity) {
+                            current_repeat.count = new_repeat_count;
+                        } else {
+                            repeat.insert(*ability_entity, Repeat{ count: new_repeat_count }).expect("Unable to insert");
+                        }
                     }
-                }
 
-                // Slow
-                if let Some(new_slow_string) = new_effects.get("slow") {
-                    let new_initiative_penalty = new_slow_string.parse::<f32>().unwrap();
-                    if let Some(current_slow) = slow.get_mut(*ability_entity) {
-                        current_slow.initiative_penalty = new_initiative_penalty;
-                    } else {
-                        slow.insert(*ability_entity, Slow{ initiative_penalty: new_initiative_penalty }).expect("Unable to insert");
+                    // Summon
+                    if let Some(new_mob) = new_effects.get("summon") {
+                        summons.insert(*ability_entity, Summon{ mob: new_mob.clone() }).expect("Unable to insert");
                     }
-                }
 
-                // Repeat
-                if let Some(new_repeat_string) = new_effects.get("repeat") {
-                    let new_repeat_count = new_repeat_string.parse::<i32>().unwrap();
-                    if let Some(current_repeat) = repeat.get_mut(*ability_entity) {
-                        current_repeat.count = new_repeat_count;
-                    } else {
-                        repeat.insert(*ability_entity, Repeat{ count: new_repeat_count }).expect("Unable to insert");
+                    // Hazard
+                    if let Some(new_hazard) = new_effects.get("hazard") {
+                        hazards.insert(*ability_entity, parse_hazard(new_hazard)).expect("Unable to insert");
                     }
-                }
 
-                // Particle Line
-                if let Some(new_particle_string) = new_effects.get("particle_line") {
-                    let new_particle = raws::parse_particle_line(new_particle_string);
-                    particle_line.insert(*ability_entity, new_particle).expect("Unable to insert");
-                }
+                    // Particle Line
+                    if let Some(new_particle_string) = new_effects.get("particle_line") {
+                        let new_particle = raws::parse_particle_line(new_particle_string);
+                        particle_line.insert(*ability_entity, new_particle).expect("Unable to insert");
+                    }
 
-                // Particle Burst
-                if let Some(new_particle_string) = new_effects.get("particle") {
-                    let new_particle = raws::parse_particle(new_particle_string);
-                    particle_burst.insert(*ability_entity, new_particle).expect("Unable to insert");
+                    // Particle Burst
+                    if let Some(new_particle_string) = new_effects.get("particle") {
+                        let new_particle = raws::parse_particle(new_particle_string);
+                        particle_burst.insert(*ability_entity, new_particle).expect("Unable to insert");
+                    }
                 }
             }
         }
@@ -370,3 +358,100 @@ impl<'a> System<'a> for RepeatAbilitySystem {
         }
     }
 }
+
+pub struct PassiveAbilitySystem {}
+
+impl<'a> System<'a> for PassiveAbilitySystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadStorage<'a, Ability>,
+        ReadStorage<'a, KnownAbilities>,
+        ReadStorage<'a, KnownAbility>,
+        WriteStorage<'a, PassiveBonus>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, Wearable>,
+        ReadStorage<'a, Weapon>,
+        ReadExpect<'a, RunState>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, abilities, known_ability_lists, known_abilities, mut passive_bonuses,
+            equipped, wearables, weapons, runstate) = data;
+
+        if *runstate != RunState::Ticking { return; }
+
+        for (entity, known_ability_list) in (&entities, &known_ability_lists).join() {
+            let mut bonus = PassiveBonus::default();
+
+            for ability_entity in known_ability_list.abilities.iter() {
+                // newly learned abilities are built lazily
+                let known_ability = match known_abilities.get(*ability_entity) {
+                    Some(known_ability) => known_ability,
+                    None => continue
+                };
+                if known_ability.ability_type != AbilityType::Passive { continue; }
+
+                let ability = match find_ability_by_name(&known_ability.name, &abilities, &entities) {
+                    Some(ability) => ability,
+                    None => {
+                        rltk::console::log(format!("WARNING - unknown passive ability {}", known_ability.name));
+                        continue;
+                    }
+                };
+                let effects = match ability.levels.get(&known_ability.level) {
+                    Some(level) => &level.effects,
+                    None => {
+                        rltk::console::log(format!("WARNING - {} has no level {}", known_ability.name, known_ability.level));
+                        continue;
+                    }
+                };
+
+                // conditional passives only apply with the right gear equipped
+                if let Some(requirement) = effects.get("requires") {
+                    let met = (&equipped, (&wearables).maybe(), (&weapons).maybe()).join()
+                        .filter(|(equip, _, _)| equip.owner == entity)
+                        .any(|(equip, wearable, weapon)| match requirement.as_str() {
+                            "shield" => wearable.is_some() && equip.slot == EquipmentSlot::OffHand,
+                            "two_handed" => weapon.is_some() && equip.slot == EquipmentSlot::TwoHanded,
+                            _ => false
+                        });
+                    if !met { continue; }
+                }
+
+                if let Some(block) = effects.get("block").and_then(|v| parse_passive::<f32>("block", v)) {
+                    bonus.block_chance += block;
+                }
+                if let Some(dodge) = effects.get("dodge").and_then(|v| parse_passive::<f32>("dodge", v)) {
+                    bonus.dodge_chance += dodge;
+                }
+                if let Some(thorns) = effects.get("thorns").and_then(|v| parse_passive::<i32>("thorns", v)) {
+                    bonus.thorns += thorns;
+                }
+                if let Some(mana_regen) = effects.get("mana_regen") { // amount/turns
+                    let (amount, turns) = mana_regen.split_once('/').unwrap_or((mana_regen, "1"));
+                    if let (Some(amount), Some(turns)) = (parse_passive::<i32>("mana_regen", amount), parse_passive::<i32>("mana_regen", turns)) {
+                        bonus.mana_regen += amount;
+                        // the most frequent regeneration wins
+                        bonus.mana_regen_turns = if bonus.mana_regen_turns > 0 { i32::min(bonus.mana_regen_turns, turns) } else { turns };
+                    }
+                }
+            }
+
+            if bonus == PassiveBonus::default() {
+                passive_bonuses.remove(entity);
+            } else if passive_bonuses.get(entity) != Some(&bonus) {
+                passive_bonuses.insert(entity, bonus).expect("Unable to insert");
+            }
+        }
+    }
+}
+
+fn parse_passive<T: std::str::FromStr>(key: &str, value: &str) -> Option<T> {
+    match value.parse::<T>() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            rltk::console::log(format!("WARNING - invalid passive {} value {}", key, value));
+            None
+        }
+    }
+}
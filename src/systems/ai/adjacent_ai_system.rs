@@ -1,6 +1,6 @@
 use specs::prelude::*;
-use crate::{MyTurn, Faction, Position, Map, WantsToMelee, TileSize, Rect, Confusion, RunState};
-use crate::raws::{Reaction, faction_reaction, RAWS};
+use crate::{MyTurn, Faction, Position, Map, WantsToMelee, TileSize, Rect, Confusion, RunState, FactionAttitudes};
+use crate::raws::{Reaction, attitude_reaction, RAWS};
 use crate::spatial;
 
 pub struct AdjacentAI {}
@@ -16,13 +16,14 @@ impl<'a> System<'a> for AdjacentAI {
         ReadExpect<'a, Entity>,
         ReadStorage<'a, TileSize>,
         ReadStorage<'a, Confusion>,
-        ReadExpect<'a, RunState>
+        ReadExpect<'a, RunState>,
+        ReadStorage<'a, FactionAttitudes>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (mut turns, factions, positions, map, 
             mut want_melee, entities, player,
-            tile_sizes, confused, runstate) = data;
+            tile_sizes, confused, runstate, attitudes) = data;
 
         if RunState::Ticking != *runstate { return; }
 
@@ -41,19 +42,19 @@ impl<'a> System<'a> for AdjacentAI {
                     parent_rect.get_all_tiles().iter().filter(|t| !mob_rect.contains(t)).for_each(|t| {
                         if t.0 > 0 && t.0 < w-1 && t.1 > 0 && t.1 < h-1 {
                             let target_idx = map.xy_idx(t.0, t.1);
-                            evaluate(target_idx, &factions, &my_faction.name, &mut reactions);
+                            evaluate(target_idx, &factions, &attitudes, &my_faction.name, &mut reactions);
                         }
                     });
                 } else {
                     // add possible reactions to adjacents for each direction
-                    if pos.x > 0 { evaluate(idx-1, &factions, &my_faction.name, &mut reactions); }
-                    if pos.x < w-1 { evaluate(idx+1, &factions, &my_faction.name, &mut reactions); }
-                    if pos.y > 0 { evaluate(idx-w as usize, &factions, &my_faction.name, &mut reactions); }
-                    if pos.y < h-1 { evaluate(idx+w as usize, &factions, &my_faction.name, &mut reactions); }
-                    if pos.y > 0 && pos.x > 0 { evaluate((idx-w as usize)-1, &factions, &my_faction.name, &mut reactions); }
-                    if pos.y > 0 && pos.x < w-1 { evaluate((idx-w as usize)+1, &factions, &my_faction.name, &mut reactions); }
-                    if pos.y < h-1 && pos.x > 0 { evaluate((idx+w as usize)-1, &factions, &my_faction.name, &mut reactions); }
-                    if pos.y < h-1 && pos.x < w-1 { evaluate((idx+w as usize)+1, &factions, &my_faction.name, &mut reactions); }
+                    if pos.x > 0 { evaluate(idx-1, &factions, &attitudes, &my_faction.name, &mut reactions); }
+                    if pos.x < w-1 { evaluate(idx+1, &factions, &attitudes, &my_faction.name, &mut reactions); }
+                    if pos.y > 0 { evaluate(idx-w as usize, &factions, &attitudes, &my_faction.name, &mut reactions); }
+                    if pos.y < h-1 { evaluate(idx+w as usize, &factions, &attitudes, &my_faction.name, &mut reactions); }
+                    if pos.y > 0 && pos.x > 0 { evaluate((idx-w as usize)-1, &factions, &attitudes, &my_faction.name, &mut reactions); }
+                    if pos.y > 0 && pos.x < w-1 { evaluate((idx-w as usize)+1, &factions, &attitudes, &my_faction.name, &mut reactions); }
+                    if pos.y < h-1 && pos.x > 0 { evaluate((idx+w as usize)-1, &factions, &attitudes, &my_faction.name, &mut reactions); }
+                    if pos.y < h-1 && pos.x < w-1 { evaluate((idx+w as usize)+1, &factions, &attitudes, &my_faction.name, &mut reactions); }
                 }
                 
                 let mut done = false;
@@ -74,12 +75,12 @@ impl<'a> System<'a> for AdjacentAI {
     }
 }
 
-fn evaluate(idx: usize, factions: &ReadStorage<Faction>, my_faction: &str, reactions: &mut Vec<(Entity, Reaction)>) {
+fn evaluate(idx: usize, factions: &ReadStorage<Faction>, attitudes: &ReadStorage<FactionAttitudes>, my_faction: &str, reactions: &mut Vec<(Entity, Reaction)>) {
     spatial::for_each_tile_content(idx, |other_entity| {
         if let Some(faction) = factions.get(other_entity) {
             reactions.push((
                 other_entity,
-                faction_reaction(my_faction, &faction.name, &RAWS.lock().unwrap())
+                attitude_reaction(my_faction, &faction.name, attitudes.get(other_entity), &RAWS.lock().unwrap())
             ));
         }
     });
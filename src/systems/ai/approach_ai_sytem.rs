@@ -1,6 +1,5 @@
 use specs::prelude::*;
-use crate::{MyTurn, WantsToApproach, Position, Map, ApplyMove, RunState};
-use crate::spatial::is_blocked;
+use crate::{MyTurn, WantsToApproach, Position, Map, ApplyMove, RunState, Swimmer, TileSize};
 
 pub struct ApproachAI {}
 
@@ -9,27 +8,29 @@ impl<'a> System<'a> for ApproachAI {
         WriteStorage<'a, MyTurn>,
         WriteStorage<'a, WantsToApproach>,
         WriteStorage<'a, Position>,
-        WriteExpect<'a, Map>,
+        ReadExpect<'a, Map>,
         Entities<'a>,
         WriteStorage<'a, ApplyMove>,
-        ReadExpect<'a, RunState>
+        ReadExpect<'a, RunState>,
+        ReadStorage<'a, Swimmer>,
+        ReadStorage<'a, TileSize>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (mut turns, mut want_approach, mut positions,
-            mut map, entities, mut apply_move, runstate) = data;
+            map, entities, mut apply_move, runstate, swimmers, tile_sizes) = data;
 
         if RunState::Ticking != *runstate { return; }
 
         let mut turn_done: Vec<Entity> = Vec::new();
         for (entity, pos, approach, _myturn) in (&entities, &mut positions, &mut want_approach, &mut turns).join() {
             // look for a path from the entity to what it wants to approach
-            let path = rltk::a_star_search(
-                map.xy_idx(pos.x, pos.y) as i32,
-                map.xy_idx(approach.idx % map.width, approach.idx / map.width) as i32,
-                &mut *map
-            );
-            if path.success && path.steps.len() > 1 && !is_blocked(path.steps[1]) {
+            let swims = swimmers.get(entity).is_some();
+            let start_idx = map.xy_idx(pos.x, pos.y);
+            let end_idx = map.xy_idx(approach.idx % map.width, approach.idx / map.width);
+            let size = tile_sizes.get(entity);
+            let path = map.find_path_for(entity, start_idx, end_idx, size, swims);
+            if path.success && path.steps.len() > 1 && map.can_fit(entity, path.steps[1], size, swims) {
                 // make the entity approach one step
                 apply_move.insert(entity, ApplyMove{ dest_idx: path.steps[1] }).expect("Unable to insert");
             }
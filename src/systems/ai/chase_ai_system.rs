@@ -1,7 +1,8 @@
 use specs::prelude::*;
-use crate::{Chasing, Map, MyTurn, Position, ApplyMove, TileSize, RunState};
+use crate::{Chasing, Map, MyTurn, Position, ApplyMove, TileSize, RunState, Swimmer, Smasher, Breakable, BlocksTile, Attributes};
+use crate::effects::{add_effect, EffectType, Targets};
+use crate::{rng, spatial};
 use std::collections::HashMap;
-use crate::spatial::is_blocked;
 
 pub struct ChaseAI {}
 
@@ -10,17 +11,22 @@ impl<'a> System<'a> for ChaseAI {
         WriteStorage<'a, MyTurn>,
         WriteStorage<'a, Chasing>,
         WriteStorage<'a, Position>,
-        WriteExpect<'a, Map>,
+        ReadExpect<'a, Map>,
         Entities<'a>,
         WriteStorage<'a, ApplyMove>,
         ReadStorage<'a, TileSize>,
-        ReadExpect<'a, RunState>
+        ReadExpect<'a, RunState>,
+        ReadStorage<'a, Swimmer>,
+        ReadStorage<'a, Smasher>,
+        ReadStorage<'a, Breakable>,
+        ReadStorage<'a, BlocksTile>,
+        ReadStorage<'a, Attributes>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (mut turns, mut chasing, mut positions, 
-            mut map, entities, mut apply_move,
-            tile_sizes, runstate) = data;
+            map, entities, mut apply_move,
+            tile_sizes, runstate, swimmers, smashers, breakables, blockers, attributes) = data;
 
         if RunState::Ticking != *runstate { return; }
         
@@ -40,30 +46,46 @@ impl<'a> System<'a> for ChaseAI {
         }
         end_chase.clear();
 
+        // closed doors and barricades that smashers can break through
+        let obstacles: HashMap<usize, Entity> = (&entities, &breakables, &blockers, &positions).join()
+            .map(|(obstacle, _breakable, _blocks, pos)| (map.xy_idx(pos.x, pos.y), obstacle))
+            .collect();
+
         let mut turn_done: Vec<Entity> = Vec::new();
         for (entity, pos, _chase, _myturn) in (&entities, &mut positions, &chasing, &turns).join() {
             let target_pos = targets[&entity];
-            let path;
-            if let Some(size) = tile_sizes.get(entity) {
-                // prevent large entities from moving into spaces too small for them to fit
-                let mut map_copy = map.clone();
-                map_copy.populate_blocked_multi(size.x, size.y);
-                path = rltk::a_star_search(
-                    map_copy.xy_idx(pos.x, pos.y),
-                    map_copy.xy_idx(target_pos.0, target_pos.1),
-                    &mut map_copy
-                );
-            } else {
-                path = rltk::a_star_search(
-                    map.xy_idx(pos.x, pos.y),
-                    map.xy_idx(target_pos.0, target_pos.1),
-                    &mut *map
-                );
+            let swims = swimmers.get(entity).is_some();
+            let smashes = smashers.get(entity).is_some();
+            let start_idx = map.xy_idx(pos.x, pos.y);
+            let end_idx = map.xy_idx(target_pos.0, target_pos.1);
+            if smashes {
+                obstacles.keys().for_each(|idx| spatial::set_blocked(*idx, false));
             }
-            if path.success && path.steps.len() > 1 && path.steps.len() < 15 && !is_blocked(path.steps[1]) {
-                apply_move.insert(entity, ApplyMove{ dest_idx: path.steps[1] }).expect("Unable to insert");
-            } else {
-                end_chase.push(entity);
+            // large entities can't move into spaces too small for them to fit
+            let size = tile_sizes.get(entity);
+            let path = map.find_path_for(entity, start_idx, end_idx, size, swims);
+            if smashes {
+                obstacles.keys().for_each(|idx| spatial::set_blocked(*idx, true));
+            }
+
+            let next_step = if path.success && path.steps.len() > 1 && path.steps.len() < 15 { Some(path.steps[1]) } else { None };
+            // large entities run into obstacles with any part of their body
+            let obstacle = next_step.and_then(|step| {
+                map.footprint(step, size).iter().find_map(|idx| obstacles.get(idx).copied())
+            });
+            match (next_step, obstacle) {
+                (Some(_), Some(obstacle)) if smashes => {
+                    let strength = attributes.get(entity).map_or(0, |attr| attr.strength.bonus);
+                    add_effect(
+                        Some(entity),
+                        EffectType::Smash{ damage: i32::max(1, rng::roll_dice(1, 6) + strength) },
+                        Targets::Single{ target: obstacle }
+                    );
+                }
+                (Some(step), _) if map.can_fit(entity, step, size, swims) => {
+                    apply_move.insert(entity, ApplyMove{ dest_idx: step }).expect("Unable to insert");
+                }
+                _ => end_chase.push(entity)
             }
             turn_done.push(entity);
         }
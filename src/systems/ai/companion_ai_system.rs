@@ -0,0 +1,95 @@
+use specs::prelude::*;
+use rltk::Point;
+use crate::{spatial, Companion, CompanionMode, Faction, FactionAttitudes, Map, MyTurn, Pools, Position, RunState, Target,
+    Viewshed, WantsToApproach, WantsToMelee};
+use crate::raws::{Reaction, attitude_reaction, RAWS};
+
+pub struct CompanionAI {}
+
+impl<'a> System<'a> for CompanionAI {
+    type SystemData = (
+        WriteStorage<'a, MyTurn>,
+        ReadStorage<'a, Companion>,
+        ReadStorage<'a, Faction>,
+        ReadStorage<'a, FactionAttitudes>,
+        ReadStorage<'a, Pools>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Viewshed>,
+        ReadStorage<'a, Target>,
+        WriteStorage<'a, WantsToApproach>,
+        WriteStorage<'a, WantsToMelee>,
+        ReadExpect<'a, Map>,
+        ReadExpect<'a, Entity>,
+        Entities<'a>,
+        ReadExpect<'a, RunState>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut turns, companions, factions, attitudes, pools, positions,
+            viewsheds, targets, mut want_approach, mut want_melee, map,
+            player, entities, runstate) = data;
+
+        if RunState::Ticking != *runstate { return; }
+
+        let player_pos = match positions.get(*player) {
+            Some(pos) => Point::new(pos.x, pos.y),
+            None => return
+        };
+
+        let mut turn_done: Vec<Entity> = Vec::new();
+        for (entity, companion, pos, viewshed, _turn) in (&entities, &companions, &positions, &viewsheds, &turns).join() {
+            let my_pos = Point::new(pos.x, pos.y);
+
+            // anything that would attack the player is hostile to their companions
+            let mut hostiles: Vec<(Entity, Point)> = Vec::new();
+            for tile in viewshed.visible_tiles.iter() {
+                spatial::for_each_tile_content(map.xy_idx(tile.x, tile.y), |other| {
+                    if other == entity || pools.get(other).is_none() { return; }
+                    if let Some(faction) = factions.get(other) {
+                        let reaction = attitude_reaction(&faction.name, "Player", attitudes.get(*player), &RAWS.lock().unwrap());
+                        if reaction == Reaction::Attack {
+                            hostiles.push((other, *tile));
+                        }
+                    }
+                });
+            }
+            let distance = |point: Point| rltk::DistanceAlg::Pythagoras.distance2d(my_pos, point);
+
+            if let Some(adjacent) = hostiles.iter().find(|(_, tile)| distance(*tile) < 1.5) {
+                want_melee.insert(entity, WantsToMelee{ target: adjacent.0 }).expect("Unable to insert");
+                turn_done.push(entity);
+                continue;
+            }
+
+            let destination = match companion.mode {
+                CompanionMode::Attack => {
+                    let target = hostiles.iter().find(|(hostile, _)| targets.get(*hostile).is_some())
+                        .or_else(|| hostiles.iter().min_by(|a, b| distance(a.1).partial_cmp(&distance(b.1)).unwrap()));
+                    match target {
+                        Some((_, tile)) => Some(*tile),
+                        None if distance(player_pos) > 2.0 => Some(player_pos),
+                        None => None
+                    }
+                }
+                CompanionMode::Guard{x, y} => {
+                    if my_pos != Point::new(x, y) { Some(Point::new(x, y)) } else { None }
+                }
+                CompanionMode::Follow => {
+                    if distance(player_pos) > 2.0 { Some(player_pos) } else { None }
+                }
+            };
+
+            match destination {
+                Some(destination) => {
+                    want_approach.insert(entity, WantsToApproach{ idx: map.xy_idx(destination.x, destination.y) as i32 })
+                        .expect("Unable to insert");
+                }
+                None => turn_done.push(entity)
+            }
+        }
+
+        for done in turn_done.iter() {
+            turns.remove(*done);
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use specs::prelude::*;
-use crate::{MyTurn, MoveMode, Movement, Position, Map, ApplyMove, RunState, tile_walkable};
+use crate::{MyTurn, MoveMode, Movement, Position, Map, ApplyMove, RunState, TileSize, tile_walkable};
 use crate::rng;
-use crate::spatial::is_blocked;
 
 pub struct DefaultMoveAI {}
 
@@ -10,20 +9,22 @@ impl<'a> System<'a> for DefaultMoveAI {
         WriteStorage<'a, MyTurn>,
         WriteStorage<'a, MoveMode>,
         WriteStorage<'a, Position>,
-        WriteExpect<'a, Map>,
+        ReadExpect<'a, Map>,
         Entities<'a>,
         WriteStorage<'a, ApplyMove>,
-        ReadExpect<'a, RunState>
+        ReadExpect<'a, RunState>,
+        ReadStorage<'a, TileSize>
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut turns, mut move_mode, mut positions, mut map,
-            entities, mut apply_move, runstate) = data;
+        let (mut turns, mut move_mode, mut positions, map,
+            entities, mut apply_move, runstate, tile_sizes) = data;
 
         if RunState::Ticking != *runstate { return; }
 
         let mut turn_done: Vec<Entity> = Vec::new();
         for (entity, pos, mode, _myturn) in (&entities, &mut positions, &mut move_mode, &turns).join() {
+            let size = tile_sizes.get(entity);
             match &mut mode.mode {
                 Movement::Static => {},
                 Movement::Random => {
@@ -42,7 +43,7 @@ impl<'a> System<'a> for DefaultMoveAI {
                     if x > 0 && x < map.width - 1
                     && y > 0 && y < map.height - 1 {
                         let dest_idx = map.xy_idx(x, y);
-                        if !is_blocked(dest_idx) {
+                        if map.can_fit(entity, dest_idx, size, false) {
                             apply_move.insert(entity, ApplyMove{ dest_idx }).expect("Unable to insert");
                         }
                     }
@@ -51,7 +52,7 @@ impl<'a> System<'a> for DefaultMoveAI {
                     if let Some(path) = path {
                         // there is a path to follow
                         if path.len() > 1 {
-                            if !is_blocked(path[1]) {
+                            if map.can_fit(entity, path[1], size, false) {
                                 // follow the path
                                 apply_move.insert(entity, ApplyMove{ dest_idx: path[1] }).expect("Unable to insert");
                                 path.remove(0); // remove the first step in the path
@@ -67,11 +68,8 @@ impl<'a> System<'a> for DefaultMoveAI {
                         let idx = map.xy_idx(target_x, target_y);
                         if tile_walkable(&map.tiles[idx]) {
                             // store the path to the location as the new path if possible to walk to the location
-                            let path = rltk::a_star_search(
-                                map.xy_idx(pos.x, pos.y) as i32,
-                                map.xy_idx(target_x, target_y) as i32,
-                                &mut *map
-                            );
+                            let start_idx = map.xy_idx(pos.x, pos.y);
+                            let path = map.find_path_for(entity, start_idx, idx, size, false);
                             if path.success && path.steps.len() > 1 {
                                 mode.mode = Movement::RandomWaypoint { path: Some(path.steps) }
                             }
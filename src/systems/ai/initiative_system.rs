@@ -1,5 +1,7 @@
 use specs::prelude::*;
-use crate::{Attributes, Duration, StatusEffectChanged, Initiative, MyTurn, Pools, Position, RunState, StatusEffect, DamageOverTime};
+use crate::{Attributes, Duration, StatusEffectChanged, Initiative, MyTurn, Pools, Position, RunState, StatusEffect, DamageOverTime,
+    KnownAbility, PassiveBonus, GameClock, Swimming, RAGE_DECAY_PER_TURN, FOCUS_REGEN_PER_TURN,
+    SWIM_INITIATIVE_PENALTY};
 use crate::effects::{add_effect, EffectType, Targets};
 use crate::rng;
 use crate::gamelog;
@@ -17,17 +19,21 @@ impl<'a> System<'a> for InitiativeSystem {
         WriteExpect<'a, RunState>,
         ReadExpect<'a, Entity>,
         ReadExpect<'a, Point>,
-        ReadStorage<'a, Pools>,
+        WriteStorage<'a, Pools>,
         WriteStorage<'a, Duration>,
         WriteStorage<'a, StatusEffectChanged>,
         ReadStorage<'a, StatusEffect>,
-        ReadStorage<'a, DamageOverTime>
+        ReadStorage<'a, DamageOverTime>,
+        WriteStorage<'a, KnownAbility>,
+        ReadStorage<'a, PassiveBonus>,
+        WriteExpect<'a, GameClock>,
+        ReadStorage<'a, Swimming>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (mut initiatives, positions, mut turns, entities,
-            attributes, mut runstate, player, player_pos, pools,
-            mut durations, mut dirty, statuses, dots) = data;
+            attributes, mut runstate, player, player_pos, mut pools,
+            mut durations, mut dirty, statuses, dots, mut known_abilities, passive_bonuses, mut clock, swimming) = data;
 
         if RunState::Ticking != *runstate { return; }
         turns.clear();
@@ -50,8 +56,14 @@ impl<'a> System<'a> for InitiativeSystem {
                     initiative.current += f32::floor(pools.initiative_penalty.total()) as i32;
                 }
 
+                // swimming is slow going
+                if swimming.get(entity).is_some() {
+                    initiative.current += SWIM_INITIATIVE_PENALTY;
+                }
+
                 if entity == *player {
                     *runstate = RunState::AwaitingInput;
+                    clock.turn += 1;
                 } else {
                     // prevent entities from acting until the player is nearby
                     let distance = rltk::DistanceAlg::Pythagoras.distance2d(
@@ -91,6 +103,24 @@ impl<'a> System<'a> for InitiativeSystem {
                     }
                 }
             }
+
+            // handle cooldowns
+            for known_ability in (&mut known_abilities).join() {
+                known_ability.tick_cooldown();
+            }
+
+            // rage slowly fades while focus comes back over time
+            for pool in (&mut pools).join() {
+                pool.rage.gain(-RAGE_DECAY_PER_TURN);
+                pool.focus.gain(FOCUS_REGEN_PER_TURN);
+            }
+
+            // passive regeneration
+            for (pool, passive) in (&mut pools, &passive_bonuses).join() {
+                if passive.mana_regen > 0 && passive.mana_regen_turns > 0 && clock.turn % passive.mana_regen_turns == 0 {
+                    pool.mana.gain(passive.mana_regen);
+                }
+            }
         }
     }
 }
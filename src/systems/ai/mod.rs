@@ -5,6 +5,7 @@ mod adjacent_ai_system;
 mod visible_ai_system;
 mod approach_ai_sytem;
 mod chase_ai_system;
+mod companion_ai_system;
 pub use default_move_ai_system::DefaultMoveAI;
 pub use initiative_system::InitiativeSystem;
 pub use quipping_system::QuipSystem;
@@ -12,3 +13,4 @@ pub use adjacent_ai_system::AdjacentAI;
 pub use visible_ai_system::VisibleAI;
 pub use approach_ai_sytem::ApproachAI;
 pub use chase_ai_system::ChaseAI;
+pub use companion_ai_system::CompanionAI;
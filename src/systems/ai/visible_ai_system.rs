@@ -1,8 +1,8 @@
 use specs::prelude::*;
 use rltk::Point;
 use crate::{spatial, AbilityType, Chasing, Confusion, Equipped, Faction, KnownAbilities, KnownAbility, Map, MyTurn, Position,
-    Ranged, Viewshed, WantsToApproach, WantsToShoot, WantsToUseAbility, Weapon, RunState};
-use crate::raws::{Reaction, faction_reaction, RAWS};
+    Ranged, Viewshed, WantsToApproach, WantsToShoot, WantsToUseAbility, Weapon, RunState, FactionAttitudes, Pools};
+use crate::raws::{Reaction, attitude_reaction, RAWS};
 use crate::rng;
 
 pub struct VisibleAI {}
@@ -26,14 +26,16 @@ impl<'a> System<'a> for VisibleAI {
         ReadStorage<'a, Equipped>,
         ReadStorage<'a, Weapon>,
         WriteStorage<'a, WantsToShoot>,
-        ReadExpect<'a, RunState>
+        ReadExpect<'a, RunState>,
+        ReadStorage<'a, FactionAttitudes>,
+        ReadStorage<'a, Pools>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (turns, factions, positions, map, mut want_approach,
             entities, player, viewsheds, mut chasing, known_abilities,
             known_ability_lists, mut wants_cast, ranged, confused,
-            equipped, weapons, mut wants_shoot, runstate) = data;
+            equipped, weapons, mut wants_shoot, runstate, attitudes, pools) = data;
 
         if RunState::Ticking != *runstate { return; }
 
@@ -44,7 +46,7 @@ impl<'a> System<'a> for VisibleAI {
                 for visible_tile in viewshed.visible_tiles.iter() {
                     let idx = map.xy_idx(visible_tile.x, visible_tile.y);
                     if my_idx != idx {
-                        evaluate(idx, &factions, &my_faction.name, &mut reactions);
+                        evaluate(idx, &factions, &attitudes, &my_faction.name, &mut reactions);
                     }
                 }
 
@@ -67,6 +69,10 @@ impl<'a> System<'a> for VisibleAI {
                                     for ability_entity in ability_entities.abilities.iter() {
                                         let known_ability = known_abilities.get(*ability_entity).unwrap();
                                         if known_ability.ability_type == AbilityType::Passive { continue; }
+                                        if !known_ability.is_ready() { continue; }
+                                        if let Some(pool) = pools.get(entity) {
+                                            if known_ability.missing_resource(pool).is_some() { continue; }
+                                        }
 
                                         if let Some(ranged) = ranged.get(*ability_entity) {
                                             if range > ranged.max_range || range < ranged.min_range { continue; }
@@ -124,12 +130,12 @@ impl<'a> System<'a> for VisibleAI {
     }
 }
 
-fn evaluate(idx: usize, factions: &ReadStorage<Faction>, my_faction: &str, reactions: &mut Vec<(usize, Reaction, Entity)>) {
+fn evaluate(idx: usize, factions: &ReadStorage<Faction>, attitudes: &ReadStorage<FactionAttitudes>, my_faction: &str, reactions: &mut Vec<(usize, Reaction, Entity)>) {
     spatial::for_each_tile_content(idx, |other_entity| {
         if let Some(faction) = factions.get(other_entity) {
             reactions.push((
                 idx,
-                faction_reaction(my_faction, &faction.name, &RAWS.lock().unwrap()),
+                attitude_reaction(my_faction, &faction.name, attitudes.get(other_entity), &RAWS.lock().unwrap()),
                 other_entity
             ));
         }
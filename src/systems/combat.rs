@@ -0,0 +1,57 @@
+use specs::prelude::*;
+use crate::{gamelog, rng, Block, Dodge, Equipped, PassiveBonus, Wearable};
+use crate::effects::{add_effect, EffectType, Targets};
+
+// checks if the defender dodges or blocks an attack that would otherwise hit
+pub fn avoids_hit(
+    defender: Entity,
+    defender_name: &str,
+    attacker_name: &str,
+    dodges: &ReadStorage<Dodge>,
+    blocks: &ReadStorage<Block>,
+    passive_bonuses: &ReadStorage<PassiveBonus>
+) -> bool {
+    let passive = passive_bonuses.get(defender);
+    let dodge_chance = dodges.get(defender).map_or(0.0, |dodge| dodge.chance)
+        + passive.map_or(0.0, |passive| passive.dodge_chance);
+    let block_chance = blocks.get(defender).map_or(0.0, |block| block.chance)
+        + passive.map_or(0.0, |passive| passive.block_chance);
+
+    let avoided_with = if dodge_chance > 0.0 && rng::roll_dice(1, 100) <= (dodge_chance * 100.0) as i32 {
+        "dodges"
+    } else if block_chance > 0.0 && rng::roll_dice(1, 100) <= (block_chance * 100.0) as i32 {
+        "blocks"
+    } else {
+        return false;
+    };
+
+    gamelog::Logger::new()
+        .character_name(defender_name)
+        .ability_name(avoided_with)
+        .append("attack from")
+        .character_name(attacker_name)
+        .append("!")
+        .log();
+    true
+}
+
+// wears down the attacker's weapon and a random piece of the defender's armour
+pub fn wear_equipment(
+    weapon: Option<Entity>,
+    defender: Entity,
+    entities: &Entities,
+    equipped_items: &ReadStorage<Equipped>,
+    wearables: &ReadStorage<Wearable>
+) {
+    if let Some(weapon_entity) = weapon {
+        add_effect(None, EffectType::Wear{ amount: 1 }, Targets::Single{ target: weapon_entity });
+    }
+    let worn_armour: Vec<Entity> = (entities, equipped_items, wearables).join()
+        .filter(|(_, wielded, _)| wielded.owner == defender)
+        .map(|(armour_entity, _, _)| armour_entity)
+        .collect();
+    if !worn_armour.is_empty() {
+        let armour_entity = worn_armour[rng::roll_dice(1, worn_armour.len() as i32) as usize - 1];
+        add_effect(None, EffectType::Wear{ amount: 1 }, Targets::Single{ target: armour_entity });
+    }
+}
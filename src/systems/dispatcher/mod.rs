@@ -27,15 +27,23 @@ construct_dispatcher!(
     (TurnStatusSystem, "turn_status", &["initiative"]),
     (QuipSystem, "quips", &["initiative"]),
     (HungerSystem, "hunger", &[]),
+    (HazardSystem, "hazards", &["initiative"]),
+    (WeatherSystem, "weather", &["hazards"]),
+    (SwimmingSystem, "swimming", &["initiative"]),
     (LearnAbilitySystem, "learn_ability", &["level_up"]),
     (LevelAbilitySystem, "level_ability", &["level_up"]),
-    (AdjacentAI, "adjacent_ai", &["initiative"]),
+    (PassiveAbilitySystem, "passive_ability", &["level_ability"]),
+    (CompanionAI, "companion_ai", &["initiative"]),
+    (AdjacentAI, "adjacent_ai", &["companion_ai"]),
     (VisibleAI, "visible_ai", &["adjacent_ai"]),
     (ApproachAI, "approach_ai", &["visible_ai"]),
     (ChaseAI, "chase_ai", &["visible_ai"]),
     (DefaultMoveAI, "default_move_ai", &[ "approach_ai"]),
     (MovementSystem, "movement", &[]),
     (TriggerSystem, "triggers", &[]),
+    (TrapDetectionSystem, "trap_detection", &["initiative"]),
+    (TrapDisarmSystem, "trap_disarm", &[]),
+    (LockSystem, "locks", &[]),
     (MeleeCombatSystem, "melee_combat", &["adjacent_ai"]),
     (RangedCombatSystem, "ranged_combat", &["visible_ai"]),
     (QuestProgressSystem, "quest_progress", &[]),
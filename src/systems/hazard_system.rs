@@ -0,0 +1,113 @@
+use specs::prelude::*;
+use crate::{gamelog, rng, tile_flammable, tile_walkable, HazardType, Map, Position, RunState, TileType};
+use crate::effects::{add_effect, EffectType, Targets};
+
+const FIRE_DAMAGE: i32 = 4;
+const POISON_DAMAGE: i32 = 2;
+const LIGHTNING_DAMAGE: i32 = 5;
+const FIRE_SPREAD_CHANCE: i32 = 30;
+const FIRE_SPREAD_TURNS: i32 = 4;
+const GAS_DRIFT_CHANCE: i32 = 50;
+
+pub struct HazardSystem {}
+
+impl<'a> System<'a> for HazardSystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        ReadStorage<'a, Position>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut map, positions, player_entity, runstate) = data;
+
+        // hazards tick once per turn
+        if *runstate != RunState::AwaitingInput { return; }
+        if map.hazards.is_empty() { return; }
+
+        let player_idx = positions.get(*player_entity).map(|pos| map.xy_idx(pos.x, pos.y));
+        let mut spread: Vec<usize> = Vec::new();
+        let mut drift: Vec<(usize, usize)> = Vec::new();
+        let mut expired: Vec<usize> = Vec::new();
+
+        let hazard_tiles: Vec<usize> = map.hazards.keys().cloned().collect();
+        for idx in hazard_tiles {
+            let hazard_type = map.hazards[&idx].hazard_type;
+            let damage = match hazard_type {
+                HazardType::Fire => FIRE_DAMAGE,
+                HazardType::PoisonGas => POISON_DAMAGE,
+                HazardType::Lightning => LIGHTNING_DAMAGE,
+                HazardType::Ice => 0
+            };
+            if damage > 0 {
+                add_effect(None, EffectType::Damage{ amount: damage, hits_self: false }, Targets::Tile{ tile_idx: idx as i32 });
+                if player_idx == Some(idx) {
+                    let message = match hazard_type {
+                        HazardType::Fire => "You are burned by the flames!",
+                        HazardType::PoisonGas => "You choke on the poisonous gas!",
+                        _ => "You are shocked by the electrified water!"
+                    };
+                    gamelog::Logger::new().colour(rltk::RGB::named(rltk::ORANGE)).append(message).log();
+                }
+            }
+
+            let neighbours = neighbours(&map, idx);
+            match hazard_type {
+                HazardType::Fire => {
+                    for neighbour in neighbours {
+                        if tile_flammable(&map.tiles[neighbour]) && !map.hazards.contains_key(&neighbour)
+                        && rng::roll_dice(1, 100) <= FIRE_SPREAD_CHANCE {
+                            spread.push(neighbour);
+                        }
+                    }
+                }
+                HazardType::PoisonGas if rng::roll_dice(1, 100) <= GAS_DRIFT_CHANCE => {
+                    let open: Vec<usize> = neighbours.into_iter()
+                        .filter(|n| tile_walkable(&map.tiles[*n]) && !map.hazards.contains_key(n))
+                        .collect();
+                    if !open.is_empty() {
+                        drift.push((idx, open[rng::roll_dice(1, open.len() as i32) as usize - 1]));
+                    }
+                }
+                _ => {}
+            }
+
+            let hazard = map.hazards.get_mut(&idx).unwrap();
+            hazard.turns -= 1;
+            if hazard.turns < 1 {
+                expired.push(idx);
+            }
+        }
+
+        for idx in expired {
+            let burnt = map.hazards[&idx].hazard_type == HazardType::Fire;
+            map.remove_hazard(idx);
+            // burnt grass is left as scorched ground
+            if burnt && tile_flammable(&map.tiles[idx]) {
+                map.tiles[idx] = TileType::Gravel;
+            }
+        }
+        for idx in spread {
+            map.add_hazard(idx, HazardType::Fire, FIRE_SPREAD_TURNS);
+        }
+        for (from, to) in drift {
+            if map.hazards.contains_key(&to) { continue; }
+            if let Some(gas) = map.hazards.remove(&from) {
+                map.hazards.insert(to, gas);
+            }
+        }
+    }
+}
+
+fn neighbours(map: &Map, idx: usize) -> Vec<usize> {
+    let (x, y) = map.idx_xy(idx);
+    let mut result = Vec::new();
+    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        let (nx, ny) = (x + dx, y + dy);
+        if nx > 0 && nx < map.width - 1 && ny > 0 && ny < map.height - 1 {
+            result.push(map.xy_idx(nx, ny));
+        }
+    }
+    result
+}
@@ -1,6 +1,6 @@
 use specs::prelude::*;
 use super::{InBackpack, WantsToUseItem, Equippable, Equipped, EquipmentChanged, EquipmentSlot, Item};
-use crate::gamelog;
+use crate::{gamelog, Cursed};
 
 pub struct ItemEquipSystem {}
 
@@ -13,12 +13,13 @@ impl<'a> System<'a> for ItemEquipSystem {
         WriteStorage<'a, Equipped>,
         WriteStorage<'a, InBackpack>,
         WriteStorage<'a, EquipmentChanged>,
-        ReadStorage<'a, Item>
+        WriteStorage<'a, Item>,
+        WriteStorage<'a, Cursed>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (player_entity, entities, mut wants_use, equippable,
-            mut equipped, mut backpack, mut dirty, items) = data;
+            mut equipped, mut backpack, mut dirty, mut items, mut cursed) = data;
 
         let mut remove_use: Vec<Entity> = Vec::new();
         for (target, useitem) in (&entities, &wants_use).join() {
@@ -44,6 +45,22 @@ impl<'a> System<'a> for ItemEquipSystem {
                         }
                     }
                 }
+
+                // cursed items can't be swapped out
+                if let Some(cursed_entity) = to_unequip.iter().find(|item_entity| cursed.get(**item_entity).is_some()) {
+                    if target == *player_entity {
+                        cursed.get_mut(*cursed_entity).unwrap().known = true;
+                        if let Some(item) = items.get(*cursed_entity) {
+                            gamelog::Logger::new()
+                                .append("You cannot remove the cursed")
+                                .item_name(item)
+                                .log();
+                        }
+                    }
+                    remove_use.push(target);
+                    continue;
+                }
+
                 for item_entity in to_unequip.iter() {
                     equipped.remove(*item_entity);
                     backpack.insert(*item_entity, InBackpack{ owner: target }).expect("Unable to insert backpack entry");
@@ -61,12 +78,25 @@ impl<'a> System<'a> for ItemEquipSystem {
                 equipped.insert(useitem.item, Equipped{ owner: target, slot: target_slot }).expect("Unable to insert equipped component");
                 backpack.remove(useitem.item);
                 if target == *player_entity {
+                    // wearing an item identifies it
+                    if let Some(item) = items.get_mut(useitem.item) {
+                        item.unidentified_name = None;
+                    }
                     if let Some(item) = items.get(useitem.item) {
                         gamelog::Logger::new()
                             .append("You equip")
                             .item_name(item)
                             .log();
                     }
+                    if let Some(curse) = cursed.get_mut(useitem.item) {
+                        if !curse.known {
+                            curse.known = true;
+                            gamelog::Logger::new()
+                                .colour(rltk::RGB::named(rltk::RED))
+                                .append("It's cursed! You can't take it off.")
+                                .log();
+                        }
+                    }
                 }
 
                 remove_use.push(target);
@@ -1,6 +1,6 @@
 use specs::prelude::*;
 use super::{WantsToUnequipItem, Equipped, InBackpack, EquipmentChanged, Item};
-use crate::gamelog;
+use crate::{gamelog, Cursed};
 
 pub struct ItemUnequipSystem {}
 
@@ -12,14 +12,28 @@ impl<'a> System<'a> for ItemUnequipSystem {
         WriteStorage<'a, Equipped>,
         WriteStorage<'a, InBackpack>,
         WriteStorage<'a, EquipmentChanged>,
-        ReadStorage<'a, Item>
+        ReadStorage<'a, Item>,
+        WriteStorage<'a, Cursed>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (player_entity, entities, mut wants_unequip,
-            mut equipped, mut backpack, mut dirty, items) = data;
+            mut equipped, mut backpack, mut dirty, items, mut cursed) = data;
 
         for (entity, to_unequip) in (&entities, &wants_unequip).join() {
+            if let Some(curse) = cursed.get_mut(to_unequip.item) {
+                if entity == *player_entity {
+                    curse.known = true;
+                    if let Some(item) = items.get(to_unequip.item) {
+                        gamelog::Logger::new()
+                            .append("You cannot remove the cursed")
+                            .item_name(item)
+                            .log();
+                    }
+                }
+                continue;
+            }
+
             equipped.remove(to_unequip.item);
             backpack.insert(to_unequip.item, InBackpack{ owner: entity }).expect("Unable to insert backpack");
             if entity == *player_entity {
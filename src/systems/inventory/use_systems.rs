@@ -13,10 +13,11 @@ impl<'a> System<'a> for ItemUseSystem {
         WriteStorage<'a, WantsToUseItem>,
         ReadStorage<'a, AreaOfEffect>,
         WriteStorage<'a, EquipmentChanged>,
+        ReadStorage<'a, Position>
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (player_entity, map, entities, mut wants_use, aoe, mut dirty) = data;
+        let (player_entity, map, entities, mut wants_use, aoe, mut dirty, positions) = data;
 
         if wants_use.is_empty() { return; }
 
@@ -28,7 +29,8 @@ impl<'a> System<'a> for ItemUseSystem {
                     None => Targets::Single{ target: *player_entity },
                     Some(target) => {
                         if let Some(aoe) = aoe.get(useitem.item) {
-                            Targets::Tiles{ tiles: aoe_tiles(&*map, target, aoe.radius) }
+                            let origin = positions.get(entity).map_or(target, |pos| Point::new(pos.x, pos.y));
+                            Targets::Tiles{ tiles: aoe_tiles(&*map, origin, target, aoe) }
                         } else {
                             Targets::Tile{ tile_idx: map.xy_idx(target.x, target.y) as i32 }
                         }
@@ -68,14 +70,16 @@ impl<'a> System<'a> for AbilityUseSystem {
                 None => {
                     let pos = positions.get(entity).unwrap();
                     if let Some(aoe) = aoe.get(use_ability.ability) {
-                        Targets::Tiles { tiles: aoe_tiles(&*map, Point::new(pos.x, pos.y), aoe.radius) }
+                        let pos = Point::new(pos.x, pos.y);
+                        Targets::Tiles { tiles: aoe_tiles(&*map, pos, pos, aoe) }
                     } else {
                         Targets::Tile { tile_idx: map.xy_idx(pos.x, pos.y) as i32 }
                     }
                 }
                 Some(target) => {
                     if let Some(aoe) = aoe.get(use_ability.ability) {
-                        Targets::Tiles{ tiles: aoe_tiles(&*map, target, aoe.radius) }
+                        let origin = positions.get(entity).map_or(target, |pos| Point::new(pos.x, pos.y));
+                        Targets::Tiles{ tiles: aoe_tiles(&*map, origin, target, aoe) }
                     } else {
                         Targets::Tile{ tile_idx: map.xy_idx(target.x, target.y) as i32 }
                     }
@@ -1,5 +1,5 @@
 use specs::prelude::*;
-use crate::{mana_at_level, hp_at_level, Attributes, CharacterClass, EquipmentChanged, WantsToLevelUp,
+use crate::{mana_at_level, hp_at_level, Attributes, CharacterClass, EquipmentChanged, WantsToLevelUp, AbilityToLearn,
     Pools, RunState, Skills, WantsToLearnAbility, WantsToLevelAbility, Point, Map};
 use crate::gamelog;
 use crate::effects::{add_effect, EffectType, Targets};
@@ -35,18 +35,31 @@ impl<'a> System<'a> for LevelUpCharacterSystem {
 
         for (entity, level_up, pool, char_class, char_attr, char_skills)
             in (&entities, &wants_level_up, &mut pools, &mut character_classes, &mut attributes, &mut skills).join() {
-            pool.level += 1;
-            pool.xp = 0; // loses overflow xp?
+            if level_up.level_gain {
+                pool.level += 1;
+                pool.xp = 0; // loses overflow xp?
+            }
+
+            // manually allocated points
+            char_attr.strength.base += level_up.allocated("Strength");
+            char_attr.dexterity.base += level_up.allocated("Dexterity");
+            char_attr.constitution.base += level_up.allocated("Constitution");
+            char_attr.intelligence.base += level_up.allocated("Intelligence");
+            char_skills.melee.base += level_up.allocated("Melee");
+            char_skills.defence.base += level_up.allocated("Defence");
+            char_skills.ranged.base += level_up.allocated("Ranged");
+            char_skills.magic.base += level_up.allocated("Magic");
 
             let passives = &mut char_class.passives;
+            let mut learned: Vec<AbilityToLearn> = Vec::new();
             for (name, passive) in level_up.passives.iter() {
-                if passive.current_level < 1 { continue; }
+                let current_passive = passives.get_mut(name).unwrap();
 
-                if passives[name].current_level != passive.current_level {
-                    let current_passive = passives.get_mut(name).unwrap();
-                    current_passive.current_level = passive.current_level;
+                // a respec can raise a passive by several levels at once
+                while current_passive.current_level < passive.current_level {
+                    current_passive.current_level += 1;
 
-                    if let Some(attribute_bonus) = &current_passive.levels[&current_passive.current_level].attribute_bonus {
+                    if let Some(attribute_bonus) = &current_passive.active_level().attribute_bonus {
                         if let Some(strength) = attribute_bonus.strength {
                             char_attr.strength.base += strength;
                         }
@@ -61,17 +74,6 @@ impl<'a> System<'a> for LevelUpCharacterSystem {
                         }
                     }
 
-                    pool.hit_points.max = hp_at_level(
-                        char_attr.constitution.base + char_attr.constitution.total_modifiers(),
-                        pool.level
-                    );
-                    pool.hit_points.current = pool.hit_points.max;
-                    pool.mana.max = mana_at_level(
-                        char_attr.intelligence.base + char_attr.intelligence.total_modifiers(),
-                        pool.level
-                    );
-                    pool.mana.current = pool.mana.max;
-
                     if let Some(skill_bonus) = &current_passive.active_level().skill_bonus {
                         if let Some(melee) = skill_bonus.melee {
                             char_skills.melee.base += melee;
@@ -88,18 +90,43 @@ impl<'a> System<'a> for LevelUpCharacterSystem {
                     }
 
                     if let Some(learn_ability) = &current_passive.active_level().learn_ability {
-                        learn_abilities.insert(*player_entity, WantsToLearnAbility{ ability_name: learn_ability.clone(), level: 1 }).expect("Unable to insert");
+                        learned.push(AbilityToLearn{ ability_name: learn_ability.clone(), level: 1, from_passive: true });
                     }
 
                     if let Some(level_ability) = &current_passive.active_level().level_ability {
-                        level_abilities.insert(*player_entity, WantsToLevelAbility{ ability_name: level_ability.clone() }).expect("Unable to insert");
+                        // abilities learned this turn don't exist yet so learn them at the higher level instead
+                        if let Some(learn) = learned.iter_mut().find(|learn| &learn.ability_name == level_ability) {
+                            learn.level += 1;
+                        } else {
+                            WantsToLevelAbility::level(&mut level_abilities, *player_entity, level_ability);
+                        }
                     }
                 }
             }
+            for learn in learned.iter() {
+                WantsToLearnAbility::learn(&mut learn_abilities, *player_entity, &learn.ability_name, learn.level, true);
+            }
+
+            pool.hit_points.max = hp_at_level(
+                char_attr.constitution.base + char_attr.constitution.total_modifiers(),
+                pool.level
+            );
+            pool.mana.max = mana_at_level(
+                char_attr.intelligence.base + char_attr.intelligence.total_modifiers(),
+                pool.level
+            );
+            // levelling up heals, a respec only keeps the pools within their new max
+            if level_up.level_gain {
+                pool.hit_points.current = pool.hit_points.max;
+                pool.mana.current = pool.mana.max;
+            } else {
+                pool.hit_points.current = i32::min(pool.hit_points.current, pool.hit_points.max);
+                pool.mana.current = i32::min(pool.mana.current, pool.mana.max);
+            }
 
             equip_dirty.insert(entity, EquipmentChanged{}).expect("Unable to insert");
 
-            if entity == *player_entity {
+            if entity == *player_entity && level_up.level_gain {
                 gamelog::clear_log();
                 gamelog::Logger::new()
                     .append("You are now level")
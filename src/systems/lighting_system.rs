@@ -1,5 +1,5 @@
 use specs::prelude::*;
-use crate::{Viewshed, Position, Map, LightSource};
+use crate::{Viewshed, Position, Map, LightSource, GameClock};
 use rltk::{RGB, Point};
 
 pub struct LightingSystem {}
@@ -9,21 +9,25 @@ impl<'a> System<'a> for LightingSystem {
         WriteExpect<'a, Map>,
         ReadStorage<'a, Viewshed>,
         ReadStorage<'a, Position>,
-        ReadStorage<'a, LightSource>
+        ReadStorage<'a, LightSource>,
+        ReadExpect<'a, GameClock>
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut map, viewshed, positions, lighting) = data;
+        let (mut map, viewshed, positions, lighting, clock) = data;
 
-        if !map.indoors {
-            // no dark outdoors maps yet
-            return;
-        }
-
-        let black = RGB::named(rltk::BLACK);
+        // outdoor maps are lit by the sun, with a blue tint at night
+        let ambient = if map.indoors {
+            RGB::named(rltk::BLACK)
+        } else {
+            let daylight = clock.daylight();
+            let tint = daylight * (0.8 + 0.2 * daylight);
+            RGB::from_f32(tint, tint, daylight)
+        };
         for l in map.light.iter_mut() {
-            *l = black;
+            *l = ambient;
         }
+        if !map.indoors && clock.daylight() >= 1.0 { return; }
 
         for (viewshed, pos, light) in (&viewshed, &positions, &lighting).join() {
             let light_point = Point::new(pos.x, pos.y);
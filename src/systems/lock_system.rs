@@ -0,0 +1,67 @@
+use specs::prelude::*;
+use crate::{gamelog, rng, Attributes, EquipmentChanged, InBackpack, Key, Lockpick, Locked, Name, WantsToUnlock};
+
+const LOCKPICK_DIFFICULTY: i32 = 12;
+
+pub struct LockSystem {}
+
+impl<'a> System<'a> for LockSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, WantsToUnlock>,
+        WriteStorage<'a, Locked>,
+        ReadStorage<'a, Key>,
+        ReadStorage<'a, Lockpick>,
+        ReadStorage<'a, InBackpack>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Attributes>,
+        WriteStorage<'a, EquipmentChanged>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, mut wants_unlock, mut locked, keys, lockpicks, backpack,
+            names, attributes, mut equipment_changed) = data;
+
+        for (entity, unlock) in (&entities, &wants_unlock).join() {
+            let lock_id = match locked.get(unlock.target) {
+                Some(lock) => lock.lock_id.clone(),
+                None => continue
+            };
+            let target_name = names.get(unlock.target).map_or("lock".to_string(), |n| n.name.clone());
+
+            // a matching key always works
+            let key = (&entities, &keys, &backpack).join()
+                .find(|(_key_entity, key, carried)| carried.owner == entity && key.lock_id == lock_id)
+                .map(|(key_entity, _key, _carried)| key_entity);
+            if let Some(key) = key {
+                let key_name = names.get(key).map_or("key".to_string(), |n| n.name.clone());
+                gamelog::Logger::new().append(format!("You unlock the {} with the {}.", target_name, key_name)).log();
+                locked.remove(unlock.target);
+                entities.delete(key).expect("Unable to delete");
+                equipment_changed.insert(entity, EquipmentChanged{}).expect("Unable to insert");
+                continue;
+            }
+
+            // otherwise try picking the lock, breaking the lockpick on failure
+            let lockpick = (&entities, &lockpicks, &backpack).join()
+                .find(|(_pick_entity, _lockpick, carried)| carried.owner == entity)
+                .map(|(pick_entity, _lockpick, _carried)| pick_entity);
+            if let Some(lockpick) = lockpick {
+                let bonus = attributes.get(entity).map_or(0, |attr| attr.dexterity.bonus);
+                if rng::roll_dice(1, 20) + bonus >= LOCKPICK_DIFFICULTY {
+                    gamelog::Logger::new().append(format!("You pick the lock on the {}.", target_name)).log();
+                    locked.remove(unlock.target);
+                } else {
+                    gamelog::Logger::new()
+                        .colour(rltk::RGB::named(rltk::ORANGE))
+                        .append("Your lockpick breaks.")
+                        .log();
+                    entities.delete(lockpick).expect("Unable to delete");
+                    equipment_changed.insert(entity, EquipmentChanged{}).expect("Unable to insert");
+                }
+            }
+        }
+
+        wants_unlock.clear();
+    }
+}
@@ -26,21 +26,10 @@ impl<'a> System<'a> for MapIndexingSystem {
                 }
             }
             if alive {
-                if let Some(size) = tile_sizes.get(entity) {
-                    // multi-tile
-                    for y in position.y .. position.y + size.y {
-                        for x in position.x .. position.x + size.x {
-                            if x > 0 && x < map.width-1
-                            && y > 0 && y < map.height-1 {
-                                let idx = map.xy_idx(x, y);
-                                spatial::index_entity(entity, idx, blockers.get(entity).is_some());
-                            }
-                        }
-                    }
-                } else {
-                    // single tile
-                    let idx = map.xy_idx(position.x, position.y);
-                    spatial::index_entity(entity, idx, blockers.get(entity).is_some());
+                // large entities occupy every tile of their footprint
+                let idx = map.xy_idx(position.x, position.y);
+                for tile_idx in map.footprint(idx, tile_sizes.get(entity)) {
+                    spatial::index_entity(entity, tile_idx, blockers.get(entity).is_some());
                 }
             }
         }
@@ -2,7 +2,8 @@ use specs::prelude::*;
 use crate::{Attributes, Skills, WantsToMelee, Name, Position,
     HungerClock, HungerState, Pools, Equipped, Weapon, AreaOfEffect,
     EquipmentSlot, WeaponAttribute, Wearable, NaturalAttackDefence, Map,
-    Dodge, Block};
+    Dodge, Block, PassiveBonus};
+use super::combat::{avoids_hit, wear_equipment};
 use crate::effects::{add_effect, aoe_tiles, EffectType, Targets};
 use rltk::{RGB, Point};
 use crate::gamelog;
@@ -27,14 +28,15 @@ impl<'a> System<'a> for MeleeCombatSystem {
         ReadStorage<'a, AreaOfEffect>,
         ReadStorage<'a, Dodge>,
         ReadStorage<'a, Block>,
-        WriteExpect<'a, Map>
+        WriteExpect<'a, Map>,
+        ReadStorage<'a, PassiveBonus>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (entities, mut wants_melees, names, attributes, 
             skills, pools, positions, hunger_clock, 
             equipped_items, weapons, wearables, natural,
-            area_of_effect, dodges, blocks, map) = data;
+            area_of_effect, dodges, blocks, map, passive_bonuses) = data;
 
         for (entity, wants_melee, name, attacker_attributes, attacker_skills, attacker_pools) in (&entities, &wants_melees, &names, &attributes, &skills, &pools).join() {
             let target_pools = pools.get(wants_melee.target).unwrap();
@@ -125,29 +127,8 @@ impl<'a> System<'a> for MeleeCombatSystem {
                 // hit
 
                 // check if target has the ability to avoid damage from the attack
-                if let Some(dodge) = dodges.get(wants_melee.target) {
-                    if rng::roll_dice(1, 100) <= (dodge.chance * 100.0) as i32 {
-                        gamelog::Logger::new()
-                            .character_name(&names.get(wants_melee.target).unwrap().name)
-                            .ability_name("dodges")
-                            .append("attack from")
-                            .character_name(&name.name)
-                            .append("!")
-                            .log();
-                        continue;
-                    }
-                }
-                if let Some(block) = blocks.get(wants_melee.target) {
-                    if rng::roll_dice(1, 100) <= (block.chance * 100.0) as i32 {
-                        gamelog::Logger::new()
-                            .character_name(&names.get(wants_melee.target).unwrap().name)
-                            .ability_name("blocks")
-                            .append("attack from")
-                            .character_name(&name.name)
-                            .append("!")
-                            .log();
-                        continue;
-                    }
+                if avoids_hit(wants_melee.target, &names.get(wants_melee.target).unwrap().name, &name.name, &dodges, &blocks, &passive_bonuses) {
+                    continue;
                 }
 
                 // calculate damage
@@ -168,6 +149,20 @@ impl<'a> System<'a> for MeleeCombatSystem {
                     Targets::Single{ target: wants_melee.target }
                 );
 
+                // thorns hurt the attacker back
+                if let Some(passive) = passive_bonuses.get(wants_melee.target) {
+                    if passive.thorns > 0 {
+                        add_effect(
+                            Some(wants_melee.target),
+                            EffectType::Damage{ amount: passive.thorns, hits_self: false },
+                            Targets::Single{ target: entity }
+                        );
+                    }
+                }
+
+                // wear down the attacker's weapon and a piece of the defender's armour
+                wear_equipment(weapon_entity, wants_melee.target, &entities, &equipped_items, &wearables);
+
                 // indicate that the attack was successful
                 gamelog::Logger::new()
                     .character_name(&name.name)
@@ -199,7 +194,9 @@ impl<'a> System<'a> for MeleeCombatSystem {
                             if let Some(aoe) = area_of_effect.get(weapon_entity.unwrap()) {
                                 if let Some(pos) = positions.get(wants_melee.target) {
                                     // TODO remove effect creator from target list
-                                    effect_target = Targets::Tiles{ tiles: aoe_tiles(&*map, Point{ x: pos.x, y: pos.y }, aoe.radius) }
+                                    let target_point = Point{ x: pos.x, y: pos.y };
+                                    let origin = positions.get(entity).map_or(target_point, |origin| Point{ x: origin.x, y: origin.y });
+                                    effect_target = Targets::Tiles{ tiles: aoe_tiles(&*map, origin, target_point, aoe) }
                                 }
                             }
                         }
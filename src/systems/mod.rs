@@ -4,6 +4,7 @@ mod inventory;
 mod hunger_system;
 mod level_up_character_system;
 mod ability_systems;
+mod combat;
 mod lighting_system;
 mod map_indexing_system;
 mod melee_combat_system;
@@ -17,6 +18,11 @@ mod gear_effect_system;
 mod status_effect_system;
 pub mod saveload_system;
 mod quest_systems;
+mod hazard_system;
+mod trap_system;
+mod weather_system;
+mod swimming_system;
+mod lock_system;
 
 pub use dispatcher::UnifiedDispatcher;
 use ai::*;
@@ -37,6 +43,11 @@ use gear_effect_system::GearEffectSystem;
 use status_effect_system::StatusEffectSystem;
 pub use saveload_system::*;
 use quest_systems::*;
+use hazard_system::HazardSystem;
+use trap_system::*;
+use weather_system::WeatherSystem;
+use swimming_system::SwimmingSystem;
+use lock_system::LockSystem;
 
 pub fn build() -> Box<dyn UnifiedDispatcher + 'static> {
     dispatcher::new()
@@ -1,6 +1,6 @@
 use specs::prelude::*;
 use crate::{spatial, Map, Position, ApplyMove, ApplyTeleport, OtherLevelPosition, EntityMoved,
-    Viewshed, RunState};
+    Viewshed, RunState, TileSize};
 
 pub struct MovementSystem {}
 
@@ -15,13 +15,14 @@ impl<'a> System<'a> for MovementSystem {
         WriteStorage<'a, EntityMoved>,
         WriteStorage<'a, Viewshed>,
         ReadExpect<'a, Entity>,
-        WriteExpect<'a, RunState>
+        WriteExpect<'a, RunState>,
+        ReadStorage<'a, TileSize>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (map, mut positions, entities, mut apply_move,
             mut apply_teleport, mut other_level, mut moved,
-            mut viewsheds, player_entity, mut runstate) = data;
+            mut viewsheds, player_entity, mut runstate, tile_sizes) = data;
 
         // apply teleporting
         for (entity, teleport) in (&entities, &apply_teleport).join() {
@@ -32,7 +33,8 @@ impl<'a> System<'a> for MovementSystem {
             } else if let Some(pos) = positions.get(entity) {
                 let idx = map.xy_idx(pos.x, pos.y);
                 let dest_idx = map.xy_idx(teleport.dest_x, teleport.dest_y);
-                spatial::move_entity(entity, idx, dest_idx);
+                let size = tile_sizes.get(entity);
+                spatial::move_footprint(entity, &map.footprint(idx, size), &map.footprint(dest_idx, size));
                 other_level.insert(entity, OtherLevelPosition{
                     x: teleport.dest_x,
                     y: teleport.dest_y,
@@ -47,7 +49,8 @@ impl<'a> System<'a> for MovementSystem {
         for (entity, movement, pos) in (&entities, &apply_move, &mut positions).join() {
             let start_idx = map.xy_idx(pos.x, pos.y);
             let dest_idx = movement.dest_idx as usize;
-            spatial::move_entity(entity, start_idx, dest_idx);
+            let size = tile_sizes.get(entity);
+            spatial::move_footprint(entity, &map.footprint(start_idx, size), &map.footprint(dest_idx, size));
             pos.x = movement.dest_idx as i32 % map.width;
             pos.y = movement.dest_idx as i32 / map.width;
             if let Some(vs) = viewsheds.get_mut(entity) {
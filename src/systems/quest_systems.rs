@@ -3,7 +3,7 @@ use specs::prelude::*;
 use crate::effects::add_effect;
 use crate::{ActiveQuests, ProgressSource, QuestProgress, QuestRequirementGoal, WantsToTurnInQuest,
     Pools, Quests, Point, Map, RunState, WantsToLevelUp, CharacterClass, QuestStatus, Name, Species,
-    determine_roll, player_xp_for_level};
+    determine_roll, player_xp_for_level, Cursed, Equipped, InBackpack};
 use crate::gamelog;
 use crate::effects;
 
@@ -77,13 +77,16 @@ impl<'a> System<'a> for QuestTurnInSystem {
         WriteExpect<'a, ActiveQuests>,
         ReadExpect<'a, Point>,
         ReadExpect<'a, Map>,
-        WriteExpect<'a, RunState>
+        WriteExpect<'a, RunState>,
+        WriteStorage<'a, Cursed>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, InBackpack>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (player, entities, mut wants_turn_in, mut level_ups,
             character_classes, mut pools, mut quests, mut active_quests,
-            player_pos, map, mut runstate) = data;
+            player_pos, map, mut runstate, mut cursed, equipped, backpacks) = data;
 
         if wants_turn_in.is_empty() { return; }
 
@@ -105,13 +108,24 @@ impl<'a> System<'a> for QuestTurnInSystem {
                             gamelog::Logger::new().append(format!("You receive {} xp", xp)).log();
                             if pool.xp >= player_xp_for_level(pool.level) {
                                 let player_class = character_classes.get(entity).unwrap();
-                                level_ups.insert(entity, WantsToLevelUp {
-                                    passives: player_class.passives.clone()
-                                }).expect("Unable to insert");
+                                level_ups.insert(entity, WantsToLevelUp::new(player_class.passives.clone())).expect("Unable to insert");
                                 *runstate = RunState::LevelUp;
                             }
                         }
                     }
+                    if reward.remove_curses {
+                        let cursed_items: Vec<Entity> = (&entities, &cursed).join()
+                            .filter(|(item_entity, _)| {
+                                equipped.get(*item_entity).is_some_and(|e| e.owner == entity)
+                                || backpacks.get(*item_entity).is_some_and(|b| b.owner == entity)
+                            })
+                            .map(|(item_entity, _)| item_entity)
+                            .collect();
+                        cursed_items.iter().for_each(|item_entity| { cursed.remove(*item_entity); });
+                        if entity == *player && !cursed_items.is_empty() {
+                            gamelog::Logger::new().append("You feel a malevolent aura lift.").log();
+                        }
+                    }
                 }
             }
             
@@ -1,7 +1,8 @@
 use specs::prelude::*;
 use crate::{Attributes, Skills, WantsToShoot, Name, Position, HungerClock, HungerState, Pools, 
     Equipped, Weapon, AreaOfEffect, EquipmentSlot, WeaponAttribute, Wearable, NaturalAttackDefence, 
-    Map, Dodge, Block};
+    Map, Dodge, Block, PassiveBonus};
+use super::combat::{avoids_hit, wear_equipment};
 use crate::effects::{add_effect, aoe_tiles, EffectType, Targets};
 use rltk::{RGB, Point};
 use crate::gamelog;
@@ -26,14 +27,15 @@ impl<'a> System<'a> for RangedCombatSystem {
         ReadStorage<'a, AreaOfEffect>,
         ReadStorage<'a, Dodge>,
         ReadStorage<'a, Block>,
-        WriteExpect<'a, Map>
+        WriteExpect<'a, Map>,
+        ReadStorage<'a, PassiveBonus>
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (entities, mut wants_shoots, names, attributes, 
             skills, pools, positions, hunger_clock, 
             equipped_items, weapons, wearables, natural,
-            area_of_effect, dodges, blocks, map) = data;
+            area_of_effect, dodges, blocks, map, passive_bonuses) = data;
 
         for (entity, wants_shoot, name, attacker_attributes, attacker_skills, attacker_pools) in (&entities, &wants_shoots, &names, &attributes, &skills, &pools).join() {
             let target_pools = pools.get(wants_shoot.target).unwrap();
@@ -91,10 +93,10 @@ impl<'a> System<'a> for RangedCombatSystem {
 
             // weapon attack ability of attacker
             let mut weapon_entity: Option<Entity> = None;
-            for (entity, wielded, weapon) in (&entities, &equipped_items, &weapons).join() {
+            for (weapon_e, wielded, weapon) in (&entities, &equipped_items, &weapons).join() {
                 if wielded.owner == entity && weapon.range.is_some() && (wielded.slot == EquipmentSlot::MainHand || wielded.slot == EquipmentSlot::TwoHanded) {
                     weapon_info = weapon.clone();
-                    weapon_entity = Some(entity);
+                    weapon_entity = Some(weapon_e);
                 }
             }
 
@@ -114,8 +116,9 @@ impl<'a> System<'a> for RangedCombatSystem {
                     status_hit_bonus += 1;
                 }
             }
+            let weather_penalty = map.weather.ranged_hit_penalty();
             let modified_hit_roll = natural_roll + attribute_hit_bonus + skill_hit_bonus
-                + weapon_hit_bonus + status_hit_bonus;
+                + weapon_hit_bonus + status_hit_bonus - weather_penalty;
             
             // natural defence ability of defender
             let base_armour_class = match natural.get(wants_shoot.target) {
@@ -144,29 +147,8 @@ impl<'a> System<'a> for RangedCombatSystem {
                 // hit
 
                 // check if target has the ability to avoid damage from the attack
-                if let Some(dodge) = dodges.get(wants_shoot.target) {
-                    if rng::roll_dice(1, 100) <= (dodge.chance * 100.0) as i32 {
-                        gamelog::Logger::new()
-                            .character_name(&names.get(wants_shoot.target).unwrap().name)
-                            .ability_name("dodges")
-                            .append("attack from")
-                            .character_name(&names.get(entity).unwrap().name)
-                            .append("!")
-                            .log();
-                        continue;
-                    }
-                }
-                if let Some(block) = blocks.get(wants_shoot.target) {
-                    if rng::roll_dice(1, 100) <= (block.chance * 100.0) as i32 {
-                        gamelog::Logger::new()
-                            .character_name(&names.get(wants_shoot.target).unwrap().name)
-                            .ability_name("blocks")
-                            .append("attack from")
-                            .character_name(&names.get(entity).unwrap().name)
-                            .append("!")
-                            .log();
-                        continue;
-                    }
+                if avoids_hit(wants_shoot.target, &names.get(wants_shoot.target).unwrap().name, &names.get(entity).unwrap().name, &dodges, &blocks, &passive_bonuses) {
+                    continue;
                 }
 
                 // calculate damage
@@ -186,6 +168,9 @@ impl<'a> System<'a> for RangedCombatSystem {
                     EffectType::Damage{ amount: damage, hits_self: false },
                     Targets::Single{ target: wants_shoot.target }
                 );
+
+                // wear down the attacker's weapon and a piece of the defender's armour
+                wear_equipment(weapon_entity, wants_shoot.target, &entities, &equipped_items, &wearables);
                 
                 gamelog::Logger::new()
                     .character_name(&name.name)
@@ -220,7 +205,9 @@ impl<'a> System<'a> for RangedCombatSystem {
                             if let Some(aoe) = area_of_effect.get(weapon_entity.unwrap()) {
                                 if let Some(pos) = positions.get(wants_shoot.target) {
                                     // TODO remove effect creator from target list
-                                    effect_target = Targets::Tiles{ tiles: aoe_tiles(&*map, rltk::Point{ x: pos.x, y: pos.y }, aoe.radius) }
+                                    let target_point = rltk::Point{ x: pos.x, y: pos.y };
+                                    let origin = positions.get(entity).map_or(target_point, |origin| rltk::Point{ x: origin.x, y: origin.y });
+                                    effect_target = Targets::Tiles{ tiles: aoe_tiles(&*map, origin, target_point, aoe) }
                                 }
                             }
                         }
@@ -33,12 +33,14 @@ pub fn save_game(ecs : &mut World) {
     let quests_copy = ecs.get_mut::<Quests>().unwrap().clone();
     let activequests_copy = ecs.get_mut::<ActiveQuests>().unwrap().clone();
     let dungeonmaster_copy = ecs.get_mut::<MasterDungeonMap>().unwrap().clone();
+    let clock_copy = ecs.get_mut::<GameClock>().unwrap().clone();
     let save_helper = ecs
         .create_entity()
         .with(SerializationHelper{
             map: map_copy,
             quests: quests_copy,
-            active_quests: activequests_copy
+            active_quests: activequests_copy,
+            clock: clock_copy
         })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
@@ -68,7 +70,9 @@ pub fn save_game(ecs : &mut World) {
             KnownAbility, AttributeBonus, WantsToUseAbility, RestoresMana, TeachesAbility, Slow, DamageOverTime, TileSize,
             WantsToLevelUp, SkillBonus, ItemSets, PartOfSet, Target, WantsToShoot, Stun, StatusEffectChanged, Boss, Chest,
             CharacterClass, SelfDamage, Rage, Block, Fortress, FrostShield, Dodge, WantsToLearnAbility, WantsToLevelAbility,
-            QuestProgress, QuestGiver, WantsToTurnInQuest, MapMarker, Species, Equipped, Repeat, RegenBonus
+            QuestProgress, QuestGiver, WantsToTurnInQuest, MapMarker, Species, Equipped, Repeat, RegenBonus, MagicItem,
+            Identify, Cursed, RemoveCurse, Durability, Gatherable, CraftingStation, RacialPassive, FactionAttitudes, PassiveBonus, Hireable, Companion, Summon, CreatesHazard, Hidden, Trap, Pit, Alarm, RandomTeleport, Nocturnal, Swimmer, Swimming, WaterBreathing, Boat, Digger, Tunnels,
+            Breakable, Smasher, Locked, Key, Lockpick, WaterDamageable
         );
     }
 
@@ -126,7 +130,9 @@ pub fn load_game(ecs: &mut World) {
             KnownAbility, AttributeBonus, WantsToUseAbility, RestoresMana, TeachesAbility, Slow, DamageOverTime, TileSize,
             WantsToLevelUp, SkillBonus, ItemSets, PartOfSet, Target, WantsToShoot, Stun, StatusEffectChanged, Boss, Chest,
             CharacterClass, SelfDamage, Rage, Block, Fortress, FrostShield, Dodge, WantsToLearnAbility, WantsToLevelAbility,
-            QuestProgress, QuestGiver, WantsToTurnInQuest, MapMarker, Species, Equipped, Repeat, RegenBonus
+            QuestProgress, QuestGiver, WantsToTurnInQuest, MapMarker, Species, Equipped, Repeat, RegenBonus, MagicItem,
+            Identify, Cursed, RemoveCurse, Durability, Gatherable, CraftingStation, RacialPassive, FactionAttitudes, PassiveBonus, Hireable, Companion, Summon, CreatesHazard, Hidden, Trap, Pit, Alarm, RandomTeleport, Nocturnal, Swimmer, Swimming, WaterBreathing, Boat, Digger, Tunnels,
+            Breakable, Smasher, Locked, Key, Lockpick, WaterDamageable
         );
     }
 
@@ -146,6 +152,8 @@ pub fn load_game(ecs: &mut World) {
             *quests = h.quests.clone();
             let mut activequests = ecs.write_resource::<ActiveQuests>();
             *activequests = h.active_quests.clone();
+            let mut clock = ecs.write_resource::<GameClock>();
+            *clock = h.clock.clone();
             spatial::set_size((worldmap.height * worldmap.width) as usize);
             deleteme = Some(e);
         }
@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use specs::prelude::*;
+use crate::{gamelog, rng, boat_at, carry_capacity_lbs, Attributes, Boat, EquipmentChanged, Equipped, InBackpack, Item, Map,
+    Pools, Position, RunState, StatusEffect, Swimming, TileType, WaterBreathing, WaterDamageable};
+use crate::effects::{add_effect, EffectType, Targets};
+
+const BASE_SWIM_TURNS: i32 = 10;
+const HEAVY_GEAR_FRACTION: f32 = 0.5;
+const HEAVY_GEAR_DIFFICULTY: i32 = 10;
+const SOAK_CHANCE: i32 = 5;
+
+pub struct SwimmingSystem {}
+
+impl<'a> System<'a> for SwimmingSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Map>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Pools>,
+        ReadStorage<'a, Attributes>,
+        WriteStorage<'a, Swimming>,
+        ReadStorage<'a, Boat>,
+        ReadStorage<'a, StatusEffect>,
+        ReadStorage<'a, WaterBreathing>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, InBackpack>,
+        ReadStorage<'a, Item>,
+        WriteStorage<'a, EquipmentChanged>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>,
+        ReadStorage<'a, WaterDamageable>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, map, positions, pools, attributes, mut swimming, boats, statuses,
+            water_breathing, equipped, backpack, items, mut equipment_changed, player_entity, runstate, water_damageable) = data;
+
+        // swimming is checked once per turn
+        if *runstate != RunState::AwaitingInput { return; }
+
+        let breathers: HashSet<Entity> = (&statuses, &water_breathing).join()
+            .map(|(status, _wb)| status.target)
+            .collect();
+
+        let mut soaked: Vec<Entity> = Vec::new();
+        for (entity, pos, pool) in (&entities, &positions, &pools).join() {
+            let idx = map.xy_idx(pos.x, pos.y);
+            let is_player = entity == *player_entity;
+            if map.tiles[idx] != TileType::DeepWater || boat_at(&boats, idx).is_some() {
+                if swimming.remove(entity).is_some() && is_player {
+                    gamelog::Logger::new().append("You climb out of the water.").log();
+                }
+                continue;
+            }
+
+            if swimming.get(entity).is_none() {
+                swimming.insert(entity, Swimming{ turns: 0 }).expect("Unable to insert");
+                if is_player {
+                    gamelog::Logger::new().colour(rltk::RGB::named(rltk::CYAN)).append("You start swimming.").log();
+                }
+            }
+            let swim = swimming.get_mut(entity).unwrap();
+            swim.turns += 1;
+
+            // carried gear can get soaked
+            if is_player {
+                for (item_entity, equip) in (&entities, &equipped).join() {
+                    if equip.owner == entity && rng::roll_dice(1, 100) <= SOAK_CHANCE {
+                        add_effect(None, EffectType::Wear{ amount: 1 }, Targets::Single{ target: item_entity });
+                    }
+                }
+                for (item_entity, carried, item, _damageable) in (&entities, &backpack, &items, &water_damageable).join() {
+                    if carried.owner == entity && rng::roll_dice(1, 100) <= SOAK_CHANCE {
+                        gamelog::Logger::new()
+                            .append("The ink runs on your")
+                            .item_name(item)
+                            .colour(rltk::RGB::named(rltk::RED))
+                            .append("- it is ruined!")
+                            .log();
+                        soaked.push(item_entity);
+                    }
+                }
+            }
+
+            if breathers.contains(&entity) { continue; }
+
+            let (endurance, strength_bonus, heavy) = match attributes.get(entity) {
+                Some(attr) => (
+                    BASE_SWIM_TURNS + attr.constitution.bonus * 2,
+                    attr.strength.bonus,
+                    pool.total_weight > carry_capacity_lbs(&attr.strength) * HEAVY_GEAR_FRACTION
+                ),
+                None => (BASE_SWIM_TURNS, 0, false)
+            };
+
+            if heavy && rng::roll_dice(1, 20) + strength_bonus < HEAVY_GEAR_DIFFICULTY {
+                add_effect(None, EffectType::Damage{ amount: rng::roll_dice(1, 6), hits_self: false }, Targets::Single{ target: entity });
+                if is_player {
+                    gamelog::Logger::new().colour(rltk::RGB::named(rltk::ORANGE)).append("Your heavy gear drags you under!").log();
+                }
+            } else if swim.turns > endurance {
+                let damage = rng::roll_dice(1, 4) + (swim.turns - endurance) / 2;
+                add_effect(None, EffectType::Damage{ amount: damage, hits_self: false }, Targets::Single{ target: entity });
+                if is_player {
+                    gamelog::Logger::new().colour(rltk::RGB::named(rltk::ORANGE)).append("You are drowning!").log();
+                }
+            } else if is_player && swim.turns == endurance - 2 {
+                gamelog::Logger::new().append("You are tiring in the water.").log();
+            }
+        }
+
+        for item_entity in soaked {
+            equipment_changed.insert(*player_entity, EquipmentChanged{}).expect("Unable to insert");
+            entities.delete(item_entity).expect("Unable to delete");
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use specs::prelude::*;
+use rltk::Point;
+use crate::{gamelog, rng, grant_xp, Attributes, CharacterClass, Hidden, Map, Name, Pools, Position, RunState,
+    Trap, Viewshed, WantsToDisarm, WantsToLevelUp};
+use crate::effects::{add_effect, EffectType, Targets, SECRET_DOOR_DIFFICULTY};
+
+// how close a hidden trap has to be to be noticed without searching
+const DETECTION_RANGE: f32 = 3.0;
+
+pub struct TrapDetectionSystem {}
+
+impl<'a> System<'a> for TrapDetectionSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Map>,
+        ReadStorage<'a, Viewshed>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Attributes>,
+        ReadStorage<'a, Trap>,
+        ReadStorage<'a, Name>,
+        WriteStorage<'a, Hidden>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, player_entity, runstate, map, viewsheds, positions, attributes,
+            traps, names, mut hidden) = data;
+
+        // passive perception happens once per turn
+        if *runstate != RunState::AwaitingInput { return; }
+
+        let player_pos = positions.get(*player_entity).unwrap();
+        let player_point = Point::new(player_pos.x, player_pos.y);
+        let viewshed = viewsheds.get(*player_entity).unwrap();
+        let intelligence = attributes.get(*player_entity).map_or(0, |attr| attr.intelligence.bonus);
+
+        let mut spotted: Vec<Entity> = Vec::new();
+        for (entity, trap, pos, _hidden) in (&entities, &traps, &positions, &hidden).join() {
+            let trap_point = Point::new(pos.x, pos.y);
+            if rltk::DistanceAlg::Pythagoras.distance2d(player_point, trap_point) > DETECTION_RANGE { continue; }
+            if !viewshed.visible_tiles.contains(&trap_point) { continue; }
+
+            if rng::roll_dice(1, 20) + intelligence >= trap.difficulty {
+                spotted.push(entity);
+            }
+        }
+
+        for entity in spotted {
+            hidden.remove(entity);
+            if let Some(name) = names.get(entity) {
+                gamelog::Logger::new()
+                    .colour(rltk::RGB::named(rltk::ORANGE))
+                    .append(format!("You spot a {}!", name.name))
+                    .log();
+            }
+        }
+
+        // secret doors are only noticed when standing next to them
+        for idx in map.secret_doors.iter() {
+            let (x, y) = map.idx_xy(*idx);
+            if rltk::DistanceAlg::Pythagoras.distance2d(player_point, Point::new(x, y)) > 1.5 { continue; }
+
+            if rng::roll_dice(1, 20) + intelligence >= SECRET_DOOR_DIFFICULTY {
+                add_effect(None, EffectType::RevealSecretDoor, Targets::Tile{ tile_idx: *idx as i32 });
+            }
+        }
+    }
+}
+
+pub struct TrapDisarmSystem {}
+
+impl<'a> System<'a> for TrapDisarmSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, RunState>,
+        WriteStorage<'a, WantsToDisarm>,
+        ReadStorage<'a, Trap>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Attributes>,
+        WriteStorage<'a, Pools>,
+        ReadStorage<'a, CharacterClass>,
+        WriteStorage<'a, WantsToLevelUp>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, player_entity, mut runstate, mut wants_disarm, traps, names,
+            attributes, mut pools, character_classes, mut level_ups) = data;
+
+        for (entity, disarm) in (&entities, &wants_disarm).join() {
+            let trap = match traps.get(disarm.trap) {
+                Some(trap) => trap,
+                None => continue
+            };
+            let trap_name = names.get(disarm.trap).map_or("trap".to_string(), |n| n.name.clone());
+
+            let bonus = attributes.get(entity).map_or(0, |attr| attr.dexterity.bonus + attr.intelligence.bonus);
+            let roll = rng::roll_dice(1, 20) + bonus;
+
+            if roll >= trap.difficulty {
+                gamelog::Logger::new().append(format!("You disarm the {}.", trap_name)).log();
+                entities.delete(disarm.trap).expect("Delete failed");
+
+                if entity == *player_entity {
+                    if let Some(pool) = pools.get_mut(entity) {
+                        let xp = trap.difficulty * 10;
+                        gamelog::Logger::new().append(format!("You receive {} xp", xp)).log();
+                        grant_xp(entity, pool, xp, &character_classes, &mut level_ups, &mut runstate);
+                    }
+                }
+            } else if roll < trap.difficulty - 5 {
+                gamelog::Logger::new()
+                    .colour(rltk::RGB::named(rltk::RED))
+                    .append(format!("You set off the {}!", trap_name))
+                    .log();
+                add_effect(
+                    Some(entity),
+                    EffectType::TriggerFire{ trigger: disarm.trap },
+                    Targets::Single{ target: entity }
+                );
+            } else {
+                gamelog::Logger::new().append(format!("You fail to disarm the {}.", trap_name)).log();
+            }
+        }
+
+        wants_disarm.clear();
+    }
+}
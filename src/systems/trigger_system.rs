@@ -1,5 +1,6 @@
 use specs::prelude::*;
-use crate::{EntityMoved, Position, EntryTrigger, Map, Name};
+use rltk::Point;
+use crate::{EntityMoved, Position, EntryTrigger, Map, Name, Hidden, AreaOfEffect};
 use crate::effects::*;
 use crate::gamelog;
 
@@ -12,12 +13,14 @@ impl<'a> System<'a> for TriggerSystem {
         ReadStorage<'a, Position>,
         ReadStorage<'a, EntryTrigger>,
         ReadStorage<'a, Name>,
-        Entities<'a>
+        Entities<'a>,
+        WriteStorage<'a, Hidden>,
+        ReadStorage<'a, AreaOfEffect>
     );
 
     fn run(&mut self, data : Self::SystemData) {
         let (map, mut entity_moved, position, entry_trigger, names, 
-            entities) = data;
+            entities, mut hidden, area_of_effect) = data;
 
         // Iterate the entities that moved and their final position
         for (entity, mut _entity_moved, pos) in (&entities, &mut entity_moved, &position).join() {
@@ -34,10 +37,20 @@ impl<'a> System<'a> for TriggerSystem {
                                 gamelog::Logger::new().append(format!("{} triggers!", &name.name)).log();
                             }
 
+                            // a sprung trap is no longer hidden
+                            hidden.remove(e);
+
+                            let targets = match area_of_effect.get(e) {
+                                Some(aoe) => {
+                                    let origin = Point::new(pos.x, pos.y);
+                                    Targets::Tiles{ tiles: aoe_tiles(&map, origin, origin, aoe) }
+                                }
+                                None => Targets::Tile{ tile_idx: idx as i32 }
+                            };
                             add_effect(
                                 Some(entity),
                                 EffectType::TriggerFire{ trigger: e },
-                                Targets::Tile{ tile_idx: idx as i32 }
+                                targets
                             );
                         }
                     }
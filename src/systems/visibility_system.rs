@@ -1,9 +1,11 @@
 use specs::prelude::*;
 use crate::BlocksVisibility;
 
-use crate::{Viewshed, Position, Map, Player};
+use crate::{Viewshed, Position, Map, Player, RacialPassive, GameClock, LightSource, Nocturnal};
 use rltk::{field_of_view, Point};
 
+const NIGHT_VISION_RANGE: i32 = 5;
+
 pub struct VisibilitySystem {}
 
 impl<'a> System<'a> for VisibilitySystem {
@@ -13,12 +15,17 @@ impl<'a> System<'a> for VisibilitySystem {
         WriteStorage<'a, Viewshed>,
         WriteStorage<'a, Position>,
         ReadStorage<'a, Player>,
-        ReadStorage<'a, BlocksVisibility>
+        ReadStorage<'a, BlocksVisibility>,
+        ReadStorage<'a, RacialPassive>,
+        ReadExpect<'a, GameClock>,
+        ReadStorage<'a, LightSource>,
+        ReadStorage<'a, Nocturnal>
     );
 
     fn run(&mut self, data : Self::SystemData) {
         let (mut map, entities, mut viewshed, pos, player,
-            blocks_visibility) = data;
+            blocks_visibility, racial_passives, clock, light_sources, nocturnal) = data;
+        let dark_outside = !map.indoors && clock.is_dark();
 
         map.view_blocked.clear();
         for (block_pos, _block) in (&pos, &blocks_visibility).join() {
@@ -30,7 +37,20 @@ impl<'a> System<'a> for VisibilitySystem {
             if viewshed.dirty {
                 viewshed.dirty = false;
                 viewshed.visible_tiles.clear();
-                viewshed.visible_tiles = field_of_view(Point::new(pos.x, pos.y), viewshed.range, &*map);
+                let mut range = viewshed.range;
+                // outdoors at night only nocturnal creatures see further than their own light
+                if dark_outside && nocturnal.get(ent).is_none() {
+                    let light_range = light_sources.get(ent).map_or(0, |light| light.range);
+                    range = i32::min(range, i32::max(NIGHT_VISION_RANGE, light_range));
+                }
+                if let Some(fog_range) = map.weather.sight_range() {
+                    range = i32::min(range, fog_range);
+                }
+                // darkvision only helps in the dark
+                if let Some(passive) = racial_passives.get(ent) {
+                    if map.indoors || dark_outside { range += passive.darkvision; }
+                }
+                viewshed.visible_tiles = field_of_view(Point::new(pos.x, pos.y), range, &*map);
                 viewshed.visible_tiles.retain(|p| p.x >= 0 && p.x < map.width && p.y >= 0 && p.y < map.height );
 
                 if player.get(ent).is_some() {
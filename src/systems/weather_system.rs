@@ -0,0 +1,74 @@
+use specs::prelude::*;
+use rltk::{Point, RGB};
+use crate::{gamelog, rng, tile_water, HazardType, Map, RunState, Weather};
+use crate::effects::{add_effect, EffectType, Targets};
+use crate::raws::{roll_weather_for_map, RAWS};
+
+const WEATHER_CHANGE_CHANCE: i32 = 300;
+const RAIN_EXTINGUISH_CHANCE: i32 = 50;
+const LIGHTNING_STRIKE_CHANCE: i32 = 15;
+const LIGHTNING_STRIKE_RANGE: i32 = 12;
+
+pub struct WeatherSystem {}
+
+impl<'a> System<'a> for WeatherSystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Point>
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut map, runstate, player_pos) = data;
+
+        // weather changes once per turn and only outdoors
+        if *runstate != RunState::AwaitingInput { return; }
+        if map.indoors { return; }
+
+        if rng::roll_dice(1, WEATHER_CHANGE_CHANCE) == 1 {
+            let weather = roll_weather_for_map(&RAWS.lock().unwrap(), &map.name);
+            if weather != map.weather {
+                map.weather = weather;
+                gamelog::Logger::new().colour(RGB::named(rltk::CYAN)).append(weather.start_message()).log();
+            }
+        }
+
+        // rain puts out fires
+        if map.weather.is_wet() {
+            let fires: Vec<usize> = map.hazards.iter()
+                .filter(|(_, hazard)| hazard.hazard_type == HazardType::Fire)
+                .map(|(idx, _)| *idx)
+                .collect();
+            for idx in fires {
+                if rng::roll_dice(1, 100) <= RAIN_EXTINGUISH_CHANCE {
+                    map.remove_hazard(idx);
+                }
+            }
+        }
+
+        // storms bring lightning strikes
+        if map.weather == Weather::Storm && rng::roll_dice(1, LIGHTNING_STRIKE_CHANCE) == 1 {
+            let x = player_pos.x + rng::range(-LIGHTNING_STRIKE_RANGE, LIGHTNING_STRIKE_RANGE + 1);
+            let y = player_pos.y + rng::range(-LIGHTNING_STRIKE_RANGE, LIGHTNING_STRIKE_RANGE + 1);
+            if x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 { return; }
+            let idx = map.xy_idx(x, y);
+
+            add_effect(
+                None,
+                EffectType::Particle{ glyph: rltk::to_cp437('‼'), fg: RGB::named(rltk::YELLOW), bg: RGB::named(rltk::BLACK), lifespan: 300.0 },
+                Targets::Tile{ tile_idx: idx as i32 }
+            );
+            add_effect(
+                None,
+                EffectType::Damage{ amount: rng::roll_dice(2, 6), hits_self: false },
+                Targets::Tile{ tile_idx: idx as i32 }
+            );
+            if tile_water(&map.tiles[idx]) {
+                add_effect(None, EffectType::Hazard{ hazard_type: HazardType::Lightning, turns: 3 }, Targets::Tile{ tile_idx: idx as i32 });
+            }
+            if map.visible_tiles[idx] {
+                gamelog::Logger::new().colour(RGB::named(rltk::YELLOW)).append("Lightning strikes nearby!").log();
+            }
+        }
+    }
+}